@@ -0,0 +1,301 @@
+//! Assemble small class files, for testing code that reads a
+//! [`CodeBundle`](super::minecraft_code::CodeBundle) without a Minecraft jar.
+
+use cafebabe::{FieldAccessFlags, MethodAccessFlags};
+
+/// A builder for a minimal class file.
+///
+/// ```rust,ignore
+/// use cafebabe::MethodAccessFlags;
+/// use froglight_dependency::dependency::minecraft::class_builder::ClassBuilder;
+///
+/// let class = ClassBuilder::new("Items")
+///     .method(MethodAccessFlags::STATIC, "<clinit>", "()V", |code| {
+///         code.ldc_string("stone");
+///         code.invokestatic("Items", "register", "(Ljava/lang/String;)LItem;");
+///         code.putstatic("Items", "STONE", "LItem;");
+///         code.return_();
+///     })
+///     .build();
+///
+/// let parsed = cafebabe::parse_class(&class).unwrap();
+/// assert_eq!(&*parsed.this_class, "Items");
+/// ```
+#[derive(Debug, Clone)]
+pub struct ClassBuilder {
+    pool: ConstantPool,
+    this_class: u16,
+    super_class: u16,
+    interfaces: Vec<u16>,
+    fields: Vec<u8>,
+    field_count: u16,
+    methods: Vec<u8>,
+    method_count: u16,
+}
+
+/// Writes the bytecode of a method in a [`ClassBuilder`].
+#[derive(Debug)]
+pub struct CodeWriter<'a> {
+    pool: &'a mut ConstantPool,
+    code: Vec<u8>,
+}
+
+#[derive(Debug, Default, Clone)]
+struct ConstantPool {
+    bytes: Vec<u8>,
+    count: u16,
+}
+
+impl ClassBuilder {
+    /// Create a public class extending `java/lang/Object`.
+    #[must_use]
+    pub fn new(name: &str) -> Self {
+        let mut pool = ConstantPool::default();
+        let this_class = pool.class(name);
+        let super_class = pool.class("java/lang/Object");
+
+        Self {
+            pool,
+            this_class,
+            super_class,
+            interfaces: Vec::new(),
+            fields: Vec::new(),
+            field_count: 0,
+            methods: Vec::new(),
+            method_count: 0,
+        }
+    }
+
+    /// Set the superclass of the class.
+    #[must_use]
+    pub fn with_super(mut self, super_class: &str) -> Self {
+        self.super_class = self.pool.class(super_class);
+        self
+    }
+
+    /// Add an interface the class implements.
+    #[must_use]
+    pub fn with_interface(mut self, interface: &str) -> Self {
+        let index = self.pool.class(interface);
+        self.interfaces.push(index);
+        self
+    }
+
+    /// Add a field to the class.
+    #[must_use]
+    pub fn field(mut self, flags: FieldAccessFlags, name: &str, descriptor: &str) -> Self {
+        let (name, descriptor) = (self.pool.utf8(name), self.pool.utf8(descriptor));
+        for value in [flags.bits(), name, descriptor, 0] {
+            self.fields.extend(value.to_be_bytes());
+        }
+        self.field_count += 1;
+        self
+    }
+
+    /// Add a method with bytecode to the class.
+    ///
+    /// # Panics
+    /// Panics if the bytecode is larger than a class file allows.
+    #[must_use]
+    pub fn method(
+        mut self,
+        flags: MethodAccessFlags,
+        name: &str,
+        descriptor: &str,
+        code: impl FnOnce(&mut CodeWriter<'_>),
+    ) -> Self {
+        let mut writer = CodeWriter { pool: &mut self.pool, code: Vec::new() };
+        code(&mut writer);
+        let code = writer.code;
+
+        let attribute = self.pool.utf8("Code");
+        self.method_header(flags, name, descriptor, 1);
+        self.methods.extend(attribute.to_be_bytes());
+        self.methods.extend(u32::try_from(code.len() + 12).unwrap().to_be_bytes());
+        self.methods.extend(8u16.to_be_bytes());
+        self.methods.extend(8u16.to_be_bytes());
+        self.methods.extend(u32::try_from(code.len()).unwrap().to_be_bytes());
+        self.methods.extend(code);
+        self.methods.extend(0u16.to_be_bytes());
+        self.methods.extend(0u16.to_be_bytes());
+        self
+    }
+
+    /// Add a method without bytecode, such as an abstract method.
+    #[must_use]
+    pub fn abstract_method(
+        mut self,
+        flags: MethodAccessFlags,
+        name: &str,
+        descriptor: &str,
+    ) -> Self {
+        self.method_header(flags, name, descriptor, 0);
+        self
+    }
+
+    fn method_header(
+        &mut self,
+        flags: MethodAccessFlags,
+        name: &str,
+        descriptor: &str,
+        attributes: u16,
+    ) {
+        let (name, descriptor) = (self.pool.utf8(name), self.pool.utf8(descriptor));
+        for value in [flags.bits(), name, descriptor, attributes] {
+            self.methods.extend(value.to_be_bytes());
+        }
+        self.method_count += 1;
+    }
+
+    /// Write the class file.
+    ///
+    /// # Panics
+    /// Panics if the class has more interfaces than a class file allows.
+    #[must_use]
+    pub fn build(self) -> Vec<u8> {
+        let mut class = vec![0xCA, 0xFE, 0xBA, 0xBE, 0, 0, 0, 65];
+        class.extend((self.pool.count + 1).to_be_bytes());
+        class.extend(self.pool.bytes);
+
+        // `ACC_PUBLIC | ACC_SUPER`
+        class.extend(0x0021u16.to_be_bytes());
+        class.extend(self.this_class.to_be_bytes());
+        class.extend(self.super_class.to_be_bytes());
+
+        class.extend(u16::try_from(self.interfaces.len()).unwrap().to_be_bytes());
+        for interface in self.interfaces {
+            class.extend(interface.to_be_bytes());
+        }
+        class.extend(self.field_count.to_be_bytes());
+        class.extend(self.fields);
+        class.extend(self.method_count.to_be_bytes());
+        class.extend(self.methods);
+        class.extend(0u16.to_be_bytes());
+
+        class
+    }
+}
+
+impl CodeWriter<'_> {
+    /// Load a string constant.
+    pub fn ldc_string(&mut self, string: &str) {
+        let index = self.pool.string(string);
+        self.op_u16(0x13, index);
+    }
+
+    /// Load an integer constant.
+    pub fn ldc_int(&mut self, value: i32) {
+        let index = self.pool.entry(3, &value.to_be_bytes());
+        self.op_u16(0x13, index);
+    }
+
+    /// Push a small integer.
+    pub fn bipush(&mut self, value: i8) { self.code.extend([0x10, value.to_be_bytes()[0]]); }
+
+    /// Load an integer local variable.
+    pub fn iload(&mut self, index: u8) { self.code.extend([0x15, index]); }
+
+    /// Load a reference local variable.
+    pub fn aload(&mut self, index: u8) { self.code.extend([0x19, index]); }
+
+    /// Read a static field.
+    pub fn getstatic(&mut self, class: &str, name: &str, descriptor: &str) {
+        let index = self.pool.member(9, class, name, descriptor);
+        self.op_u16(0xB2, index);
+    }
+
+    /// Write a static field.
+    pub fn putstatic(&mut self, class: &str, name: &str, descriptor: &str) {
+        let index = self.pool.member(9, class, name, descriptor);
+        self.op_u16(0xB3, index);
+    }
+
+    /// Read an instance field.
+    pub fn getfield(&mut self, class: &str, name: &str, descriptor: &str) {
+        let index = self.pool.member(9, class, name, descriptor);
+        self.op_u16(0xB4, index);
+    }
+
+    /// Write an instance field.
+    pub fn putfield(&mut self, class: &str, name: &str, descriptor: &str) {
+        let index = self.pool.member(9, class, name, descriptor);
+        self.op_u16(0xB5, index);
+    }
+
+    /// Call a virtual method.
+    pub fn invokevirtual(&mut self, class: &str, name: &str, descriptor: &str) {
+        let index = self.pool.member(10, class, name, descriptor);
+        self.op_u16(0xB6, index);
+    }
+
+    /// Call a constructor, private method or super method.
+    pub fn invokespecial(&mut self, class: &str, name: &str, descriptor: &str) {
+        let index = self.pool.member(10, class, name, descriptor);
+        self.op_u16(0xB7, index);
+    }
+
+    /// Call a static method.
+    pub fn invokestatic(&mut self, class: &str, name: &str, descriptor: &str) {
+        let index = self.pool.member(10, class, name, descriptor);
+        self.op_u16(0xB8, index);
+    }
+
+    /// Create a new object.
+    pub fn new_object(&mut self, class: &str) {
+        let index = self.pool.class(class);
+        self.op_u16(0xBB, index);
+    }
+
+    /// Duplicate the top of the stack.
+    pub fn dup(&mut self) { self.code.push(0x59); }
+
+    /// Discard the top of the stack.
+    pub fn pop(&mut self) { self.code.push(0x57); }
+
+    /// Return an integer.
+    pub fn ireturn(&mut self) { self.code.push(0xAC); }
+
+    /// Return a reference.
+    pub fn areturn(&mut self) { self.code.push(0xB0); }
+
+    /// Return nothing.
+    pub fn return_(&mut self) { self.code.push(0xB1); }
+
+    fn op_u16(&mut self, opcode: u8, index: u16) {
+        self.code.push(opcode);
+        self.code.extend(index.to_be_bytes());
+    }
+}
+
+impl ConstantPool {
+    fn entry(&mut self, tag: u8, data: &[u8]) -> u16 {
+        self.bytes.push(tag);
+        self.bytes.extend(data);
+        self.count += 1;
+        self.count
+    }
+
+    fn utf8(&mut self, string: &str) -> u16 {
+        let mut data = u16::try_from(string.len()).unwrap().to_be_bytes().to_vec();
+        data.extend(string.as_bytes());
+        self.entry(1, &data)
+    }
+
+    fn class(&mut self, name: &str) -> u16 {
+        let name = self.utf8(name);
+        self.entry(7, &name.to_be_bytes())
+    }
+
+    fn string(&mut self, string: &str) -> u16 {
+        let string = self.utf8(string);
+        self.entry(8, &string.to_be_bytes())
+    }
+
+    fn member(&mut self, tag: u8, class: &str, name: &str, descriptor: &str) -> u16 {
+        let class = self.class(class);
+        let (name, descriptor) = (self.utf8(name), self.utf8(descriptor));
+        let name_and_type =
+            self.entry(12, &[name.to_be_bytes(), descriptor.to_be_bytes()].concat());
+        self.entry(tag, &[class.to_be_bytes(), name_and_type.to_be_bytes()].concat())
+    }
+}
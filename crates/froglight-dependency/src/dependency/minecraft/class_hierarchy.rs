@@ -0,0 +1,200 @@
+//! TODO
+
+use hashbrown::{HashMap, HashSet};
+
+/// An index of the class hierarchy of a
+/// [`CodeBundle`](super::minecraft_code::CodeBundle).
+///
+/// Only classes inside the bundle are indexed,
+/// so classes from the JDK such as `java/lang/Object`
+/// only appear as parents.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ClassHierarchy(HashMap<String, ClassNode>);
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+struct ClassNode {
+    super_class: Option<String>,
+    interfaces: Vec<String>,
+    subclasses: Vec<String>,
+    implementors: Vec<String>,
+}
+
+impl ClassHierarchy {
+    /// Build a [`ClassHierarchy`] from a list of
+    /// classes, their superclass, and their interfaces.
+    pub(crate) fn build<'a>(
+        classes: impl Iterator<Item = (&'a str, Option<&'a str>, Vec<&'a str>)>,
+    ) -> Self {
+        let mut hierarchy = Self::default();
+
+        for (class, super_class, interfaces) in classes {
+            if let Some(super_class) = super_class {
+                hierarchy.node(super_class).subclasses.push(class.to_string());
+            }
+            for interface in &interfaces {
+                hierarchy.node(interface).implementors.push(class.to_string());
+            }
+
+            let node = hierarchy.node(class);
+            node.super_class = super_class.map(str::to_string);
+            node.interfaces = interfaces.into_iter().map(str::to_string).collect();
+        }
+
+        for node in hierarchy.0.values_mut() {
+            node.subclasses.sort_unstable();
+            node.implementors.sort_unstable();
+        }
+
+        hierarchy
+    }
+
+    fn node(&mut self, class: &str) -> &mut ClassNode { self.0.entry_ref(class).or_default() }
+
+    /// Get the direct superclass of a class.
+    #[must_use]
+    pub fn superclass(&self, class: &str) -> Option<&str> {
+        self.0.get(class).and_then(|node| node.super_class.as_deref())
+    }
+
+    /// Iterate over the superclass chain of a class,
+    /// starting with its direct superclass.
+    pub fn superclasses<'a>(&'a self, class: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        let mut current = Some(class);
+        core::iter::from_fn(move || {
+            current = current.and_then(|class| self.superclass(class));
+            current
+        })
+    }
+
+    /// Get the interfaces a class directly implements.
+    #[must_use]
+    pub fn interfaces(&self, class: &str) -> &[String] {
+        self.0.get(class).map_or(&[], |node| node.interfaces.as_slice())
+    }
+
+    /// Get all interfaces a class implements,
+    /// including those of its superclasses and superinterfaces.
+    ///
+    /// Interfaces are ordered from most to least specific.
+    #[must_use]
+    pub fn all_interfaces<'a>(&'a self, class: &'a str) -> Vec<&'a str> {
+        let mut visited = HashSet::new();
+        let mut interfaces = Vec::new();
+
+        let mut queue: Vec<&str> =
+            core::iter::once(class).chain(self.superclasses(class)).collect();
+        let mut index = 0;
+        while let Some(current) = queue.get(index).copied() {
+            index += 1;
+            for interface in self.interfaces(current) {
+                if visited.insert(interface.as_str()) {
+                    interfaces.push(interface.as_str());
+                    queue.push(interface.as_str());
+                }
+            }
+        }
+
+        interfaces
+    }
+
+    /// Get the classes that directly extend a class.
+    #[must_use]
+    pub fn subclasses(&self, class: &str) -> &[String] {
+        self.0.get(class).map_or(&[], |node| node.subclasses.as_slice())
+    }
+
+    /// Get all classes that extend a class, directly or indirectly.
+    #[must_use]
+    pub fn all_subclasses<'a>(&'a self, class: &str) -> Vec<&'a str> {
+        let mut subclasses = Vec::new();
+
+        let mut queue: Vec<&str> = self.subclasses(class).iter().map(String::as_str).collect();
+        while let Some(current) = queue.pop() {
+            subclasses.push(current);
+            queue.extend(self.subclasses(current).iter().map(String::as_str));
+        }
+
+        subclasses.sort_unstable();
+        subclasses
+    }
+
+    /// Get the classes and interfaces that directly implement an interface.
+    #[must_use]
+    pub fn implementors(&self, interface: &str) -> &[String] {
+        self.0.get(interface).map_or(&[], |node| node.implementors.as_slice())
+    }
+
+    /// Get all classes that implement an interface, directly or indirectly.
+    ///
+    /// This includes subclasses of implementors
+    /// and implementors of any subinterfaces.
+    #[must_use]
+    pub fn all_implementors<'a>(&'a self, interface: &str) -> Vec<&'a str> {
+        let mut visited = HashSet::new();
+
+        let mut queue: Vec<&str> =
+            self.implementors(interface).iter().map(String::as_str).collect();
+        while let Some(current) = queue.pop() {
+            if visited.insert(current) {
+                queue.extend(self.implementors(current).iter().map(String::as_str));
+                queue.extend(self.subclasses(current).iter().map(String::as_str));
+            }
+        }
+
+        let mut implementors: Vec<&str> = visited.into_iter().collect();
+        implementors.sort_unstable();
+        implementors
+    }
+
+    /// Returns `true` if a class extends or implements another class,
+    /// directly or indirectly.
+    ///
+    /// A class is not considered a subclass of itself.
+    #[must_use]
+    pub fn is_subclass_of(&self, class: &str, parent: &str) -> bool {
+        self.superclasses(class).any(|super_class| super_class == parent)
+            || self.all_interfaces(class).contains(&parent)
+    }
+}
+
+#[test]
+#[cfg(test)]
+fn hierarchy() {
+    let hierarchy = ClassHierarchy::build(
+        [
+            ("Entity", Some("java/lang/Object"), vec!["Nameable"]),
+            ("LivingEntity", Some("Entity"), vec!["Attackable"]),
+            ("MobEntity", Some("LivingEntity"), vec![]),
+            ("ZombieEntity", Some("MobEntity"), vec!["Monster"]),
+            ("ItemEntity", Some("Entity"), vec![]),
+            ("Monster", None, vec!["Hostile"]),
+        ]
+        .into_iter(),
+    );
+
+    assert_eq!(hierarchy.superclass("ZombieEntity"), Some("MobEntity"));
+    assert_eq!(
+        hierarchy.superclasses("ZombieEntity").collect::<Vec<_>>(),
+        ["MobEntity", "LivingEntity", "Entity", "java/lang/Object"]
+    );
+    assert_eq!(
+        hierarchy.all_interfaces("ZombieEntity"),
+        ["Monster", "Attackable", "Nameable", "Hostile"]
+    );
+
+    assert_eq!(hierarchy.subclasses("Entity"), ["ItemEntity", "LivingEntity"]);
+    assert_eq!(
+        hierarchy.all_subclasses("Entity"),
+        ["ItemEntity", "LivingEntity", "MobEntity", "ZombieEntity"]
+    );
+    assert_eq!(hierarchy.all_implementors("Hostile"), ["Monster", "ZombieEntity"]);
+    assert_eq!(
+        hierarchy.all_implementors("Nameable"),
+        ["Entity", "ItemEntity", "LivingEntity", "MobEntity", "ZombieEntity"]
+    );
+
+    assert!(hierarchy.is_subclass_of("ZombieEntity", "LivingEntity"));
+    assert!(hierarchy.is_subclass_of("ZombieEntity", "Hostile"));
+    assert!(!hierarchy.is_subclass_of("ItemEntity", "LivingEntity"));
+    assert!(!hierarchy.is_subclass_of("Entity", "Entity"));
+}
@@ -12,33 +12,111 @@ use crate::{container::DependencyContainer, version::Version};
 /// Paths to Minecraft's built-in data generators.
 #[derive(Debug, Default, Clone, PartialEq, Eq, Dependency)]
 #[dep(path = crate)]
-pub struct DataGenerator(HashMap<Version, PathBuf>);
+pub struct DataGenerator {
+    outputs: HashMap<(Version, GeneratorOutput), PathBuf>,
+    jvm_args: Vec<String>,
+}
+
+/// A set of outputs the data generator can produce.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GeneratorOutput {
+    /// Reports, such as `registries.json` and `blocks.json`.
+    Reports,
+    /// Server data, such as recipes, loot tables and tags.
+    Server,
+    /// Client assets, such as models and blockstates.
+    Client,
+    /// Development tools, such as NBT to SNBT conversion.
+    Dev,
+    /// All of the above.
+    #[default]
+    All,
+}
+
+impl GeneratorOutput {
+    /// The argument passed to the data generator.
+    #[must_use]
+    pub const fn arg(self) -> &'static str {
+        match self {
+            GeneratorOutput::Reports => "--reports",
+            GeneratorOutput::Server => "--server",
+            GeneratorOutput::Client => "--client",
+            GeneratorOutput::Dev => "--dev",
+            GeneratorOutput::All => "--all",
+        }
+    }
+
+    /// The suffix of the directory the output is cached in.
+    #[must_use]
+    pub const fn suffix(self) -> &'static str {
+        match self {
+            GeneratorOutput::Reports => "-generated-reports",
+            GeneratorOutput::Server => "-generated-server",
+            GeneratorOutput::Client => "-generated-client",
+            GeneratorOutput::Dev => "-generated-dev",
+            GeneratorOutput::All => "-generated",
+        }
+    }
+}
 
 impl DataGenerator {
-    /// Get the [`Path`] of the data generator for the given version.
+    /// Get the [`Path`] of the full data generator output for the given
+    /// version.
     ///
     /// Returns `None` if the path is not yet known.
     #[must_use]
     pub fn version(&self, version: &Version) -> Option<&Path> {
-        self.0.get(version).map(PathBuf::as_path)
+        self.output(version, GeneratorOutput::All)
     }
 
-    /// Get the [`Path`] of the data generator for the given version.
+    /// Get the [`Path`] of the full data generator output for the given
+    /// version.
+    ///
+    /// Prefer [`DataGenerator::get_output`] if only part of the output is
+    /// needed.
     ///
     /// # Errors
     /// Returns an error if there was an error generating the data.
-    #[expect(clippy::missing_panics_doc)]
     pub async fn get_version(
         &mut self,
         version: &Version,
         deps: &mut DependencyContainer,
     ) -> anyhow::Result<&Path> {
-        if !self.0.contains_key(version) {
+        self.get_output(version, GeneratorOutput::All, deps).await
+    }
+
+    /// Get the [`Path`] of a data generator output for the given version.
+    ///
+    /// Returns `None` if the path is not yet known.
+    #[must_use]
+    pub fn output(&self, version: &Version, output: GeneratorOutput) -> Option<&Path> {
+        self.outputs.get(&(version.clone(), output)).map(PathBuf::as_path)
+    }
+
+    /// Get the [`Path`] of a data generator output for the given version.
+    ///
+    /// # Errors
+    /// Returns an error if there was an error generating the data.
+    #[expect(clippy::missing_panics_doc)]
+    pub async fn get_output(
+        &mut self,
+        version: &Version,
+        output: GeneratorOutput,
+        deps: &mut DependencyContainer,
+    ) -> anyhow::Result<&Path> {
+        // The full output contains every other output, so reuse it if it exists.
+        if let Some(all) = self.output(version, GeneratorOutput::All).map(Path::to_path_buf) {
+            self.outputs.entry((version.clone(), output)).or_insert(all);
+        }
+
+        if !self.outputs.contains_key(&(version.clone(), output)) {
             deps.get_or_retrieve::<MinecraftJar>().await?;
             deps.scoped_fut::<MinecraftJar, anyhow::Result<()>>(
                 async |jar: &mut MinecraftJar, deps: &mut DependencyContainer| {
                     let server = jar.get_server(version, deps).await?;
-                    self.0.insert(version.clone(), Self::run_generator(server, &deps.cache).await?);
+                    let path =
+                        Self::run_generator(server, output, &self.jvm_args, &deps.cache).await?;
+                    self.outputs.insert((version.clone(), output), path);
                     Ok(())
                 },
             )
@@ -46,48 +124,129 @@ impl DataGenerator {
             .map_err(|err| anyhow::anyhow!("DataGenerator: {err}"))?;
         }
 
-        Ok(self.version(version).unwrap())
+        Ok(self.output(version, output).unwrap())
+    }
+
+    /// Get the arguments passed to the JVM when running the data generator.
+    #[must_use]
+    pub fn jvm_args(&self) -> &[String] { &self.jvm_args }
+
+    /// Set the arguments passed to the JVM when running the data generator.
+    ///
+    /// For example, `["-Xmx4G"]` to increase the maximum heap size.
+    ///
+    /// These are only meant to tune the JVM and do not change what is
+    /// generated, so they are not part of the cache key. Outputs that were
+    /// already generated are reused no matter which arguments were set.
+    pub fn set_jvm_args<T: Into<String>>(&mut self, args: impl IntoIterator<Item = T>) {
+        self.jvm_args = args.into_iter().map(Into::into).collect();
     }
 }
 
 impl DataGenerator {
     const GENERATOR_CACHE: &str = "generator-cache";
 
-    async fn run_generator(jar: &Path, cache: &Path) -> anyhow::Result<PathBuf> {
-        let out = jar.with_file_name(format!(
-            "{}-generated",
-            jar.file_name().unwrap().to_string_lossy().split_once('.').unwrap().0
-        ));
-
-        if tokio::fs::try_exists(&out).await? {
-            tracing::debug!("Using \"{}\"", out.display());
-        } else {
-            tracing::debug!("Generating \"{}\"", jar.display());
-            tokio::fs::create_dir_all(&out).await?;
-
-            let cache = cache.join(Self::GENERATOR_CACHE);
-            if !tokio::fs::try_exists(&cache).await? {
-                tokio::fs::create_dir_all(&cache).await?;
-            }
+    /// Get the directory an output of the jar is generated into.
+    fn directory(jar: &Path, output: GeneratorOutput) -> PathBuf {
+        jar.with_file_name(format!(
+            "{}{}",
+            jar.file_name().unwrap().to_string_lossy().split_once('.').unwrap().0,
+            output.suffix()
+        ))
+    }
 
-            let process = Command::new("java")
-                .arg("-DbundlerMainClass=net.minecraft.data.Main")
-                .arg("-jar")
-                .arg(jar)
-                .arg("--output")
-                .arg(&out)
-                .arg("--all")
-                .current_dir(cache)
-                .output()
-                .await?;
-
-            if !process.status.success() {
-                let stdout = String::from_utf8_lossy(&process.stdout);
-                let stderr = String::from_utf8_lossy(&process.stderr);
-                anyhow::bail!("DataGenerator failed:\n{stderr}\n{stdout}");
+    /// Find an output of the jar that was already generated.
+    ///
+    /// The full output contains every other output, so it is used if it exists.
+    async fn existing(jar: &Path, output: GeneratorOutput) -> anyhow::Result<Option<PathBuf>> {
+        for output in [output, GeneratorOutput::All] {
+            let directory = Self::directory(jar, output);
+            if tokio::fs::try_exists(&directory).await? {
+                return Ok(Some(directory));
             }
         }
+        Ok(None)
+    }
+
+    async fn run_generator(
+        jar: &Path,
+        output: GeneratorOutput,
+        jvm_args: &[String],
+        cache: &Path,
+    ) -> anyhow::Result<PathBuf> {
+        let out = Self::directory(jar, output);
+
+        if let Some(existing) = Self::existing(jar, output).await? {
+            tracing::debug!("Using \"{}\"", existing.display());
+            return Ok(existing);
+        }
+
+        tracing::debug!("Generating \"{}\" ({})", jar.display(), output.arg());
+
+        let cache = cache.join(Self::GENERATOR_CACHE);
+        if !tokio::fs::try_exists(&cache).await? {
+            tokio::fs::create_dir_all(&cache).await?;
+        }
+
+        // Generate into a temporary directory,
+        // so an interrupted run is not mistaken for a finished one.
+        let partial = out.with_extension("partial");
+        if tokio::fs::try_exists(&partial).await? {
+            tokio::fs::remove_dir_all(&partial).await?;
+        }
+        tokio::fs::create_dir_all(&partial).await?;
+
+        let process = Command::new("java")
+            .args(jvm_args)
+            .arg("-DbundlerMainClass=net.minecraft.data.Main")
+            .arg("-jar")
+            .arg(jar)
+            .arg("--output")
+            .arg(&partial)
+            .arg(output.arg())
+            .current_dir(cache)
+            .output()
+            .await?;
+
+        if !process.status.success() {
+            let stdout = String::from_utf8_lossy(&process.stdout);
+            let stderr = String::from_utf8_lossy(&process.stderr);
+            anyhow::bail!("DataGenerator failed:\n{stderr}\n{stdout}");
+        }
+
+        tokio::fs::rename(&partial, &out).await?;
 
         Ok(out)
     }
 }
+
+#[tokio::test]
+#[cfg(test)]
+async fn existing() {
+    let directory = std::env::temp_dir().join("froglight-data-generator-test");
+    let jar = directory.join("server.jar");
+    let all = directory.join("server-generated");
+    let reports = directory.join("server-generated-reports");
+    tokio::fs::create_dir_all(&directory).await.unwrap();
+    for path in [&all, &reports] {
+        if tokio::fs::try_exists(path).await.unwrap() {
+            tokio::fs::remove_dir_all(path).await.unwrap();
+        }
+    }
+
+    // Nothing has been generated yet.
+    let existing = DataGenerator::existing(&jar, GeneratorOutput::Reports).await.unwrap();
+    assert_eq!(existing, None);
+
+    // The full output contains the reports.
+    tokio::fs::create_dir(&all).await.unwrap();
+    let existing = DataGenerator::existing(&jar, GeneratorOutput::Reports).await.unwrap();
+    assert_eq!(existing, Some(all.clone()));
+
+    // An existing partial output is preferred.
+    tokio::fs::create_dir(&reports).await.unwrap();
+    let existing = DataGenerator::existing(&jar, GeneratorOutput::Reports).await.unwrap();
+    assert_eq!(existing, Some(reports));
+
+    tokio::fs::remove_dir_all(&directory).await.unwrap();
+}
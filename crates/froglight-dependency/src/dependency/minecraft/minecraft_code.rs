@@ -1,27 +1,66 @@
 //! TODO
 
-use std::{io::Read, path::Path};
+use std::{
+    io::Read,
+    path::Path,
+    str::FromStr,
+    sync::{Arc, OnceLock},
+};
 
-use cafebabe::ClassFile;
+use cafebabe::{ClassFile, MethodAccessFlags, MethodInfo};
 use froglight_tool_macros::Dependency;
 use hashbrown::HashMap;
 use zip::ZipArchive;
 
+use super::{ClassHierarchy, MinecraftLibraries, XrefIndex};
 use crate::{container::DependencyContainer, dependency::yarn::MappedJar, version::Version};
 
 /// Parsed Minecraft code.
 #[derive(Clone, Default, PartialEq, Eq, Dependency)]
 #[dep(path = crate)]
-pub struct MinecraftCode(HashMap<Version, CodeBundle>);
+pub struct MinecraftCode {
+    client: HashMap<Version, CodeBundle>,
+    server: HashMap<Version, CodeBundle>,
+    merged: HashMap<Version, CodeBundle>,
+    classpath: HashMap<Version, CodeBundle>,
+}
+
+/// Which side of the game a [`CodeBundle`] contains.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CodeSide {
+    /// Classes from the client jar.
+    #[default]
+    Client,
+    /// Classes from the server jar.
+    Server,
+    /// Classes from both the client and server jars.
+    ///
+    /// Classes present in both jars are taken from the client.
+    Merged,
+}
+
+impl FromStr for CodeSide {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "client" => Ok(Self::Client),
+            "server" => Ok(Self::Server),
+            "merged" => Ok(Self::Merged),
+            _ => {
+                anyhow::bail!("Unknown side \"{s}\", expected \"client\", \"server\" or \"merged\"")
+            }
+        }
+    }
+}
 
 impl MinecraftCode {
-    /// Get the [`CodeBundle`] for a given version.
+    /// Get the client [`CodeBundle`] for a given version.
     ///
     /// Returns `None` if the version is not yet known.
     #[must_use]
-    pub fn version(&self, version: &Version) -> Option<&CodeBundle> { self.0.get(version) }
+    pub fn version(&self, version: &Version) -> Option<&CodeBundle> { self.client.get(version) }
 
-    /// Get the [`CodeBundle`] for a given version.
+    /// Get the client [`CodeBundle`] for a given version.
     ///
     /// # Errors
     /// Returns an error if there was an error getting the [`CodeBundle`].
@@ -31,12 +70,12 @@ impl MinecraftCode {
         version: &Version,
         deps: &mut DependencyContainer,
     ) -> anyhow::Result<&CodeBundle> {
-        if !self.0.contains_key(version) {
+        if !self.client.contains_key(version) {
             deps.get_or_retrieve::<MappedJar>().await?;
             deps.scoped_fut::<MappedJar, anyhow::Result<()>>(
                 async |jar: &mut MappedJar, deps: &mut DependencyContainer| {
                     let client = jar.get_client(version, deps).await?;
-                    self.0.insert(version.clone(), CodeBundle::build_from(client)?);
+                    self.client.insert(version.clone(), CodeBundle::build_from(client)?);
                     Ok(())
                 },
             )
@@ -46,51 +85,518 @@ impl MinecraftCode {
 
         Ok(self.version(version).unwrap())
     }
+
+    /// Get the server [`CodeBundle`] for a given version.
+    ///
+    /// Returns `None` if the version is not yet known.
+    #[must_use]
+    pub fn server(&self, version: &Version) -> Option<&CodeBundle> { self.server.get(version) }
+
+    /// Get the server [`CodeBundle`] for a given version.
+    ///
+    /// # Errors
+    /// Returns an error if there was an error getting the [`CodeBundle`].
+    #[expect(clippy::missing_panics_doc)]
+    pub async fn get_server(
+        &mut self,
+        version: &Version,
+        deps: &mut DependencyContainer,
+    ) -> anyhow::Result<&CodeBundle> {
+        if !self.server.contains_key(version) {
+            deps.get_or_retrieve::<MappedJar>().await?;
+            deps.scoped_fut::<MappedJar, anyhow::Result<()>>(
+                async |jar: &mut MappedJar, deps: &mut DependencyContainer| {
+                    let server = jar.get_server(version, deps).await?;
+                    self.server.insert(version.clone(), CodeBundle::build_from(server)?);
+                    Ok(())
+                },
+            )
+            .await
+            .map_err(|err| anyhow::anyhow!("MinecraftCode: {err}"))?;
+        }
+
+        Ok(self.server(version).unwrap())
+    }
+
+    /// Get the merged [`CodeBundle`] for a given version.
+    ///
+    /// Returns `None` if the version is not yet known.
+    #[must_use]
+    pub fn merged(&self, version: &Version) -> Option<&CodeBundle> { self.merged.get(version) }
+
+    /// Get the merged [`CodeBundle`] for a given version.
+    ///
+    /// This contains all client classes as well as any server-only classes.
+    ///
+    /// # Errors
+    /// Returns an error if there was an error getting the [`CodeBundle`].
+    #[expect(clippy::missing_panics_doc)]
+    pub async fn get_merged(
+        &mut self,
+        version: &Version,
+        deps: &mut DependencyContainer,
+    ) -> anyhow::Result<&CodeBundle> {
+        if !self.merged.contains_key(version) {
+            let mut bundle = self.get_version(version, deps).await?.clone();
+            bundle.merge(self.get_server(version, deps).await?);
+            self.merged.insert(version.clone(), bundle);
+        }
+
+        Ok(self.merged(version).unwrap())
+    }
+
+    /// Get the [`CodeBundle`] for a given version and [`CodeSide`].
+    ///
+    /// Returns `None` if the version is not yet known.
+    #[must_use]
+    pub fn side(&self, version: &Version, side: CodeSide) -> Option<&CodeBundle> {
+        match side {
+            CodeSide::Client => self.version(version),
+            CodeSide::Server => self.server(version),
+            CodeSide::Merged => self.merged(version),
+        }
+    }
+
+    /// Get the [`CodeBundle`] for a given version and [`CodeSide`].
+    ///
+    /// # Errors
+    /// Returns an error if there was an error getting the [`CodeBundle`].
+    pub async fn get_side(
+        &mut self,
+        version: &Version,
+        side: CodeSide,
+        deps: &mut DependencyContainer,
+    ) -> anyhow::Result<&CodeBundle> {
+        match side {
+            CodeSide::Client => self.get_version(version, deps).await,
+            CodeSide::Server => self.get_server(version, deps).await,
+            CodeSide::Merged => self.get_merged(version, deps).await,
+        }
+    }
+
+    /// Get the classpath [`CodeBundle`] for a given version.
+    ///
+    /// Returns `None` if the version is not yet known.
+    #[must_use]
+    pub fn classpath(&self, version: &Version) -> Option<&CodeBundle> {
+        self.classpath.get(version)
+    }
+
+    /// Get the classpath [`CodeBundle`] for a given version.
+    ///
+    /// This contains all Minecraft classes as well as all classes
+    /// from the libraries it depends on, such as `brigadier` or `netty`.
+    ///
+    /// # Errors
+    /// Returns an error if there was an error getting the [`CodeBundle`].
+    #[expect(clippy::missing_panics_doc)]
+    pub async fn get_classpath(
+        &mut self,
+        version: &Version,
+        deps: &mut DependencyContainer,
+    ) -> anyhow::Result<&CodeBundle> {
+        if !self.classpath.contains_key(version) {
+            let mut bundle = self.get_version(version, deps).await?.clone();
+
+            deps.get_or_retrieve::<MinecraftLibraries>().await?;
+            deps.scoped_fut::<MinecraftLibraries, anyhow::Result<()>>(
+                async |libraries: &mut MinecraftLibraries, deps: &mut DependencyContainer| {
+                    for library in libraries.get_version(version, deps).await? {
+                        bundle.extend_from(library)?;
+                    }
+                    Ok(())
+                },
+            )
+            .await
+            .map_err(|err| anyhow::anyhow!("MinecraftCode: {err}"))?;
+
+            self.classpath.insert(version.clone(), bundle);
+        }
+
+        Ok(self.classpath(version).unwrap())
+    }
 }
 
 /// Parsed Minecraft code for a specific version.
-#[derive(Clone, PartialEq, Eq)]
-pub struct CodeBundle(HashMap<String, Vec<u8>>);
+///
+/// Classes are parsed the first time they are requested,
+/// and the result is cached for all later requests.
+///
+/// Classes borrow from the bytes stored in the bundle,
+/// so they are accessed through [`CodeBundle::with_classes`].
+#[derive(Clone)]
+pub struct CodeBundle(Arc<ClassCell>);
+
+impl PartialEq for CodeBundle {
+    fn eq(&self, other: &Self) -> bool { self.0.borrow_owner() == other.0.borrow_owner() }
+}
+impl Eq for CodeBundle {}
+
+self_cell::self_cell!(
+    /// The raw bytes of every class, stored next to the [`Classes`] that
+    /// borrow from them.
+    struct ClassCell {
+        owner: HashMap<String, Arc<[u8]>>,
+        #[not_covariant]
+        dependent: Classes,
+    }
+);
 
 impl CodeBundle {
-    /// Build a [`CodeBundle`] from a decompiled jar.
+    /// Create a [`CodeBundle`] from the names and bytes of classes,
+    /// such as `("net/minecraft/block/Blocks", bytes)`.
+    #[must_use]
+    pub fn from_classes(classes: impl IntoIterator<Item = (String, Vec<u8>)>) -> Self {
+        Self::new(classes.into_iter().map(|(name, data)| (name, data.into())).collect())
+    }
+
+    fn new(classes: HashMap<String, Arc<[u8]>>) -> Self {
+        Self(Arc::new(ClassCell::new(classes, |classes| Classes {
+            entries: classes
+                .iter()
+                .map(|(name, data)| (name.as_str(), ClassEntry { data, parsed: OnceLock::new() }))
+                .collect(),
+            hierarchy: OnceLock::new(),
+            xrefs: OnceLock::new(),
+        })))
+    }
+
+    /// Build a [`CodeBundle`] from a jar.
     fn build_from(jar: &Path) -> anyhow::Result<Self> {
+        let mut bundle = Self::from_classes([]);
+        bundle.extend_from(jar)?;
+        Ok(bundle)
+    }
+
+    /// Add all classes from a jar to the [`CodeBundle`].
+    ///
+    /// Classes that already exist in the [`CodeBundle`] are not replaced.
+    fn extend_from(&mut self, jar: &Path) -> anyhow::Result<()> {
         tracing::debug!("Parsing \"{}\"", jar.display());
 
-        let mut map = HashMap::new();
         let mut zip = ZipArchive::new(std::io::Cursor::new(std::fs::read(jar)?))?;
+        let mut classes = self.0.borrow_owner().clone();
 
         for index in 0..zip.len() {
             if !zip.name_for_index(index).is_some_and(|n| {
-                Path::new(n).extension().is_some_and(|ext| ext.eq_ignore_ascii_case("class"))
+                !n.starts_with("META-INF/")
+                    && !n.ends_with("module-info.class")
+                    && Path::new(n).extension().is_some_and(|ext| ext.eq_ignore_ascii_case("class"))
             }) {
                 continue;
             }
 
             let mut file = zip.by_index(index)?;
+            let name = file.name().trim_end_matches(".class").to_string();
+            if classes.contains_key(&name) {
+                continue;
+            }
+
             let mut file_buf = Vec::new();
             file.read_to_end(&mut file_buf)?;
+            classes.insert(name, file_buf.into());
+        }
+
+        *self = Self::new(classes);
+        Ok(())
+    }
+
+    /// Add all classes from another [`CodeBundle`].
+    ///
+    /// Classes that already exist in the [`CodeBundle`] are not replaced.
+    fn merge(&mut self, other: &Self) {
+        let mut classes = self.0.borrow_owner().clone();
+        for (name, data) in other.0.borrow_owner() {
+            classes.entry_ref(name.as_str()).or_insert_with(|| data.clone());
+        }
+        *self = Self::new(classes);
+    }
+
+    /// Returns `true` if the [`CodeBundle`] contains the given class.
+    #[must_use]
+    pub fn contains(&self, class: &str) -> bool { self.0.borrow_owner().contains_key(class) }
+
+    /// Iterate over the names of all classes in the [`CodeBundle`].
+    pub fn class_names(&self) -> impl Iterator<Item = &str> {
+        self.0.borrow_owner().keys().map(String::as_str)
+    }
+
+    /// Access the parsed [`Classes`] of the [`CodeBundle`].
+    ///
+    /// The cache is shared between all clones of a [`CodeBundle`],
+    /// so each class is only parsed once.
+    pub fn with_classes<'s, R>(&'s self, f: impl for<'a> FnOnce(&'s Classes<'a>) -> R) -> R {
+        self.0.with_dependent(|_, classes| f(classes))
+    }
+}
+
+/// The classes of a [`CodeBundle`], borrowing from its bytes.
+///
+/// See [`CodeBundle::with_classes`].
+pub struct Classes<'a> {
+    entries: HashMap<&'a str, ClassEntry<'a>>,
+    /// The class hierarchy, built the first time it is requested.
+    hierarchy: OnceLock<ClassHierarchy>,
+    /// The cross-reference index, built the first time it is requested.
+    xrefs: OnceLock<XrefIndex>,
+}
+
+/// The raw bytes of a class and its cached parse result.
+struct ClassEntry<'a> {
+    data: &'a [u8],
+    parsed: OnceLock<Result<ClassFile<'a>, String>>,
+}
+
+impl<'a> ClassEntry<'a> {
+    /// Parse the class, or return the cached result.
+    fn parse(&self) -> Result<&ClassFile<'a>, &str> {
+        self.parsed
+            .get_or_init(|| cafebabe::parse_class(self.data).map_err(|err| err.to_string()))
+            .as_ref()
+            .map_err(String::as_str)
+    }
+}
+
+impl<'a> Classes<'a> {
+    /// Returns `true` if the [`Classes`] contain the given class.
+    #[must_use]
+    pub fn contains(&self, class: &str) -> bool { self.entries.contains_key(class) }
+
+    /// Iterate over the names of all classes.
+    pub fn class_names(&self) -> impl Iterator<Item = &'a str> + '_ { self.entries.keys().copied() }
+
+    /// Get the [`ClassHierarchy`] of all classes.
+    ///
+    /// The hierarchy is built the first time it is requested.
+    /// Classes that could not be parsed are logged and skipped.
+    #[must_use]
+    pub fn hierarchy(&self) -> &ClassHierarchy {
+        self.hierarchy.get_or_init(|| {
+            tracing::debug!("Building class hierarchy");
+            ClassHierarchy::build(self.get_filter(|_| true).map(|class| {
+                (
+                    &*class.this_class,
+                    class.super_class.as_deref(),
+                    class.interfaces.iter().map(|i| &**i).collect(),
+                )
+            }))
+        })
+    }
+
+    /// Get the [`XrefIndex`] of all classes.
+    ///
+    /// The index is built the first time it is requested.
+    /// Classes that could not be parsed are logged and skipped.
+    #[must_use]
+    pub fn xrefs(&self) -> &XrefIndex {
+        self.xrefs.get_or_init(|| {
+            tracing::debug!("Building cross-reference index");
+            XrefIndex::build(self.get_filter(|_| true))
+        })
+    }
+
+    /// Find the class that defines a method, following the class hierarchy.
+    ///
+    /// Superclasses are searched first, then interfaces for default methods.
+    /// If only abstract declarations exist, the most specific one is returned.
+    ///
+    /// Private methods are not inherited,
+    /// so they are only found in the starting class.
+    /// Static methods are found in superclasses, the same way `invokestatic`
+    /// resolves them, but not in interfaces.
+    ///
+    /// If `descriptor` is `None`, the first method with a matching name is
+    /// used.
+    #[must_use]
+    pub fn resolve_method(
+        &self,
+        class: &str,
+        method: &str,
+        descriptor: Option<&str>,
+    ) -> Option<(&ClassFile<'a>, &MethodInfo<'a>)> {
+        let hierarchy = self.hierarchy();
+        let start = class;
+        let find = |class: &str, skipped: MethodAccessFlags| {
+            let skipped = if class == start { MethodAccessFlags::empty() } else { skipped };
+            self.get(class).and_then(|file| {
+                file.methods
+                    .iter()
+                    .find(|m| {
+                        m.name == method
+                            && descriptor.is_none_or(|d| m.descriptor.to_string() == d)
+                            && !m.access_flags.intersects(skipped)
+                    })
+                    .map(|m| (file, m))
+            })
+        };
+        let is_abstract = |(_, m): &(&ClassFile<'a>, &MethodInfo<'a>)| {
+            m.access_flags.contains(MethodAccessFlags::ABSTRACT)
+        };
 
-            map.insert(file.name().trim_end_matches(".class").to_string(), file_buf);
+        let mut declaration = None;
+        for class in core::iter::once(class).chain(hierarchy.superclasses(class)) {
+            if let Some(found) = find(class, MethodAccessFlags::PRIVATE) {
+                if !is_abstract(&found) {
+                    return Some(found);
+                }
+                declaration.get_or_insert(found);
+            }
+        }
+        for interface in hierarchy.all_interfaces(class) {
+            if let Some(found) =
+                find(interface, MethodAccessFlags::PRIVATE | MethodAccessFlags::STATIC)
+            {
+                if !is_abstract(&found) {
+                    return Some(found);
+                }
+                declaration.get_or_insert(found);
+            }
         }
 
-        Ok(Self(map))
+        declaration
     }
 
     /// Get the [`ClassFile`] for a given class.
+    ///
+    /// Returns `None` if the class does not exist or could not be parsed.
+    /// Parsing errors are logged, use [`Classes::try_get`] to handle them.
     #[must_use]
-    #[expect(clippy::missing_panics_doc)]
-    pub fn get<'a>(&'a self, class: &str) -> Option<ClassFile<'a>> {
-        self.0.get(class).map(|data| cafebabe::parse_class(data).unwrap())
+    pub fn get(&self, class: &str) -> Option<&ClassFile<'a>> {
+        self.try_get(class).unwrap_or_else(|err| {
+            tracing::error!("{err}");
+            None
+        })
+    }
+
+    /// Get the [`ClassFile`] for a given class.
+    ///
+    /// Returns `Ok(None)` if the class does not exist.
+    ///
+    /// # Errors
+    /// Returns an error naming the class if it could not be parsed.
+    pub fn try_get(&self, class: &str) -> anyhow::Result<Option<&ClassFile<'a>>> {
+        match self.entries.get(class) {
+            Some(entry) => entry
+                .parse()
+                .map(Some)
+                .map_err(|err| anyhow::anyhow!("Failed to parse class \"{class}\": {err}")),
+            None => Ok(None),
+        }
     }
 
     /// Iterate over a filtered set of [`ClassFile`]s
-    #[must_use]
-    #[expect(clippy::missing_panics_doc)]
-    pub fn get_filter<'a>(
-        &'a self,
-        filter: impl FnMut(&(&String, &Vec<u8>)) -> bool,
-    ) -> impl Iterator<Item = ClassFile<'a>> {
-        self.0.iter().filter(filter).map(|(_, data)| cafebabe::parse_class(data).unwrap())
+    ///
+    /// Classes that could not be parsed are logged and skipped,
+    /// use [`Classes::try_get_filter`] to handle them.
+    pub fn get_filter(
+        &self,
+        filter: impl FnMut(&&str) -> bool,
+    ) -> impl Iterator<Item = &ClassFile<'a>> {
+        self.try_get_filter(filter)
+            .filter_map(|result| result.inspect_err(|err| tracing::error!("{err}")).ok())
     }
+
+    /// Iterate over a filtered set of [`ClassFile`]s.
+    ///
+    /// Each item is an error naming the class if it could not be parsed.
+    pub fn try_get_filter(
+        &self,
+        mut filter: impl FnMut(&&str) -> bool,
+    ) -> impl Iterator<Item = anyhow::Result<&ClassFile<'a>>> {
+        self.entries.iter().filter(move |(name, _)| filter(name)).map(|(name, entry)| {
+            entry.parse().map_err(|err| anyhow::anyhow!("Failed to parse class \"{name}\": {err}"))
+        })
+    }
+}
+
+#[test]
+#[cfg(test)]
+fn bundle() {
+    use super::class_builder::ClassBuilder;
+
+    let bundle = CodeBundle::from_classes([
+        (String::from("Valid"), ClassBuilder::new("Valid").build()),
+        (String::from("Malformed"), vec![0xCA, 0xFE, 0xBA, 0xBE, 0, 0]),
+    ]);
+
+    // Classes are only parsed once, and clones share the cached class.
+    let first = bundle.with_classes(|classes| {
+        let class = classes.try_get("Valid").unwrap().unwrap();
+        assert_eq!(&*class.this_class, "Valid");
+        core::ptr::from_ref(class).cast::<()>()
+    });
+    let clone = bundle.clone();
+    let second =
+        clone.with_classes(|classes| core::ptr::from_ref(classes.get("Valid").unwrap()).cast());
+    assert_eq!(first, second);
+
+    bundle.with_classes(|classes| {
+        // Malformed classes return an error naming the class.
+        let error = classes.try_get("Malformed").unwrap_err();
+        assert!(error.to_string().starts_with("Failed to parse class \"Malformed\""), "{error}");
+        assert!(classes.get("Malformed").is_none());
+        assert!(classes.try_get("Missing").unwrap().is_none());
+
+        let errors = classes.try_get_filter(|_| true).filter(Result::is_err).count();
+        assert_eq!(errors, 1);
+    });
+}
+
+#[test]
+#[cfg(test)]
+fn resolve_method() {
+    use super::class_builder::{ClassBuilder, CodeWriter};
+
+    let public = MethodAccessFlags::PUBLIC;
+    let empty = |code: &mut CodeWriter<'_>| code.return_();
+    let class = |builder: ClassBuilder| {
+        let class = builder.build();
+        let name = cafebabe::parse_class(&class).unwrap().this_class.to_string();
+        (name, class)
+    };
+
+    let bundle = CodeBundle::from_classes([
+        class(
+            ClassBuilder::new("Entity")
+                .method(public, "tick", "()V", empty)
+                .method(public, "remove", "()V", empty),
+        ),
+        class(
+            ClassBuilder::new("LivingEntity")
+                .with_super("Entity")
+                .with_interface("Attackable")
+                .method(MethodAccessFlags::PRIVATE, "tick", "()V", empty)
+                .method(MethodAccessFlags::STATIC, "remove", "()V", empty),
+        ),
+        class(ClassBuilder::new("ZombieEntity").with_super("LivingEntity")),
+        class(
+            ClassBuilder::new("Attackable")
+                .abstract_method(public | MethodAccessFlags::ABSTRACT, "attack", "()V")
+                .method(MethodAccessFlags::STATIC, "create", "()V", empty),
+        ),
+    ]);
+
+    let owner = |class: &str, method: &str| {
+        bundle.with_classes(|classes| {
+            classes
+                .resolve_method(class, method, Some("()V"))
+                .map(|(class, _)| class.this_class.to_string())
+        })
+    };
+
+    // Private methods are skipped when inherited...
+    assert_eq!(owner("ZombieEntity", "tick").as_deref(), Some("Entity"));
+    // ...but found in the starting class.
+    assert_eq!(owner("LivingEntity", "tick").as_deref(), Some("LivingEntity"));
+
+    // Static methods are inherited from superclasses, such as
+    // `invokestatic ZombieEntity.remove` calling `LivingEntity.remove`.
+    assert_eq!(owner("ZombieEntity", "remove").as_deref(), Some("LivingEntity"));
+    assert_eq!(owner("LivingEntity", "remove").as_deref(), Some("LivingEntity"));
+    // Static interface methods are only found in the interface itself.
+    assert_eq!(owner("ZombieEntity", "create"), None);
+    assert_eq!(owner("Attackable", "create").as_deref(), Some("Attackable"));
+
+    assert_eq!(owner("ZombieEntity", "attack").as_deref(), Some("Attackable"));
+    assert_eq!(owner("ZombieEntity", "jump"), None);
 }
@@ -0,0 +1,121 @@
+//! TODO
+
+use std::{
+    fmt::Write,
+    path::{Path, PathBuf},
+};
+
+use froglight_tool_macros::Dependency;
+use hashbrown::HashMap;
+use sha1::{Digest, Sha1};
+
+use crate::{
+    container::DependencyContainer,
+    dependency::mojang::{
+        release_manifest::{ReleaseArtifact, ReleaseLibrary},
+        ReleaseManifests,
+    },
+    version::Version,
+};
+
+/// Library JAR paths.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Dependency)]
+#[dep(path = crate)]
+pub struct MinecraftLibraries(HashMap<Version, Vec<PathBuf>>);
+
+impl MinecraftLibraries {
+    /// The directory to store all library jars.
+    pub const CACHE_DIR: &str = "libraries";
+
+    /// Get the [`Path`]s of the libraries for the given version.
+    ///
+    /// Returns `None` if the paths are not yet known.
+    #[must_use]
+    pub fn version(&self, version: &Version) -> Option<&[PathBuf]> {
+        self.0.get(version).map(Vec::as_slice)
+    }
+
+    /// Get the [`Path`]s of the libraries for the given version.
+    ///
+    /// Only libraries and natives required on Linux are retrieved.
+    ///
+    /// # Errors
+    /// Returns an error if there was an error getting the libraries.
+    #[expect(clippy::missing_panics_doc)]
+    pub async fn get_version(
+        &mut self,
+        version: &Version,
+        deps: &mut DependencyContainer,
+    ) -> anyhow::Result<&[PathBuf]> {
+        if !self.0.contains_key(version) {
+            deps.get_or_retrieve::<ReleaseManifests>().await?;
+            deps.scoped_fut::<ReleaseManifests, anyhow::Result<()>>(
+                async |manifest: &mut ReleaseManifests, deps| {
+                    let release = manifest.get_release(version, deps).await?;
+
+                    let mut libraries = Vec::new();
+                    for artifact in release.artifacts_for(ReleaseLibrary::LINUX) {
+                        libraries.push(Self::download_and_cache(artifact, deps).await?);
+                    }
+
+                    self.0.insert(version.clone(), libraries);
+                    Ok(())
+                },
+            )
+            .await
+            .map_err(|err| anyhow::anyhow!("MinecraftLibraries: {err}"))?;
+        }
+
+        Ok(self.version(version).unwrap())
+    }
+}
+
+impl MinecraftLibraries {
+    async fn download_and_cache(
+        artifact: &ReleaseArtifact,
+        deps: &mut DependencyContainer,
+    ) -> anyhow::Result<PathBuf> {
+        let path = deps.cache.join(Self::CACHE_DIR).join(&artifact.path);
+
+        if tokio::fs::try_exists(&path).await? {
+            if Self::verify(&path, &artifact.sha1).await? {
+                tracing::trace!("Using \"{}\"", path.display());
+                return Ok(path);
+            }
+            tracing::warn!("Hash mismatch for \"{}\", retrieving again", path.display());
+        }
+
+        tracing::debug!("Retrieving \"{}\"", artifact.url);
+
+        // Download the jar and make sure it is intact
+        let response = deps.client.get(&artifact.url).send().await?.bytes().await?;
+        let hash = Self::hash(&response);
+        if hash != artifact.sha1 {
+            anyhow::bail!(
+                "Hash mismatch for \"{}\", expected {} but got {hash}",
+                artifact.url,
+                artifact.sha1
+            );
+        }
+
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::write(&path, &response).await?;
+
+        Ok(path)
+    }
+
+    /// Returns `true` if the file at the given path matches the hash.
+    async fn verify(path: &Path, sha1: &str) -> anyhow::Result<bool> {
+        Ok(Self::hash(&tokio::fs::read(path).await?) == sha1)
+    }
+
+    /// Get the hex-encoded SHA1 hash of the given data.
+    pub(crate) fn hash(data: &[u8]) -> String {
+        Sha1::digest(data).iter().fold(String::with_capacity(40), |mut acc, byte| {
+            let _ = write!(acc, "{byte:02x}");
+            acc
+        })
+    }
+}
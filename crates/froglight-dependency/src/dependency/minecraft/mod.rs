@@ -1,16 +1,37 @@
 //! TODO
 
+#[cfg(any(test, feature = "testing"))]
+pub mod class_builder;
+
+mod class_hierarchy;
+pub use class_hierarchy::ClassHierarchy;
+
 mod data_generator;
-pub use data_generator::DataGenerator;
+pub use data_generator::{DataGenerator, GeneratorOutput};
 
 pub mod minecraft_code;
-pub use minecraft_code::MinecraftCode;
+pub use minecraft_code::{CodeSide, MinecraftCode};
 
 mod minecraft_jar;
 pub use minecraft_jar::MinecraftJar;
 
+mod minecraft_libraries;
+pub use minecraft_libraries::MinecraftLibraries;
+
+mod pumpkin_data;
+pub use pumpkin_data::{
+    PumpkinBlock, PumpkinBlockState, PumpkinBlocks, PumpkinData, PumpkinProperty, PumpkinRecipe,
+    PumpkinShape, PumpkinVersionData,
+};
+
 mod pumpkin_extractor;
 pub use pumpkin_extractor::PumpkinExtractor;
 
+mod server_bundle;
+pub use server_bundle::{ServerBundle, ServerBundles};
+
 mod translations;
 pub use translations::{Translations, TranslationsFile};
+
+mod xref_index;
+pub use xref_index::{XrefIndex, XrefLocation};
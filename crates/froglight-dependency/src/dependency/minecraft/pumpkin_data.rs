@@ -0,0 +1,319 @@
+//! TODO
+
+use std::path::{Path, PathBuf};
+
+use froglight_tool_macros::Dependency;
+use hashbrown::HashMap;
+use serde::{de::DeserializeOwned, Deserialize};
+
+use super::PumpkinExtractor;
+use crate::{container::DependencyContainer, version::Version};
+
+/// Parsed data extracted using Pumpkin's Extractor.
+#[derive(Debug, Default, Clone, PartialEq, Dependency)]
+#[dep(path = crate)]
+pub struct PumpkinData(HashMap<Version, PumpkinVersionData>);
+
+impl PumpkinData {
+    /// Get the [`PumpkinVersionData`] for the given version.
+    ///
+    /// Returns `None` if the data has not been parsed yet.
+    #[must_use]
+    pub fn version(&self, version: &Version) -> Option<&PumpkinVersionData> { self.0.get(version) }
+
+    /// Get the [`PumpkinVersionData`] for the given version.
+    ///
+    /// # Errors
+    /// Returns an error if there was an error extracting or parsing the data.
+    #[expect(clippy::missing_panics_doc)]
+    pub async fn get_version(
+        &mut self,
+        version: &Version,
+        deps: &mut DependencyContainer,
+    ) -> anyhow::Result<&PumpkinVersionData> {
+        if !self.0.contains_key(version) {
+            deps.get_or_retrieve::<PumpkinExtractor>().await?;
+            deps.scoped_fut::<PumpkinExtractor, anyhow::Result<()>>(
+                async |extractor: &mut PumpkinExtractor, deps: &mut DependencyContainer| {
+                    let path = extractor.get_version(version, deps).await?;
+                    self.0.insert(version.clone(), PumpkinVersionData::parse(path).await?);
+                    Ok(())
+                },
+            )
+            .await
+            .map_err(|err| anyhow::anyhow!("PumpkinData: {err}"))?;
+        }
+
+        Ok(self.version(version).unwrap())
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Parsed Pumpkin Extractor data for a specific version.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PumpkinVersionData {
+    /// The directory containing the extractor output.
+    pub directory: PathBuf,
+    /// Blocks, their states and their shapes.
+    pub blocks: PumpkinBlocks,
+    /// All recipes.
+    pub recipes: Vec<PumpkinRecipe>,
+}
+
+impl PumpkinVersionData {
+    const BLOCKS_FILE: &str = "blocks.json";
+    const RECIPES_FILE: &str = "recipes.json";
+
+    /// Parse the extractor output in the given directory.
+    async fn parse(directory: &Path) -> anyhow::Result<Self> {
+        let blocks = Self::read_file(directory, Self::BLOCKS_FILE).await?;
+        let recipes = if tokio::fs::try_exists(directory.join(Self::RECIPES_FILE)).await? {
+            Self::read_file(directory, Self::RECIPES_FILE).await?
+        } else {
+            Vec::new()
+        };
+
+        Ok(Self { directory: directory.to_path_buf(), blocks, recipes })
+    }
+
+    /// Read and parse any file from the extractor output,
+    /// such as `items.json` or `entities.json`.
+    ///
+    /// # Errors
+    /// Returns an error if the file could not be read or parsed.
+    pub async fn file<T: DeserializeOwned>(&self, name: &str) -> anyhow::Result<T> {
+        Self::read_file(&self.directory, name).await
+    }
+
+    async fn read_file<T: DeserializeOwned>(directory: &Path, name: &str) -> anyhow::Result<T> {
+        let path = directory.join(name);
+        tracing::debug!("Parsing \"{}\"", path.display());
+
+        let contents = tokio::fs::read_to_string(&path).await?;
+        serde_json::from_str(&contents).map_err(|err| anyhow::anyhow!("\"{name}\": {err}"))
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Blocks and their shapes.
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+pub struct PumpkinBlocks {
+    /// All block entity types, indexed by id.
+    #[serde(default)]
+    pub block_entity_types: Vec<String>,
+    /// All block shapes, indexed by id.
+    #[serde(default)]
+    pub shapes: Vec<PumpkinShape>,
+    /// All blocks, ordered by id.
+    pub blocks: Vec<PumpkinBlock>,
+}
+
+impl PumpkinBlocks {
+    /// Get a [`PumpkinBlock`] by name, without the namespace.
+    #[must_use]
+    pub fn block(&self, name: &str) -> Option<&PumpkinBlock> {
+        let name = name.trim_start_matches("minecraft:");
+        self.blocks.iter().find(|block| block.name == name)
+    }
+
+    /// Get the [`PumpkinBlock`] and [`PumpkinBlockState`] for a state id.
+    #[must_use]
+    pub fn state(&self, state_id: u32) -> Option<(&PumpkinBlock, &PumpkinBlockState)> {
+        self.blocks.iter().find_map(|block| block.state(state_id).map(|state| (block, state)))
+    }
+
+    /// Get the [`PumpkinShape`]s for a list of shape ids.
+    pub fn shapes<'a>(&'a self, ids: &'a [u16]) -> impl Iterator<Item = &'a PumpkinShape> + 'a {
+        ids.iter().filter_map(|id| self.shapes.get(usize::from(*id)))
+    }
+}
+
+/// An axis-aligned box used for block collisions and outlines.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize)]
+pub struct PumpkinShape {
+    /// The minimum corner of the box.
+    pub min: [f64; 3],
+    /// The maximum corner of the box.
+    pub max: [f64; 3],
+}
+
+/// A block and all of its states.
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+pub struct PumpkinBlock {
+    /// The block id.
+    pub id: u32,
+    /// The block name, without the namespace.
+    pub name: String,
+    /// The block's translation key.
+    #[serde(default)]
+    pub translation_key: String,
+    /// How long the block takes to break.
+    #[serde(default)]
+    pub hardness: f32,
+    /// How resistant the block is to explosions.
+    #[serde(default)]
+    pub blast_resistance: f32,
+    /// The id of the block's item.
+    #[serde(default)]
+    pub item_id: u32,
+    /// The id of the block's default state.
+    pub default_state_id: u32,
+    /// The block's properties, in state order.
+    #[serde(default)]
+    pub properties: Vec<PumpkinProperty>,
+    /// The block's states, ordered by id.
+    pub states: Vec<PumpkinBlockState>,
+}
+
+impl PumpkinBlock {
+    /// Get a [`PumpkinBlockState`] by its id.
+    #[must_use]
+    pub fn state(&self, state_id: u32) -> Option<&PumpkinBlockState> {
+        let first = self.states.first()?.id;
+        let index = usize::try_from(state_id.checked_sub(first)?).ok()?;
+        self.states.get(index).filter(|state| state.id == state_id)
+    }
+
+    /// Get the property values of a state by its id.
+    ///
+    /// States are ordered with the last property changing the fastest,
+    /// the same way Minecraft orders them.
+    #[must_use]
+    pub fn state_properties(&self, state_id: u32) -> Option<Vec<(&str, &str)>> {
+        let mut index = usize::try_from(state_id.checked_sub(self.states.first()?.id)?).ok()?;
+        if index >= self.states.len() {
+            return None;
+        }
+
+        let mut values = Vec::with_capacity(self.properties.len());
+        for property in self.properties.iter().rev() {
+            let count = property.values.len();
+            if count == 0 {
+                return None;
+            }
+            values.push((property.name.as_str(), property.values[index % count].as_str()));
+            index /= count;
+        }
+
+        values.reverse();
+        Some(values)
+    }
+}
+
+/// A block property and its possible values.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize)]
+pub struct PumpkinProperty {
+    /// The property name.
+    pub name: String,
+    /// All possible values, in state order.
+    pub values: Vec<String>,
+}
+
+/// A single block state.
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+pub struct PumpkinBlockState {
+    /// The state id.
+    pub id: u32,
+    /// The light level emitted by the state.
+    #[serde(default)]
+    pub luminance: u8,
+    /// How much light the state blocks.
+    #[serde(default)]
+    pub opacity: Option<u8>,
+    /// The ids of the state's collision shapes.
+    #[serde(default)]
+    pub collision_shapes: Vec<u16>,
+    /// The ids of the state's outline shapes.
+    #[serde(default)]
+    pub outline_shapes: Vec<u16>,
+    /// The id of the state's block entity type, if it has one.
+    #[serde(default)]
+    pub block_entity_type: Option<u32>,
+    /// Any other fields not covered above.
+    #[serde(flatten)]
+    pub other: serde_json::Map<String, serde_json::Value>,
+}
+
+/// A recipe.
+///
+/// Recipes differ greatly by type,
+/// so all fields other than the type are kept as raw JSON.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize)]
+pub struct PumpkinRecipe {
+    /// The recipe type, such as `minecraft:crafting_shaped`.
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// The remaining recipe data.
+    #[serde(flatten)]
+    pub data: serde_json::Map<String, serde_json::Value>,
+}
+
+#[test]
+#[cfg(test)]
+fn parse() {
+    let blocks: PumpkinBlocks = serde_json::from_str(TRIMMED_EXAMPLE).unwrap();
+    assert_eq!(blocks.shapes.len(), 2);
+    assert_eq!(blocks.blocks.len(), 2);
+
+    let stone = blocks.block("minecraft:stone").unwrap();
+    assert_eq!(stone.state_properties(1), Some(Vec::new()));
+    assert_eq!(blocks.shapes(&stone.states[0].collision_shapes).count(), 1);
+
+    let (slab, state) = blocks.state(5).unwrap();
+    assert_eq!(slab.name, "oak_slab");
+    assert_eq!(state.other.get("air"), Some(&serde_json::Value::Bool(false)));
+    assert_eq!(slab.state_properties(2), Some(vec![("type", "top"), ("waterlogged", "true")]));
+    assert_eq!(slab.state_properties(5), Some(vec![("type", "bottom"), ("waterlogged", "false")]));
+    assert_eq!(slab.state_properties(8), None);
+
+    let recipe: PumpkinRecipe =
+        serde_json::from_str(r#"{"type":"minecraft:smelting","cookingtime":200}"#).unwrap();
+    assert_eq!(recipe.kind, "minecraft:smelting");
+    assert_eq!(recipe.data.get("cookingtime"), Some(&serde_json::Value::from(200)));
+}
+
+#[cfg(test)]
+const TRIMMED_EXAMPLE: &str = r#"{
+  "block_entity_types": [],
+  "shapes": [
+    { "min": [0.0, 0.0, 0.0], "max": [1.0, 1.0, 1.0] },
+    { "min": [0.0, 0.0, 0.0], "max": [1.0, 0.5, 1.0] }
+  ],
+  "blocks": [
+    {
+      "id": 1,
+      "name": "stone",
+      "translation_key": "block.minecraft.stone",
+      "hardness": 1.5,
+      "blast_resistance": 6.0,
+      "item_id": 1,
+      "default_state_id": 1,
+      "states": [
+        { "id": 1, "air": false, "luminance": 0, "collision_shapes": [0], "outline_shapes": [0] }
+      ]
+    },
+    {
+      "id": 2,
+      "name": "oak_slab",
+      "translation_key": "block.minecraft.oak_slab",
+      "hardness": 2.0,
+      "blast_resistance": 3.0,
+      "item_id": 2,
+      "default_state_id": 5,
+      "properties": [
+        { "name": "type", "values": ["top", "bottom", "double"] },
+        { "name": "waterlogged", "values": ["true", "false"] }
+      ],
+      "states": [
+        { "id": 2, "air": false, "collision_shapes": [1] },
+        { "id": 3, "air": false, "collision_shapes": [1] },
+        { "id": 4, "air": false, "collision_shapes": [1] },
+        { "id": 5, "air": false, "collision_shapes": [1] },
+        { "id": 6, "air": false, "collision_shapes": [0] },
+        { "id": 7, "air": false, "collision_shapes": [0] }
+      ]
+    }
+  ]
+}"#;
@@ -43,9 +43,10 @@ impl PumpkinExtractor {
         deps: &mut DependencyContainer,
     ) -> anyhow::Result<&Path> {
         if !self.versions.contains_key(version) {
-            Self::extract_version(version, deps)
+            let path = Self::extract_version(version, deps)
                 .await
                 .map_err(|err| anyhow::anyhow!("Pumpkin Extractor: {err}"))?;
+            self.versions.insert(version.clone(), path);
         }
 
         Ok(self.version(version).unwrap())
@@ -59,13 +60,24 @@ impl PumpkinExtractor {
     pub const REPOSITORY: &str =
         "https://github.com/Pumpkin-MC/Extractor/archive/refs/heads/master.zip";
 
+    /// The directory the extractor writes its output to.
+    const OUTPUT_DIR: &str = "pumpkin_extractor_output";
+    /// The directory the gradle server runs in.
+    const RUN_DIR: &str = "run";
+
     async fn extract_version(
         version: &Version,
         deps: &mut DependencyContainer,
-    ) -> anyhow::Result<()> {
+    ) -> anyhow::Result<PathBuf> {
         let version_str = version.to_short_string();
         let cache = deps.cache.join(Self::CACHE_DIR);
 
+        let ver_cache = deps.cache.join(version.to_long_string()).join(Self::CACHE_DIR);
+        if tokio::fs::try_exists(&ver_cache).await? {
+            tracing::debug!("Using \"{}\"", ver_cache.display());
+            return Ok(ver_cache);
+        }
+
         if !tokio::fs::try_exists(&cache).await? {
             tokio::fs::create_dir_all(&cache).await?;
 
@@ -117,24 +129,35 @@ impl PumpkinExtractor {
         // Write the template file
         tokio::fs::write(cache.join("gradle.properties"), gradle.into_bytes()).await?;
 
+        // Remove any output left over from a previous run
+        let output = cache.join(Self::RUN_DIR).join(Self::OUTPUT_DIR);
+        if tokio::fs::try_exists(&output).await? {
+            tokio::fs::remove_dir_all(&output).await?;
+        }
+
         // Run the extractor
+        tracing::debug!("Extracting \"{version_str}\" with Pumpkin Extractor");
         let process =
             Command::new("./gradlew").arg("runServer").current_dir(&cache).output().await?;
 
-        if process.status.success() {
-            // Cache the generated output
-
-            let _ver_cache = deps.cache.join(version_str).join(Self::CACHE_DIR);
-            // tokio::fs::rename(cache.join("pumpkin_extractor_output"), ver_cache).await?;
-
-            Ok(())
-        } else {
+        if !process.status.success() || !tokio::fs::try_exists(&output).await? {
             // Delete the partial output
+            if tokio::fs::try_exists(&output).await? {
+                tokio::fs::remove_dir_all(&output).await?;
+            }
 
             let stdout = String::from_utf8_lossy(&process.stdout);
             let stderr = String::from_utf8_lossy(&process.stderr);
-            Err(anyhow::anyhow!("DataGenerator failed:\n{stderr}\n{stdout}"))
+            anyhow::bail!("Pumpkin Extractor failed:\n{stderr}\n{stdout}");
         }
+
+        // Cache the generated output
+        if let Some(parent) = ver_cache.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::rename(output, &ver_cache).await?;
+
+        Ok(ver_cache)
     }
 
     const GRADLE_TEMPLATE: &str = "# Done to increase the memory available to gradle.
@@ -0,0 +1,212 @@
+//! TODO
+
+use std::{
+    fmt::Write,
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+use froglight_tool_macros::Dependency;
+use hashbrown::HashMap;
+use sha2::{Digest, Sha256};
+use zip::ZipArchive;
+
+use super::MinecraftJar;
+use crate::{container::DependencyContainer, version::Version};
+
+/// A collection of unpacked [`ServerBundle`]s.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Dependency)]
+#[dep(path = crate)]
+pub struct ServerBundles(HashMap<Version, ServerBundle>);
+
+/// The contents of an unpacked server jar.
+///
+/// Since `1.18` the server jar is a bundler,
+/// which contains the actual server jar and all of its libraries.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerBundle {
+    /// The path to the server jar.
+    pub server: PathBuf,
+    /// The main class of the server, if known.
+    pub main_class: Option<String>,
+    /// The paths to the server's libraries.
+    pub libraries: Vec<PathBuf>,
+}
+
+impl ServerBundles {
+    /// Get the [`ServerBundle`] for the given version.
+    ///
+    /// Returns `None` if the bundle has not been unpacked yet.
+    #[must_use]
+    pub fn version(&self, version: &Version) -> Option<&ServerBundle> { self.0.get(version) }
+
+    /// Get the [`ServerBundle`] for the given version.
+    ///
+    /// # Errors
+    /// Returns an error if there was an error unpacking the bundle.
+    #[expect(clippy::missing_panics_doc)]
+    pub async fn get_version(
+        &mut self,
+        version: &Version,
+        deps: &mut DependencyContainer,
+    ) -> anyhow::Result<&ServerBundle> {
+        if !self.0.contains_key(version) {
+            deps.get_or_retrieve::<MinecraftJar>().await?;
+            deps.scoped_fut::<MinecraftJar, anyhow::Result<()>>(
+                async |jar: &mut MinecraftJar, deps: &mut DependencyContainer| {
+                    let server = jar.get_server(version, deps).await?;
+                    self.0.insert(version.clone(), ServerBundle::unpack(server).await?);
+                    Ok(())
+                },
+            )
+            .await
+            .map_err(|err| anyhow::anyhow!("ServerBundles: {err}"))?;
+        }
+
+        Ok(self.version(version).unwrap())
+    }
+}
+
+impl ServerBundle {
+    const VERSIONS_LIST: &str = "META-INF/versions.list";
+    const VERSIONS_DIR: &str = "META-INF/versions";
+    const LIBRARIES_LIST: &str = "META-INF/libraries.list";
+    const LIBRARIES_DIR: &str = "META-INF/libraries";
+    const MAIN_CLASS: &str = "META-INF/main-class";
+
+    /// Unpack a server jar into a directory next to it.
+    ///
+    /// If the jar is not a bundler, it is used as-is.
+    async fn unpack(jar: &Path) -> anyhow::Result<Self> {
+        let mut zip = ZipArchive::new(std::io::Cursor::new(tokio::fs::read(jar).await?))?;
+
+        let Some(versions) = Self::read_string(&mut zip, Self::VERSIONS_LIST)? else {
+            tracing::debug!("Using \"{}\", not a bundler", jar.display());
+            return Ok(Self { server: jar.to_path_buf(), main_class: None, libraries: Vec::new() });
+        };
+        let libraries = Self::read_string(&mut zip, Self::LIBRARIES_LIST)?.unwrap_or_default();
+        let main_class =
+            Self::read_string(&mut zip, Self::MAIN_CLASS)?.map(|s| s.trim().to_string());
+
+        let out =
+            jar.with_file_name(format!("{}-unbundled", jar.file_stem().unwrap().to_string_lossy()));
+
+        let Some(server) = BundleEntry::parse_list(&versions).into_iter().next() else {
+            anyhow::bail!("No server jar listed in \"{}\"", Self::VERSIONS_LIST);
+        };
+        let server = Self::extract(&mut zip, &server, Self::VERSIONS_DIR, &out).await?;
+
+        let mut library_paths = Vec::new();
+        for library in BundleEntry::parse_list(&libraries) {
+            library_paths.push(Self::extract(&mut zip, &library, Self::LIBRARIES_DIR, &out).await?);
+        }
+
+        Ok(Self { server, main_class, libraries: library_paths })
+    }
+
+    /// Extract a [`BundleEntry`] from the bundler, verifying its hash.
+    async fn extract(
+        zip: &mut ZipArchive<std::io::Cursor<Vec<u8>>>,
+        entry: &BundleEntry,
+        directory: &str,
+        out: &Path,
+    ) -> anyhow::Result<PathBuf> {
+        let path = out.join(&entry.path);
+        if tokio::fs::try_exists(&path).await? {
+            tracing::trace!("Using \"{}\"", path.display());
+            return Ok(path);
+        }
+
+        tracing::debug!("Unpacking \"{}\"", entry.id);
+
+        let mut buffer = Vec::new();
+        zip.by_name(&format!("{directory}/{}", entry.path))?.read_to_end(&mut buffer)?;
+
+        let hash = Sha256::digest(&buffer).iter().fold(String::with_capacity(64), |mut acc, b| {
+            let _ = write!(acc, "{b:02x}");
+            acc
+        });
+        if hash != entry.sha256 {
+            anyhow::bail!(
+                "Hash mismatch for \"{}\", expected {} but got {hash}",
+                entry.id,
+                entry.sha256
+            );
+        }
+
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::write(&path, buffer).await?;
+
+        Ok(path)
+    }
+
+    /// Read a file from the bundler as a string.
+    ///
+    /// Returns `None` if the file does not exist.
+    fn read_string(
+        zip: &mut ZipArchive<std::io::Cursor<Vec<u8>>>,
+        name: &str,
+    ) -> anyhow::Result<Option<String>> {
+        match zip.by_name(name) {
+            Ok(mut file) => {
+                let mut content = String::new();
+                file.read_to_string(&mut content)?;
+                Ok(Some(content))
+            }
+            Err(zip::result::ZipError::FileNotFound) => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+}
+
+/// An entry in a bundler's `versions.list` or `libraries.list`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct BundleEntry {
+    sha256: String,
+    id: String,
+    path: String,
+}
+
+impl BundleEntry {
+    /// Parse a list of tab-separated entries.
+    ///
+    /// Invalid lines are skipped.
+    fn parse_list(content: &str) -> Vec<Self> {
+        content
+            .lines()
+            .filter_map(|line| {
+                let mut parts = line.trim().splitn(3, '\t');
+                Some(Self {
+                    sha256: parts.next()?.to_string(),
+                    id: parts.next()?.to_string(),
+                    path: parts.next()?.to_string(),
+                })
+            })
+            .collect()
+    }
+}
+
+#[test]
+#[cfg(test)]
+fn parse() {
+    let entries = BundleEntry::parse_list(TRIMMED_EXAMPLE);
+    assert_eq!(entries.len(), 2);
+
+    assert_eq!(
+        entries[0].sha256,
+        "5e7a2a23e0d6c9d6d8d8c3a8e44c5b4b2f2c1d0b5cd56c08d9c8e3c8a5f2e2c1"
+    );
+    assert_eq!(entries[0].id, "com.mojang:brigadier:1.3.10");
+    assert_eq!(entries[0].path, "com/mojang/brigadier/1.3.10/brigadier-1.3.10.jar");
+
+    assert_eq!(entries[1].id, "com.mojang:datafixerupper:8.0.16");
+    assert_eq!(entries[1].path, "com/mojang/datafixerupper/8.0.16/datafixerupper-8.0.16.jar");
+}
+
+#[cfg(test)]
+const TRIMMED_EXAMPLE: &str = "5e7a2a23e0d6c9d6d8d8c3a8e44c5b4b2f2c1d0b5cd56c08d9c8e3c8a5f2e2c1\tcom.mojang:brigadier:1.3.10\tcom/mojang/brigadier/1.3.10/brigadier-1.3.10.jar
+0a48ab2e5e8d7a2c30b1a2bfcb1d4e52a4b2b1c8e5cd0e3a4dbe8d5cc2a3e4d1\tcom.mojang:datafixerupper:8.0.16\tcom/mojang/datafixerupper/8.0.16/datafixerupper-8.0.16.jar
+
+";
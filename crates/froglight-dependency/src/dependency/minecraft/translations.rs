@@ -6,58 +6,213 @@ use hashbrown::HashMap;
 use zip::ZipArchive;
 
 use crate::{
-    container::DependencyContainer, dependency::minecraft::MinecraftJar, version::Version,
+    container::DependencyContainer,
+    dependency::{
+        minecraft::{MinecraftJar, MinecraftLibraries},
+        mojang::AssetManifests,
+    },
+    version::Version,
 };
 
 /// A collection of [`TranslationsFile`]s.
 #[derive(Debug, Default, Clone, PartialEq, Eq, Dependency)]
 #[dep(path = crate)]
-pub struct Translations(HashMap<Version, TranslationsFile>);
+pub struct Translations(HashMap<Version, HashMap<String, TranslationsFile>>);
 
 impl Translations {
-    /// Get the [`TranslationsFile`] for the given version.
+    /// The default locale, which is stored inside the client jar.
+    pub const DEFAULT_LOCALE: &'static str = "en_us";
+    /// The directory to store all downloaded language files.
+    const CACHE_DIR: &'static str = "assets";
+
+    /// Get the default [`TranslationsFile`] for the given version.
     ///
     /// Returns `None` if the translations are not yet known.
     #[must_use]
-    pub fn version(&self, version: &Version) -> Option<&TranslationsFile> { self.0.get(version) }
+    pub fn version(&self, version: &Version) -> Option<&TranslationsFile> {
+        self.locale(version, Self::DEFAULT_LOCALE)
+    }
 
-    /// Get the [`TranslationsFile`] for the given version.
+    /// Get the default [`TranslationsFile`] for the given version.
     ///
     /// # Errors
     /// Returns an error if there was an error retrieving the data.
-    #[expect(clippy::missing_panics_doc)]
     pub async fn get_version(
         &mut self,
         version: &Version,
         deps: &mut DependencyContainer,
     ) -> anyhow::Result<&TranslationsFile> {
-        if !self.0.contains_key(version) {
-            deps.get_or_retrieve::<MinecraftJar>().await?;
-            deps.scoped_fut::<MinecraftJar, anyhow::Result<()>>(
-                async |jar: &mut MinecraftJar, deps: &mut DependencyContainer| {
-                    let client = jar.get_client(version, deps).await?;
-
-                    let mut zip = ZipArchive::new(std::fs::File::open(client)?)?;
-                    match zip.by_name("assets/minecraft/lang/en_us.json") {
-                        Err(err) => anyhow::bail!("Translations: {err}"),
-                        Ok(mut file) => {
-                            let mut data = String::new();
-                            file.read_to_string(&mut data)?;
-                            let translations = serde_json::from_str(&data)?;
-
-                            self.0
-                                .insert(version.clone(), TranslationsFile(Arc::new(translations)));
-                        }
-                    }
+        self.get_locale(version, Self::DEFAULT_LOCALE, deps).await
+    }
+
+    /// Get the [`TranslationsFile`] for the given version and locale.
+    ///
+    /// Returns `None` if the translations are not yet known.
+    #[must_use]
+    pub fn locale(&self, version: &Version, locale: &str) -> Option<&TranslationsFile> {
+        self.0.get(version).and_then(|locales| locales.get(locale))
+    }
+
+    /// Get the [`TranslationsFile`] for the given version and locale.
+    ///
+    /// # Errors
+    /// Returns an error if there was an error retrieving the data,
+    /// or if the locale does not exist.
+    #[expect(clippy::missing_panics_doc)]
+    pub async fn get_locale(
+        &mut self,
+        version: &Version,
+        locale: &str,
+        deps: &mut DependencyContainer,
+    ) -> anyhow::Result<&TranslationsFile> {
+        if self.locale(version, locale).is_none() {
+            let translations = if locale == Self::DEFAULT_LOCALE {
+                Self::read_client_locale(version, deps).await
+            } else {
+                Self::read_asset_locale(version, locale, deps).await
+            }
+            .map_err(|err| anyhow::anyhow!("Translations: {err}"))?;
+
+            self.0.entry(version.clone()).or_default().insert(locale.to_string(), translations);
+        }
 
-                    Ok(())
+        Ok(self.locale(version, locale).unwrap())
+    }
+
+    /// Get all [`TranslationsFile`]s for the given version, keyed by locale.
+    ///
+    /// Returns `None` if no translations are known for the version.
+    #[must_use]
+    pub fn locales(&self, version: &Version) -> Option<&HashMap<String, TranslationsFile>> {
+        self.0.get(version)
+    }
+
+    /// Get all [`TranslationsFile`]s for the given version, keyed by locale.
+    ///
+    /// # Errors
+    /// Returns an error if there was an error retrieving the data.
+    #[expect(clippy::missing_panics_doc)]
+    pub async fn get_all_locales(
+        &mut self,
+        version: &Version,
+        deps: &mut DependencyContainer,
+    ) -> anyhow::Result<&HashMap<String, TranslationsFile>> {
+        deps.get_or_retrieve::<AssetManifests>().await?;
+        let mut locales = deps
+            .scoped_fut::<AssetManifests, anyhow::Result<Vec<String>>>(
+                async |manifests: &mut AssetManifests, deps: &mut DependencyContainer| {
+                    let assets = manifests.get_assets(version, deps).await?;
+                    Ok(assets.languages().map(ToString::to_string).collect())
                 },
             )
             .await
             .map_err(|err| anyhow::anyhow!("Translations: {err}"))?;
+
+        locales.push(Self::DEFAULT_LOCALE.to_string());
+        locales.sort_unstable();
+
+        for locale in &locales {
+            self.get_locale(version, locale, deps).await?;
         }
 
-        Ok(self.version(version).unwrap())
+        Ok(self.locales(version).unwrap())
+    }
+
+    /// Look up a translation by version, locale, and key.
+    ///
+    /// Returns `None` if the locale is not yet known
+    /// or if the key is not translated.
+    #[must_use]
+    pub fn translate(&self, version: &Version, locale: &str, key: &str) -> Option<&str> {
+        self.locale(version, locale).and_then(|file| file.get(key)).map(String::as_str)
+    }
+
+    /// Get all keys that are missing from each loaded locale,
+    /// compared to the default locale.
+    ///
+    /// Returns `None` if the default locale is not yet known.
+    #[must_use]
+    pub fn untranslated(&self, version: &Version) -> Option<HashMap<&str, Vec<&str>>> {
+        let locales = self.locales(version)?;
+        let reference = locales.get(Self::DEFAULT_LOCALE)?;
+
+        Some(
+            locales
+                .iter()
+                .filter(|(locale, _)| *locale != Self::DEFAULT_LOCALE)
+                .map(|(locale, file)| (locale.as_str(), file.untranslated(reference).collect()))
+                .collect(),
+        )
+    }
+}
+
+impl Translations {
+    /// Read the default locale from the client jar.
+    async fn read_client_locale(
+        version: &Version,
+        deps: &mut DependencyContainer,
+    ) -> anyhow::Result<TranslationsFile> {
+        deps.get_or_retrieve::<MinecraftJar>().await?;
+        deps.scoped_fut::<MinecraftJar, anyhow::Result<TranslationsFile>>(
+            async |jar: &mut MinecraftJar, deps: &mut DependencyContainer| {
+                let client = jar.get_client(version, deps).await?;
+
+                let mut zip = ZipArchive::new(std::fs::File::open(client)?)?;
+                let mut file =
+                    zip.by_name(&format!("assets/minecraft/lang/{}.json", Self::DEFAULT_LOCALE))?;
+
+                let mut data = String::new();
+                file.read_to_string(&mut data)?;
+                Ok(TranslationsFile(Arc::new(serde_json::from_str(&data)?)))
+            },
+        )
+        .await
+    }
+
+    /// Read a locale from the version's asset index.
+    async fn read_asset_locale(
+        version: &Version,
+        locale: &str,
+        deps: &mut DependencyContainer,
+    ) -> anyhow::Result<TranslationsFile> {
+        deps.get_or_retrieve::<AssetManifests>().await?;
+        deps.scoped_fut::<AssetManifests, anyhow::Result<TranslationsFile>>(
+            async |manifests: &mut AssetManifests, deps: &mut DependencyContainer| {
+                let assets = manifests.get_assets(version, deps).await?;
+                let Some(entry) = assets.language(locale) else {
+                    anyhow::bail!("Locale \"{locale}\" not found for {version}");
+                };
+
+                let path = deps.cache.join(Self::CACHE_DIR).join(entry.get_path());
+                if tokio::fs::try_exists(&path).await? {
+                    tracing::debug!("Reading \"{}\"", path.display());
+                    let content = tokio::fs::read(&path).await?;
+                    if MinecraftLibraries::hash(&content) == entry.hash {
+                        return Ok(TranslationsFile(Arc::new(serde_json::from_slice(&content)?)));
+                    }
+                    tracing::warn!("Hash mismatch for \"{}\", retrieving again", path.display());
+                }
+
+                let url = entry.get_url();
+                tracing::debug!("Retrieving \"{url}\"");
+
+                // Download the file and make sure it is intact
+                let content = deps.client.get(&url).send().await?.bytes().await?;
+                let hash = MinecraftLibraries::hash(&content);
+                if hash != entry.hash {
+                    anyhow::bail!(
+                        "Hash mismatch for \"{url}\", expected {} but got {hash}",
+                        entry.hash
+                    );
+                }
+
+                tokio::fs::create_dir_all(path.parent().unwrap()).await?;
+                tokio::fs::write(&path, &content).await?;
+
+                Ok(TranslationsFile(Arc::new(serde_json::from_slice(&content)?)))
+            },
+        )
+        .await
     }
 }
 
@@ -106,6 +261,17 @@ impl TranslationsFile {
     }
 }
 
+impl TranslationsFile {
+    /// Iterate over all keys in the `reference` file
+    /// that are missing from this file.
+    pub fn untranslated<'a>(
+        &'a self,
+        reference: &'a TranslationsFile,
+    ) -> impl Iterator<Item = &'a str> + 'a {
+        reference.keys().filter(|key| !self.contains_key(*key)).map(String::as_str)
+    }
+}
+
 impl std::ops::Deref for TranslationsFile {
     type Target = HashMap<String, String>;
     fn deref(&self) -> &Self::Target { &self.0 }
@@ -0,0 +1,236 @@
+//! TODO
+
+use cafebabe::{
+    attributes::{AttributeData, CodeData},
+    bytecode::Opcode,
+    constant_pool::{BootstrapArgument, LiteralConstant, Loadable, MemberRef},
+    ClassFile,
+};
+use hashbrown::HashMap;
+
+/// A cross-reference index of a
+/// [`CodeBundle`](super::minecraft_code::CodeBundle).
+///
+/// References are recorded as written in the bytecode,
+/// so a call to `ZombieEntity.tick()` is not listed under `Entity.tick()`
+/// even if `ZombieEntity` does not override it.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct XrefIndex {
+    invocations: HashMap<(String, String), Vec<(String, XrefLocation)>>,
+    field_reads: HashMap<(String, String), Vec<XrefLocation>>,
+    field_writes: HashMap<(String, String), Vec<XrefLocation>>,
+    strings: HashMap<String, Vec<XrefLocation>>,
+}
+
+/// A location inside of a method.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct XrefLocation {
+    /// The class containing the method.
+    pub class: String,
+    /// The name of the method.
+    pub method: String,
+    /// The descriptor of the method.
+    pub descriptor: String,
+    /// The bytecode offset inside the method.
+    pub offset: usize,
+}
+
+impl XrefIndex {
+    /// Build an [`XrefIndex`] from a set of classes.
+    pub(crate) fn build<'a, 'b: 'a>(classes: impl Iterator<Item = &'a ClassFile<'b>>) -> Self {
+        let mut index = Self::default();
+
+        for class in classes {
+            let bootstrap = class.attributes.iter().find_map(|attr| match &attr.data {
+                AttributeData::BootstrapMethods(methods) => Some(methods),
+                _ => None,
+            });
+
+            for method in &class.methods {
+                let Some(code) = method.attributes.iter().find_map(|attr| match &attr.data {
+                    AttributeData::Code(code) => Some(code),
+                    _ => None,
+                }) else {
+                    continue;
+                };
+
+                let location = XrefLocation {
+                    class: class.this_class.to_string(),
+                    method: method.name.to_string(),
+                    descriptor: method.descriptor.to_string(),
+                    offset: 0,
+                };
+                index.index_code(code, &location, |attr_index| {
+                    bootstrap
+                        .and_then(|methods| methods.get(usize::from(attr_index)))
+                        .into_iter()
+                        .flat_map(|entry| &entry.arguments)
+                        .filter_map(|arg| match arg {
+                            BootstrapArgument::MethodHandle(handle) => Some((
+                                handle.class_name.to_string(),
+                                handle.member_ref.name.to_string(),
+                                handle.member_ref.descriptor.to_string(),
+                            )),
+                            _ => None,
+                        })
+                        .collect()
+                });
+            }
+        }
+
+        // Sort all locations so results do not depend on class order.
+        index.invocations.values_mut().for_each(|v| v.sort_unstable_by(|a, b| a.1.cmp(&b.1)));
+        index.field_reads.values_mut().for_each(|v| v.sort_unstable());
+        index.field_writes.values_mut().for_each(|v| v.sort_unstable());
+        index.strings.values_mut().for_each(|v| v.sort_unstable());
+
+        index
+    }
+
+    fn index_code(
+        &mut self,
+        code: &CodeData<'_>,
+        location: &XrefLocation,
+        bootstrap: impl Fn(u16) -> Vec<(String, String, String)>,
+    ) {
+        let Some(bytecode) = &code.bytecode else { return };
+
+        for (offset, opcode) in &bytecode.opcodes {
+            let location = XrefLocation { offset: *offset, ..location.clone() };
+            match opcode {
+                Opcode::Invokevirtual(member)
+                | Opcode::Invokespecial(member)
+                | Opcode::Invokestatic(member)
+                | Opcode::Invokeinterface(member, _) => {
+                    self.invocations
+                        .entry(Self::key(member))
+                        .or_default()
+                        .push((member.name_and_type.descriptor.to_string(), location));
+                }
+                Opcode::Invokedynamic(invoke) => {
+                    for (class, name, descriptor) in bootstrap(invoke.attr_index) {
+                        self.invocations
+                            .entry((class, name))
+                            .or_default()
+                            .push((descriptor, location.clone()));
+                    }
+                }
+                Opcode::Getfield(member) | Opcode::Getstatic(member) => {
+                    self.field_reads.entry(Self::key(member)).or_default().push(location);
+                }
+                Opcode::Putfield(member) | Opcode::Putstatic(member) => {
+                    self.field_writes.entry(Self::key(member)).or_default().push(location);
+                }
+                Opcode::Ldc(Loadable::LiteralConstant(LiteralConstant::String(string)))
+                | Opcode::LdcW(Loadable::LiteralConstant(LiteralConstant::String(string))) => {
+                    self.strings.entry(string.to_string()).or_default().push(location);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn key(member: &MemberRef<'_>) -> (String, String) {
+        (member.class_name.to_string(), member.name_and_type.name.to_string())
+    }
+
+    /// Get all locations that invoke a method.
+    ///
+    /// Method references passed to `invokedynamic`,
+    /// such as `Block::new`, are included.
+    ///
+    /// If `descriptor` is `None`, all overloads of the method are included.
+    #[must_use]
+    pub fn callers(
+        &self,
+        class: &str,
+        method: &str,
+        descriptor: Option<&str>,
+    ) -> Vec<&XrefLocation> {
+        self.invocations
+            .get(&(class.to_string(), method.to_string()))
+            .into_iter()
+            .flatten()
+            .filter(|(desc, _)| descriptor.is_none_or(|d| d == desc))
+            .map(|(_, location)| location)
+            .collect()
+    }
+
+    /// Get all locations that read a field.
+    #[must_use]
+    pub fn field_readers(&self, class: &str, field: &str) -> &[XrefLocation] {
+        self.field_reads.get(&(class.to_string(), field.to_string())).map_or(&[], Vec::as_slice)
+    }
+
+    /// Get all locations that write to a field.
+    #[must_use]
+    pub fn field_writers(&self, class: &str, field: &str) -> &[XrefLocation] {
+        self.field_writes.get(&(class.to_string(), field.to_string())).map_or(&[], Vec::as_slice)
+    }
+
+    /// Get all locations that load a string constant.
+    #[must_use]
+    pub fn string_loads(&self, string: &str) -> &[XrefLocation] {
+        self.strings.get(string).map_or(&[], Vec::as_slice)
+    }
+
+    /// Iterate over all string constants and the locations that load them.
+    pub fn strings(&self) -> impl Iterator<Item = (&str, &[XrefLocation])> {
+        self.strings.iter().map(|(string, locations)| (string.as_str(), locations.as_slice()))
+    }
+}
+
+#[test]
+#[cfg(test)]
+fn index() {
+    use cafebabe::MethodAccessFlags;
+
+    use super::{class_builder::ClassBuilder, minecraft_code::CodeBundle};
+
+    let blocks = ClassBuilder::new("Blocks")
+        .method(MethodAccessFlags::STATIC, "<clinit>", "()V", |code| {
+            code.ldc_string("stone");
+            code.invokestatic("Blocks", "register", "(Ljava/lang/String;)LBlock;");
+            code.putstatic("Blocks", "STONE", "LBlock;");
+            code.return_();
+        })
+        .build();
+    let items = ClassBuilder::new("Items")
+        .method(MethodAccessFlags::STATIC, "<clinit>", "()V", |code| {
+            code.getstatic("Blocks", "STONE", "LBlock;");
+            code.invokestatic("Items", "register", "(LBlock;)LItem;");
+            code.putstatic("Items", "STONE", "LItem;");
+            code.return_();
+        })
+        .build();
+    let bundle = CodeBundle::from_classes([
+        (String::from("Blocks"), blocks),
+        (String::from("Items"), items),
+    ]);
+    bundle.with_classes(|classes| {
+        let xrefs = classes.xrefs();
+
+        let at = |class: &str, offset: usize| XrefLocation {
+            class: class.to_string(),
+            method: String::from("<clinit>"),
+            descriptor: String::from("()V"),
+            offset,
+        };
+
+        // Callers can be filtered by descriptor.
+        assert_eq!(xrefs.callers("Blocks", "register", None), [&at("Blocks", 3)]);
+        assert_eq!(xrefs.callers("Items", "register", Some("(LBlock;)LItem;")), [&at("Items", 3)]);
+        assert!(xrefs.callers("Items", "register", Some("(Ljava/lang/String;)LItem;")).is_empty());
+
+        // Field accesses are listed under the class named in the bytecode.
+        assert_eq!(xrefs.field_writers("Blocks", "STONE"), [at("Blocks", 6)]);
+        assert_eq!(xrefs.field_readers("Blocks", "STONE"), [at("Items", 0)]);
+        assert_eq!(xrefs.field_writers("Items", "STONE"), [at("Items", 6)]);
+        assert!(xrefs.field_readers("Items", "STONE").is_empty());
+
+        // String constants are indexed by their value.
+        assert_eq!(xrefs.string_loads("stone"), [at("Blocks", 0)]);
+        assert!(xrefs.string_loads("dirt").is_empty());
+        assert_eq!(xrefs.strings().count(), 1);
+    });
+}
@@ -73,6 +73,27 @@ pub struct AssetManifest {
     /// The objects in the manifest.
     pub objects: HashMap<String, AssetManifestEntry>,
 }
+impl AssetManifest {
+    /// Get the [`AssetManifestEntry`] for a language file.
+    ///
+    /// Returns `None` if the language does not exist.
+    #[must_use]
+    pub fn language(&self, locale: &str) -> Option<&AssetManifestEntry> {
+        self.objects.get(&format!("minecraft/lang/{locale}.json"))
+    }
+
+    /// Iterate over all languages in the manifest.
+    ///
+    /// # Note
+    /// The `en_us` language is not included,
+    /// as it is stored inside the client jar.
+    pub fn languages(&self) -> impl Iterator<Item = &str> {
+        self.objects.keys().filter_map(|key| {
+            key.strip_prefix("minecraft/lang/").and_then(|lang| lang.strip_suffix(".json"))
+        })
+    }
+}
+
 impl std::ops::Deref for AssetManifest {
     type Target = HashMap<String, AssetManifestEntry>;
     fn deref(&self) -> &Self::Target { &self.objects }
@@ -91,9 +112,20 @@ pub struct AssetManifestEntry {
 }
 
 impl AssetManifestEntry {
+    const RESOURCE_URL: &'static str = "https://resources.download.minecraft.net";
+
     /// Get the URL of the asset.
     #[must_use]
-    pub fn get_url(&self) -> String { todo!("TODO: Get URL from hash") }
+    pub fn get_url(&self) -> String { format!("{}/{}", Self::RESOURCE_URL, self.get_path()) }
+
+    /// Get the relative path of the asset.
+    ///
+    /// Assets are stored in a directory named after
+    /// the first two characters of their hash.
+    #[must_use]
+    pub fn get_path(&self) -> String {
+        format!("{}/{}", self.hash.get(..2).unwrap_or_default(), self.hash)
+    }
 }
 
 #[test]
@@ -106,6 +138,28 @@ fn parse() {
     assert_eq!(manifest["icons/icon_32x32.png"].hash, "af96f55a90eaf11b327f1b5f8834a051027dc506");
     assert_eq!(manifest["icons/icon_128x128.png"].hash, "b62ca8ec10d07e6bf5ac8dae0c8c1d2e6a1e3356");
     assert_eq!(manifest["icons/icon_256x256.png"].hash, "8030dd9dc315c0381d52c4782ea36c6baf6e8135");
+
+    assert_eq!(
+        manifest["icons/icon_16x16.png"].get_url(),
+        "https://resources.download.minecraft.net/5f/5ff04807c356f1beed0b86ccf659b44b9983e3fa"
+    );
+}
+
+#[test]
+#[cfg(test)]
+fn languages() {
+    let mut manifest: AssetManifest = serde_json::from_str(TRIMMED_EXAMPLE).unwrap();
+    assert_eq!(manifest.languages().count(), 0);
+
+    let entry = AssetManifestEntry {
+        hash: String::from("d3ae4d5cf5d3a0e6a6a0dba2b6ff24c5ce2e29e8"),
+        size: 461_346,
+    };
+    manifest.insert(String::from("minecraft/lang/de_de.json"), entry.clone());
+
+    assert_eq!(manifest.languages().collect::<Vec<_>>(), ["de_de"]);
+    assert_eq!(manifest.language("de_de"), Some(&entry));
+    assert_eq!(manifest.language("en_us"), None);
 }
 
 #[cfg(test)]
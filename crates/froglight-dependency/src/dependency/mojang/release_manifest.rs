@@ -85,6 +85,9 @@ pub struct ReleaseManifest {
     /// The Java version.
     #[serde(rename = "javaVersion")]
     pub java_version: ReleaseJavaVersion,
+    /// The libraries required by the version.
+    #[serde(default)]
+    pub libraries: Vec<ReleaseLibrary>,
     /// The main class.
     #[serde(rename = "mainClass")]
     pub main_class: String,
@@ -101,6 +104,20 @@ pub struct ReleaseManifest {
     pub release_type: String,
 }
 
+impl ReleaseManifest {
+    /// Iterate over all libraries that are allowed on the given operating
+    /// system.
+    pub fn libraries_for<'a>(&'a self, os: &'a str) -> impl Iterator<Item = &'a ReleaseLibrary> {
+        self.libraries.iter().filter(move |lib| lib.is_allowed(os))
+    }
+
+    /// Iterate over all library artifacts required on the given operating
+    /// system, including any native artifacts.
+    pub fn artifacts_for<'a>(&'a self, os: &'a str) -> impl Iterator<Item = &'a ReleaseArtifact> {
+        self.libraries_for(os).flat_map(move |lib| lib.artifacts(os))
+    }
+}
+
 /// Information about the version's [`AssetManifest`](super::AssetManifest).
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ReleaseAssetIndex {
@@ -141,6 +158,127 @@ pub struct ReleaseDownload {
     pub url: String,
 }
 
+/// A library required by the version.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReleaseLibrary {
+    /// The maven coordinates of the library.
+    pub name: String,
+    /// The library's downloads.
+    pub downloads: ReleaseLibraryDownloads,
+    /// The rules for when the library is required.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rules: Vec<ReleaseRule>,
+    /// The native classifiers for each operating system.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub natives: HashMap<String, String>,
+}
+
+impl ReleaseLibrary {
+    /// The operating system name used for Linux.
+    pub const LINUX: &'static str = "linux";
+
+    /// Returns `true` if the library is allowed on the given operating system.
+    ///
+    /// Libraries without any rules are always allowed,
+    /// otherwise the last matching rule decides.
+    #[must_use]
+    pub fn is_allowed(&self, os: &str) -> bool {
+        if self.rules.is_empty() {
+            return true;
+        }
+
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule| rule.matches(os))
+            .is_some_and(|rule| rule.action == ReleaseRuleAction::Allow)
+    }
+
+    /// Get the native [`ReleaseArtifact`] for the given operating system.
+    ///
+    /// Returns `None` if the library has no natives for the operating system.
+    #[must_use]
+    pub fn native(&self, os: &str) -> Option<&ReleaseArtifact> {
+        let classifier = self.natives.get(os)?.replace("${arch}", "64");
+        self.downloads.classifiers.get(&classifier)
+    }
+
+    /// Iterate over the library's [`ReleaseArtifact`]s
+    /// for the given operating system.
+    pub fn artifacts(&self, os: &str) -> impl Iterator<Item = &ReleaseArtifact> {
+        self.downloads.artifact.iter().chain(self.native(os))
+    }
+}
+
+/// Download information for a library.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReleaseLibraryDownloads {
+    /// The library jar.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub artifact: Option<ReleaseArtifact>,
+    /// Additional jars, such as natives.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub classifiers: HashMap<String, ReleaseArtifact>,
+}
+
+/// Information about a library download.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReleaseArtifact {
+    /// The relative path of the artifact.
+    pub path: String,
+    /// The SHA1 hash of the artifact.
+    pub sha1: String,
+    /// The size of the artifact.
+    pub size: u32,
+    /// The URL of the artifact.
+    pub url: String,
+}
+
+/// A rule for when a library is required.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReleaseRule {
+    /// Whether the rule allows or disallows the library.
+    pub action: ReleaseRuleAction,
+    /// The operating system the rule applies to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub os: Option<ReleaseRuleOs>,
+    /// The launcher features the rule applies to.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub features: HashMap<String, bool>,
+}
+
+impl ReleaseRule {
+    /// Returns `true` if the rule applies to the given operating system.
+    ///
+    /// Rules that depend on launcher features never match.
+    #[must_use]
+    pub fn matches(&self, os: &str) -> bool {
+        self.features.is_empty()
+            && self.os.as_ref().is_none_or(|rule| rule.name.as_ref().is_none_or(|name| name == os))
+    }
+}
+
+/// The action of a [`ReleaseRule`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReleaseRuleAction {
+    /// The library is allowed.
+    Allow,
+    /// The library is disallowed.
+    Disallow,
+}
+
+/// The operating system of a [`ReleaseRule`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReleaseRuleOs {
+    /// The name of the operating system.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// The architecture of the operating system.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub arch: Option<String>,
+}
+
 /// Information about the Java version.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ReleaseJavaVersion {
@@ -160,6 +298,25 @@ fn parse() {
     assert_eq!(example.downloads.client_mappings.sha1, "98c9a121ce9d560fd9d5aa2ea576f117c0950c26");
     assert_eq!(example.downloads.server.sha1, "2c873903a90c9633dd6bd2e3501046100daceafd");
     assert_eq!(example.downloads.server_mappings.sha1, "bed2cd62c9c5cf4c173360647c577aedb65c8a1c");
+
+    assert_eq!(example.libraries.len(), 4);
+    let linux: Vec<_> = example.artifacts_for(ReleaseLibrary::LINUX).map(|a| &a.path).collect();
+    assert_eq!(
+        linux,
+        [
+            "com/mojang/brigadier/1.3.10/brigadier-1.3.10.jar",
+            "org/lwjgl/lwjgl/3.3.3/lwjgl-3.3.3-natives-linux.jar",
+            "org/lwjgl/lwjgl/lwjgl-platform/2.9.4/lwjgl-platform-2.9.4-natives-linux.jar",
+        ]
+    );
+    let macos: Vec<_> = example.artifacts_for("osx").map(|a| &a.path).collect();
+    assert_eq!(
+        macos,
+        [
+            "com/mojang/brigadier/1.3.10/brigadier-1.3.10.jar",
+            "org/lwjgl/lwjgl/3.3.3/lwjgl-3.3.3-natives-macos.jar",
+        ]
+    );
 }
 
 #[cfg(test)]
@@ -198,6 +355,84 @@ const TRIMMED_EXAMPLE: &str = r#"{
     "component": "java-runtime-delta",
     "majorVersion": 21
   },
+  "libraries": [
+    {
+      "downloads": {
+        "artifact": {
+          "path": "com/mojang/brigadier/1.3.10/brigadier-1.3.10.jar",
+          "sha1": "d15b53a14cf20fdcaa98f731af5dda654452c010",
+          "size": 78456,
+          "url": "https://libraries.minecraft.net/com/mojang/brigadier/1.3.10/brigadier-1.3.10.jar"
+        }
+      },
+      "name": "com.mojang:brigadier:1.3.10"
+    },
+    {
+      "downloads": {
+        "artifact": {
+          "path": "org/lwjgl/lwjgl/3.3.3/lwjgl-3.3.3-natives-linux.jar",
+          "sha1": "1713758e3660ba66e1e954396fd18126038b33c0",
+          "size": 114627,
+          "url": "https://libraries.minecraft.net/org/lwjgl/lwjgl/3.3.3/lwjgl-3.3.3-natives-linux.jar"
+        }
+      },
+      "name": "org.lwjgl:lwjgl:3.3.3:natives-linux",
+      "rules": [
+        {
+          "action": "allow",
+          "os": {
+            "name": "linux"
+          }
+        }
+      ]
+    },
+    {
+      "downloads": {
+        "artifact": {
+          "path": "org/lwjgl/lwjgl/3.3.3/lwjgl-3.3.3-natives-macos.jar",
+          "sha1": "33a6efa288390490ce6eb6c3df47ac21ecf648cf",
+          "size": 60543,
+          "url": "https://libraries.minecraft.net/org/lwjgl/lwjgl/3.3.3/lwjgl-3.3.3-natives-macos.jar"
+        }
+      },
+      "name": "org.lwjgl:lwjgl:3.3.3:natives-macos",
+      "rules": [
+        {
+          "action": "allow",
+          "os": {
+            "name": "osx"
+          }
+        }
+      ]
+    },
+    {
+      "downloads": {
+        "classifiers": {
+          "natives-linux": {
+            "path": "org/lwjgl/lwjgl/lwjgl-platform/2.9.4/lwjgl-platform-2.9.4-natives-linux.jar",
+            "sha1": "931074f46c795d2f7b30ed6395df5715cfd7675b",
+            "size": 578680,
+            "url": "https://libraries.minecraft.net/org/lwjgl/lwjgl/lwjgl-platform/2.9.4/lwjgl-platform-2.9.4-natives-linux.jar"
+          }
+        }
+      },
+      "name": "org.lwjgl.lwjgl:lwjgl-platform:2.9.4",
+      "natives": {
+        "linux": "natives-linux"
+      },
+      "rules": [
+        {
+          "action": "allow"
+        },
+        {
+          "action": "disallow",
+          "os": {
+            "name": "osx"
+          }
+        }
+      ]
+    }
+  ],
   "mainClass": "net.minecraft.client.main.Main",
   "minimumLauncherVersion": 21,
   "releaseTime": "2025-01-29T14:03:54+00:00",
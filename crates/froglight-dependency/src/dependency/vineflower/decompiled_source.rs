@@ -0,0 +1,239 @@
+//! TODO
+
+use std::path::{Path, PathBuf};
+
+use froglight_tool_macros::Dependency;
+use hashbrown::HashMap;
+use regex::Regex;
+
+use crate::{
+    container::DependencyContainer,
+    dependency::{
+        vineflower::{DecompiledJar, Vineflower},
+        yarn::MappedJar,
+    },
+    version::Version,
+};
+
+/// Decompiled Java source code, looked up by class.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Dependency)]
+#[dep(path = crate)]
+pub struct DecompiledSource(HashMap<Version, HashMap<String, String>>);
+
+/// A line of source code that matched a search.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceMatch {
+    /// The class the line belongs to, such as `net/minecraft/block/Blocks`.
+    pub class: String,
+    /// The line number, starting at `1`.
+    pub line: usize,
+    /// The contents of the line.
+    pub text: String,
+}
+
+impl DecompiledSource {
+    /// Get the source code of a class for the given version.
+    ///
+    /// Returns `None` if the source is not yet known.
+    #[must_use]
+    pub fn class(&self, version: &Version, class: &str) -> Option<&str> {
+        self.0
+            .get(version)
+            .and_then(|classes| classes.get(&Self::outer_class(class)))
+            .map(String::as_str)
+    }
+
+    /// Get the source code of a class for the given version.
+    ///
+    /// Accepts both `net/minecraft/block/Blocks` and
+    /// `net.minecraft.block.Blocks`. Inner classes return the source of
+    /// their outer class.
+    ///
+    /// If the client or server jar has already been fully decompiled the source
+    /// is read from there, otherwise only the requested class is decompiled.
+    ///
+    /// # Errors
+    /// Returns an error if the class does not exist or could not be
+    /// decompiled.
+    #[expect(clippy::missing_panics_doc)]
+    pub async fn get_class(
+        &mut self,
+        version: &Version,
+        class: &str,
+        deps: &mut DependencyContainer,
+    ) -> anyhow::Result<&str> {
+        let class = Self::outer_class(class);
+        if self.class(version, &class).is_none() {
+            let source = Self::read_class(version, &class, deps)
+                .await
+                .map_err(|err| anyhow::anyhow!("DecompiledSource: {err}"))?;
+            self.0.entry(version.clone()).or_default().insert(class.clone(), source);
+        }
+
+        Ok(self.class(version, &class).unwrap())
+    }
+
+    /// Search all client sources of a version for lines matching a [`Regex`].
+    ///
+    /// This requires decompiling the entire client jar.
+    ///
+    /// # Errors
+    /// Returns an error if the jar could not be decompiled
+    /// or the sources could not be read.
+    pub async fn search(
+        version: &Version,
+        regex: &Regex,
+        deps: &mut DependencyContainer,
+    ) -> anyhow::Result<Vec<SourceMatch>> {
+        deps.get_or_retrieve::<DecompiledJar>().await?;
+        let root = deps
+            .scoped_fut::<DecompiledJar, anyhow::Result<PathBuf>>(
+                async |jar: &mut DecompiledJar, deps: &mut DependencyContainer| {
+                    jar.get_client(version, deps).await.map(Path::to_path_buf)
+                },
+            )
+            .await
+            .map_err(|err| anyhow::anyhow!("DecompiledSource: {err}"))?;
+
+        let mut matches = Vec::new();
+        let mut directories = vec![root.clone()];
+        while let Some(directory) = directories.pop() {
+            let mut entries = tokio::fs::read_dir(&directory).await?;
+            while let Some(entry) = entries.next_entry().await? {
+                let path = entry.path();
+                if entry.file_type().await?.is_dir() {
+                    directories.push(path);
+                } else if path.extension().is_some_and(|ext| ext == "java") {
+                    let class = path.strip_prefix(&root)?.with_extension("");
+                    let class = class.to_string_lossy().replace('\\', "/");
+                    let source = tokio::fs::read_to_string(&path).await?;
+                    matches.extend(Self::search_source(&class, &source, regex));
+                }
+            }
+        }
+
+        matches.sort_by(|a, b| a.class.cmp(&b.class).then(a.line.cmp(&b.line)));
+        Ok(matches)
+    }
+
+    /// Search a single source file for lines matching a [`Regex`].
+    #[must_use]
+    pub fn search_source(class: &str, source: &str, regex: &Regex) -> Vec<SourceMatch> {
+        source
+            .lines()
+            .enumerate()
+            .filter(|(_, text)| regex.is_match(text))
+            .map(|(index, text)| SourceMatch {
+                class: class.to_string(),
+                line: index + 1,
+                text: text.to_string(),
+            })
+            .collect()
+    }
+}
+
+impl DecompiledSource {
+    /// The suffix of the directory single classes are decompiled into.
+    const CLASSES_SUFFIX: &str = "-classes";
+
+    /// Normalize a class name and strip any inner classes.
+    fn outer_class(class: &str) -> String {
+        let class = class.trim_end_matches(".java").trim_end_matches(".class").replace('.', "/");
+        match class.split_once('$') {
+            Some((outer, _)) => outer.to_string(),
+            None => class,
+        }
+    }
+
+    async fn read_class(
+        version: &Version,
+        class: &str,
+        deps: &mut DependencyContainer,
+    ) -> anyhow::Result<String> {
+        let file = format!("{class}.java");
+
+        // Use the fully decompiled jars if they already exist.
+        if let Some(decompiled) = deps.get::<DecompiledJar>() {
+            for directory in [decompiled.client(version), decompiled.server(version)] {
+                let Some(path) = directory.map(|dir| dir.join(&file)) else { continue };
+                if tokio::fs::try_exists(&path).await? {
+                    tracing::debug!("Reading \"{}\"", path.display());
+                    return Ok(tokio::fs::read_to_string(path).await?);
+                }
+            }
+        }
+
+        // Otherwise decompile only the requested class.
+        deps.get_or_retrieve::<MappedJar>().await?;
+        for server in [false, true] {
+            let jar = deps
+                .scoped_fut::<MappedJar, anyhow::Result<PathBuf>>(
+                    async |jars: &mut MappedJar, deps: &mut DependencyContainer| {
+                        if server {
+                            jars.get_server(version, deps).await.map(Path::to_path_buf)
+                        } else {
+                            jars.get_client(version, deps).await.map(Path::to_path_buf)
+                        }
+                    },
+                )
+                .await?;
+
+            let output = jar.with_file_name(format!(
+                "{}{}",
+                jar.file_stem().unwrap().to_string_lossy(),
+                Self::CLASSES_SUFFIX
+            ));
+
+            let path = output.join(&file);
+            if !tokio::fs::try_exists(&path).await? {
+                deps.get_or_retrieve::<Vineflower>()
+                    .await?
+                    .decompile_class(&jar, class, &output)
+                    .await?;
+            }
+
+            if tokio::fs::try_exists(&path).await? {
+                tracing::debug!("Reading \"{}\"", path.display());
+                return Ok(tokio::fs::read_to_string(path).await?);
+            }
+        }
+
+        anyhow::bail!("Could not find class \"{class}\"")
+    }
+}
+
+#[test]
+#[cfg(test)]
+fn search() {
+    assert_eq!(
+        DecompiledSource::outer_class("net.minecraft.block.Blocks"),
+        "net/minecraft/block/Blocks"
+    );
+    assert_eq!(
+        DecompiledSource::outer_class("net/minecraft/block/Block$Settings"),
+        "net/minecraft/block/Block"
+    );
+
+    let regex = Regex::new(r#"register\("stone""#).unwrap();
+    let matches =
+        DecompiledSource::search_source("net/minecraft/block/Blocks", TRIMMED_EXAMPLE, &regex);
+    assert_eq!(
+        matches,
+        vec![SourceMatch {
+            class: String::from("net/minecraft/block/Blocks"),
+            line: 4,
+            text: String::from(
+                r#"   public static final Block STONE = register("stone", Block::new);"#
+            ),
+        }]
+    );
+}
+
+#[cfg(test)]
+const TRIMMED_EXAMPLE: &str = r#"package net.minecraft.block;
+
+public class Blocks {
+   public static final Block STONE = register("stone", Block::new);
+   public static final Block GRANITE = register("granite", Block::new);
+}
+"#;
@@ -5,5 +5,8 @@
 mod decompiled_jar;
 pub use decompiled_jar::DecompiledJar;
 
+mod decompiled_source;
+pub use decompiled_source::{DecompiledSource, SourceMatch};
+
 mod vineflower;
 pub use vineflower::Vineflower;
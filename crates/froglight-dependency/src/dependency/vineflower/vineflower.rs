@@ -58,4 +58,37 @@ impl Vineflower {
             }
         }
     }
+
+    /// Decompile a single class from a jar file using Vineflower.
+    ///
+    /// Inner classes are decompiled along with their outer class.
+    ///
+    /// # Errors
+    /// Returns an error if the decompiling fails.
+    pub async fn decompile_class(
+        &self,
+        jar: &Path,
+        class: &str,
+        output: &Path,
+    ) -> anyhow::Result<()> {
+        tracing::debug!("Decompiling \"{class}\" from \"{}\"", jar.display());
+        tokio::fs::create_dir_all(&output).await?;
+
+        let process = Command::new("java")
+            .arg("-jar")
+            .arg(&self.0)
+            .arg(format!("--only={class}"))
+            .arg(jar)
+            .arg(output)
+            .output()
+            .await?;
+
+        if process.status.success() {
+            Ok(())
+        } else {
+            let stdout = String::from_utf8_lossy(&process.stdout);
+            let stderr = String::from_utf8_lossy(&process.stderr);
+            Err(anyhow::anyhow!("Vineflower failed:\n{stderr}\n{stdout}"))
+        }
+    }
 }
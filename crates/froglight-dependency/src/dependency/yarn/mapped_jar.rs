@@ -8,7 +8,7 @@ use hashbrown::HashMap;
 use crate::{
     container::DependencyContainer,
     dependency::{
-        minecraft::MinecraftJar,
+        minecraft::{MinecraftJar, ServerBundles},
         yarn::{TinyRemapper, YarnMappings},
     },
     version::Version,
@@ -77,10 +77,10 @@ impl MappedJar {
         deps: &mut DependencyContainer,
     ) -> anyhow::Result<&Path> {
         if !self.server.contains_key(version) {
-            deps.get_or_retrieve::<MinecraftJar>().await?;
-            deps.scoped_fut::<MinecraftJar, anyhow::Result<()>>(
-                async |jars: &mut MinecraftJar, deps: &mut DependencyContainer| {
-                    let server = jars.get_server(version, deps).await?;
+            deps.get_or_retrieve::<ServerBundles>().await?;
+            deps.scoped_fut::<ServerBundles, anyhow::Result<()>>(
+                async |bundles: &mut ServerBundles, deps: &mut DependencyContainer| {
+                    let server = &bundles.get_version(version, deps).await?.server;
                     self.server
                         .insert(version.clone(), Self::map_jar(version, server, deps).await?);
                     Ok(())
@@ -100,10 +100,8 @@ impl MappedJar {
         jar: &Path,
         deps: &mut DependencyContainer,
     ) -> anyhow::Result<PathBuf> {
-        let out = jar.with_file_name(format!(
-            "{}-mapped.jar",
-            jar.file_name().unwrap().to_string_lossy().split_once('.').unwrap().0
-        ));
+        let out = jar
+            .with_file_name(format!("{}-mapped.jar", jar.file_stem().unwrap().to_string_lossy()));
 
         if tokio::fs::try_exists(&out).await? {
             tracing::debug!("Using \"{}\"", out.display());
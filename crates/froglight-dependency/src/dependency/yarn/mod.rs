@@ -6,6 +6,9 @@ pub use fabric_maven::FabricMaven;
 mod mapped_jar;
 pub use mapped_jar::MappedJar;
 
+mod tiny_mappings;
+pub use tiny_mappings::{TinyClass, TinyMappings, TinyMember};
+
 mod tiny_remapper;
 pub use tiny_remapper::TinyRemapper;
 
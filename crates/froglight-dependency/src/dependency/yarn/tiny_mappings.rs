@@ -0,0 +1,178 @@
+use hashbrown::HashMap;
+
+/// Mappings parsed from a Tiny v2 file.
+///
+/// Only classes, fields and methods are kept,
+/// parameters and comments are skipped.
+///
+/// See [`https://fabricmc.net/wiki/documentation:tiny2`][0]
+///
+/// [0]: https://fabricmc.net/wiki/documentation:tiny2
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TinyMappings {
+    /// The namespaces of the mappings,
+    /// such as `official`, `intermediary` and `named`.
+    pub namespaces: Vec<String>,
+    /// The mapped classes.
+    pub classes: Vec<TinyClass>,
+}
+
+/// A class in a set of [`TinyMappings`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TinyClass {
+    /// The names of the class, one for each namespace.
+    pub names: Vec<String>,
+    /// The fields of the class.
+    pub fields: Vec<TinyMember>,
+    /// The methods of the class.
+    pub methods: Vec<TinyMember>,
+}
+
+/// A field or method in a [`TinyClass`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TinyMember {
+    /// The descriptor of the member, in the first namespace.
+    pub descriptor: String,
+    /// The names of the member, one for each namespace.
+    pub names: Vec<String>,
+}
+
+impl TinyMappings {
+    /// Parse the contents of a Tiny v2 file.
+    ///
+    /// # Errors
+    /// Returns an error if the file is not a Tiny v2 file.
+    pub fn parse(content: &str) -> anyhow::Result<Self> {
+        let mut lines = content.lines();
+
+        let mut header = lines.next().unwrap_or_default().split('\t');
+        if (header.next(), header.next()) != (Some("tiny"), Some("2")) {
+            anyhow::bail!("TinyMappings: Expected a Tiny v2 header");
+        }
+        let namespaces = header.skip(1).map(String::from).collect();
+
+        let mut classes = Vec::<TinyClass>::new();
+        for line in lines {
+            let content = line.trim_start_matches('\t');
+            let depth = line.len() - content.len();
+
+            let mut parts = content.split('\t');
+            match (depth, parts.next()) {
+                (0, Some("c")) => {
+                    classes.push(TinyClass {
+                        names: parts.map(String::from).collect(),
+                        ..TinyClass::default()
+                    });
+                }
+                (1, Some(kind @ ("f" | "m"))) => {
+                    // Properties are also indented once, but come before any classes.
+                    let Some(class) = classes.last_mut() else { continue };
+
+                    let member = TinyMember {
+                        descriptor: parts.next().unwrap_or_default().to_string(),
+                        names: parts.map(String::from).collect(),
+                    };
+                    if kind == "f" {
+                        class.fields.push(member);
+                    } else {
+                        class.methods.push(member);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(Self { namespaces, classes })
+    }
+
+    /// Get the index of a namespace.
+    #[must_use]
+    pub fn namespace(&self, namespace: &str) -> Option<usize> {
+        self.namespaces.iter().position(|ns| ns == namespace)
+    }
+
+    /// Create a map of class names from one namespace to another.
+    #[must_use]
+    pub fn class_map(&self, from: usize, to: usize) -> HashMap<&str, &str> {
+        self.classes.iter().map(|class| (class.name(from), class.name(to))).collect()
+    }
+}
+
+impl TinyClass {
+    /// Get the name of the class in a namespace.
+    ///
+    /// Falls back to the first namespace if the name is missing.
+    #[must_use]
+    pub fn name(&self, namespace: usize) -> &str { tiny_name(&self.names, namespace) }
+}
+
+impl TinyMember {
+    /// Get the name of the member in a namespace.
+    ///
+    /// Falls back to the first namespace if the name is missing.
+    #[must_use]
+    pub fn name(&self, namespace: usize) -> &str { tiny_name(&self.names, namespace) }
+
+    /// Get the descriptor of the member,
+    /// with class names remapped using a map from
+    /// [`TinyMappings::class_map`].
+    #[must_use]
+    pub fn remap_descriptor(&self, classes: &HashMap<&str, &str>) -> String {
+        let mut remapped = String::with_capacity(self.descriptor.len());
+
+        let mut rest = self.descriptor.as_str();
+        while let Some(start) = rest.find('L') {
+            let Some(end) = rest[start..].find(';') else { break };
+            let class = &rest[start + 1..start + end];
+
+            remapped.push_str(&rest[..=start]);
+            remapped.push_str(classes.get(class).copied().unwrap_or(class));
+            remapped.push(';');
+            rest = &rest[start + end + 1..];
+        }
+        remapped.push_str(rest);
+
+        remapped
+    }
+}
+
+fn tiny_name(names: &[String], namespace: usize) -> &str {
+    names
+        .get(namespace)
+        .filter(|name| !name.is_empty())
+        .or_else(|| names.first())
+        .map_or("", String::as_str)
+}
+
+#[test]
+#[cfg(test)]
+fn parse() {
+    let mappings = TinyMappings::parse(TRIMMED_EXAMPLE).unwrap();
+    assert_eq!(mappings.namespaces, ["official", "intermediary", "named"]);
+    assert_eq!(mappings.namespace("named"), Some(2));
+    assert_eq!(mappings.classes.len(), 2);
+
+    let block = &mappings.classes[1];
+    assert_eq!(block.name(1), "net/minecraft/class_2248");
+    assert_eq!(block.name(2), "net/minecraft/block/Block");
+    assert_eq!(block.fields.len(), 1);
+    assert_eq!(block.methods.len(), 1);
+
+    let method = &block.methods[0];
+    assert_eq!(method.name(2), "getDefaultState");
+    assert_eq!(
+        method.remap_descriptor(&mappings.class_map(0, 2)),
+        "(I[Lnet/minecraft/block/Block;)Lnet/minecraft/block/BlockState;"
+    );
+}
+
+#[cfg(test)]
+const TRIMMED_EXAMPLE: &str = "tiny\t2\t0\tofficial\tintermediary\tnamed
+\tescaped-names
+c\tdtc\tnet/minecraft/class_2680\tnet/minecraft/block/BlockState
+\tc\tThe state of a block.
+c\tdfy\tnet/minecraft/class_2248\tnet/minecraft/block/Block
+\tf\tLdtc;\ta\tfield_10646\tdefaultState
+\tm\t(I[Ldfy;)Ldtc;\to\tmethod_9564\tgetDefaultState
+\t\tp\t1\t\t\tid
+";
@@ -4,7 +4,7 @@ use froglight_tool_macros::Dependency;
 use hashbrown::HashMap;
 use zip::ZipArchive;
 
-use super::YarnMaven;
+use super::{TinyMappings, YarnMaven};
 use crate::{container::DependencyContainer, version::Version};
 
 /// A collection of [`YarnMapping`]s.
@@ -72,6 +72,16 @@ impl YarnMappings {
     }
 }
 
+impl YarnMapping {
+    /// Read and parse the mappings.
+    ///
+    /// # Errors
+    /// Returns an error if the mappings could not be read or parsed.
+    pub async fn read(&self) -> anyhow::Result<TinyMappings> {
+        TinyMappings::parse(&tokio::fs::read_to_string(&self.0).await?)
+    }
+}
+
 impl std::ops::Deref for YarnMapping {
     type Target = PathBuf;
     fn deref(&self) -> &Self::Target { &self.0 }
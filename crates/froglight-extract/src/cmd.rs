@@ -1,9 +1,14 @@
 //! TODO
 
-use std::{path::PathBuf, sync::Once};
+use std::{convert::Infallible, path::PathBuf, str::FromStr, sync::Once};
 
 use clap::Parser;
-use froglight_dependency::{container::SharedDependencies, version::Version};
+use froglight_dependency::{
+    container::{DependencyContainer, SharedDependencies},
+    dependency::mojang::VersionManifest,
+    version::Version,
+};
+use serde_json::{Map, Value};
 use tokio::runtime::Builder;
 
 use crate::json::{JsonModule, JsonOutput};
@@ -12,6 +17,9 @@ use crate::json::{JsonModule, JsonOutput};
 ///
 /// If a `JsonOutput` is present it will be serialized to the console or a file.
 ///
+/// When extracting multiple versions the output is either combined
+/// into one object keyed by version, or written to one file per version.
+///
 /// # Example
 /// ```rust
 /// /// Import the function in `main.rs`.
@@ -36,20 +44,53 @@ pub fn main() -> anyhow::Result<()> {
         }
 
         let dependencies = SharedDependencies::from_rust_env();
-        crate::extract(args.version, &args.modules, dependencies.clone()).await?;
-
-        // If the `JsonOutput` is present, serialize it to the console or a file.
-        if let Some(output) = dependencies.write().await.take::<JsonOutput>() {
-            let json = serde_json::to_string_pretty(&output.0)?;
-            if let Some(path) = args.output {
-                // Write the JSON to the output file.
-                tokio::fs::write(path, json).await?;
+
+        // Resolve any version ranges, skipping duplicates.
+        let mut versions = Vec::<Version>::new();
+        for arg in &args.versions {
+            for version in arg.resolve(&mut *dependencies.write().await).await? {
+                if !versions.contains(&version) {
+                    versions.push(version);
+                }
+            }
+        }
+
+        let mut combined = Map::new();
+        for version in &versions {
+            tracing::info!("Extracting version {version}");
+            crate::extract(version.clone(), &args.modules, dependencies.clone()).await?;
+
+            // If the `JsonOutput` is present, keep it or write it to its own file.
+            let Some(output) = dependencies.write().await.take::<JsonOutput>() else { continue };
+            if let Some(directory) = &args.output_dir {
+                tokio::fs::create_dir_all(directory).await?;
+                let path = directory.join(format!("{version}.json"));
+                tokio::fs::write(path, serde_json::to_string_pretty(&output.0)?).await?;
             } else {
-                // Print the JSON to the console.
-                println!("{json}");
+                combined.insert(version.to_long_string(), output.0);
             }
         }
 
+        if combined.is_empty() {
+            return Ok(());
+        }
+
+        // Only key the output by version if there are multiple versions.
+        let output = if versions.len() == 1 {
+            combined.into_iter().next().map(|(_, output)| output).unwrap_or_default()
+        } else {
+            Value::Object(combined)
+        };
+
+        let json = serde_json::to_string_pretty(&output)?;
+        if let Some(path) = args.output {
+            // Write the JSON to the output file.
+            tokio::fs::write(path, json).await?;
+        } else {
+            // Print the JSON to the console.
+            println!("{json}");
+        }
+
         Ok(())
     })
 }
@@ -57,9 +98,12 @@ pub fn main() -> anyhow::Result<()> {
 /// The [`froglight_extract::main`](main) command line arguments.
 #[derive(Parser)]
 pub struct ExtractArgs {
-    /// The version to extract.
-    #[clap(short, long)]
-    pub version: Version,
+    /// The versions to extract.
+    ///
+    /// Accepts single versions such as `1.21.4`, and ranges of releases
+    /// such as `1.21.1..1.21.4` or `1.21.1..=1.21.4`.
+    #[clap(short, long = "version", required = true, num_args = 1.., value_delimiter = ',')]
+    pub versions: Vec<VersionArg>,
 
     /// The extract modules to run.
     #[clap(short, long)]
@@ -70,6 +114,116 @@ pub struct ExtractArgs {
     /// If `None`, the result will be logged to the console.
     #[clap(short, long)]
     pub output: Option<PathBuf>,
+
+    /// A directory to write one output file per version to.
+    #[clap(long, conflicts_with = "output")]
+    pub output_dir: Option<PathBuf>,
+}
+
+/// A version or range of versions passed to [`ExtractArgs`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VersionArg {
+    /// A single version, such as `1.21.4`.
+    Single(Version),
+    /// A range of versions, such as `1.21.1..1.21.4`.
+    ///
+    /// Includes the start and every release after it,
+    /// up to the end if `inclusive` is set.
+    Range {
+        /// The first version in the range.
+        start: Version,
+        /// The last version in the range.
+        end: Version,
+        /// Whether the range includes `end`.
+        inclusive: bool,
+    },
+}
+
+impl VersionArg {
+    /// Get the versions this argument refers to, oldest first.
+    ///
+    /// Ranges are resolved using the [`VersionManifest`].
+    ///
+    /// # Errors
+    /// Returns an error if the [`VersionManifest`] could not be retrieved,
+    /// or does not contain the start or end of a range.
+    pub async fn resolve(&self, deps: &mut DependencyContainer) -> anyhow::Result<Vec<Version>> {
+        match self {
+            Self::Single(version) => Ok(vec![version.clone()]),
+            Self::Range { .. } => {
+                self.resolve_with(deps.get_or_retrieve::<VersionManifest>().await?)
+            }
+        }
+    }
+
+    /// Get the versions this argument refers to, oldest first.
+    ///
+    /// # Errors
+    /// Returns an error if the [`VersionManifest`]
+    /// does not contain the start or end of a range.
+    pub fn resolve_with(&self, manifest: &VersionManifest) -> anyhow::Result<Vec<Version>> {
+        let Self::Range { start, end, inclusive } = self else {
+            return Ok(vec![self.to_single()]);
+        };
+
+        let entry = |version: &Version| {
+            manifest.get(version).ok_or_else(|| {
+                anyhow::anyhow!("Version \"{version}\" is not in the version manifest")
+            })
+        };
+        let (first, last) = (entry(start)?, entry(end)?);
+        if first.release_time > last.release_time {
+            anyhow::bail!("Version range \"{self}\" starts after it ends");
+        }
+
+        let mut entries = manifest
+            .versions
+            .iter()
+            .filter(|entry| {
+                if entry.id == *start {
+                    true
+                } else if entry.id == *end {
+                    *inclusive
+                } else {
+                    entry.version_type == "release"
+                        && entry.release_time > first.release_time
+                        && entry.release_time < last.release_time
+                }
+            })
+            .collect::<Vec<_>>();
+        entries.sort_by_key(|entry| entry.release_time);
+
+        Ok(entries.into_iter().map(|entry| entry.id.clone()).collect())
+    }
+
+    fn to_single(&self) -> Version {
+        match self {
+            Self::Single(version) | Self::Range { start: version, .. } => version.clone(),
+        }
+    }
+}
+
+impl FromStr for VersionArg {
+    type Err = Infallible;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some((start, end)) = s.split_once("..=") {
+            Ok(Self::Range { start: start.parse()?, end: end.parse()?, inclusive: true })
+        } else if let Some((start, end)) = s.split_once("..") {
+            Ok(Self::Range { start: start.parse()?, end: end.parse()?, inclusive: false })
+        } else {
+            s.parse().map(Self::Single)
+        }
+    }
+}
+
+impl std::fmt::Display for VersionArg {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Single(version) => write!(f, "{version}"),
+            Self::Range { start, end, inclusive: true } => write!(f, "{start}..={end}"),
+            Self::Range { start, end, inclusive: false } => write!(f, "{start}..{end}"),
+        }
+    }
 }
 
 /// Initialize logging with the default environment filter.
@@ -88,3 +242,35 @@ pub fn logging() {
         fmt().with_env_filter(filter).with_writer(std::io::stderr).init();
     });
 }
+
+#[test]
+#[cfg(test)]
+fn version_range() {
+    let entry = |id: &str, version_type: &str, day: u8| {
+        serde_json::json!({
+            "id": id, "type": version_type, "url": "", "sha1": "", "complianceLevel": 1,
+            "time": format!("2024-01-{day:02}T00:00:00+00:00"),
+            "releaseTime": format!("2024-01-{day:02}T00:00:00+00:00"),
+        })
+    };
+    let manifest: VersionManifest = serde_json::from_value(serde_json::json!({
+        "latest": { "release": "1.21.4", "snapshot": "1.21.4" },
+        "versions": [
+            entry("1.21.4", "release", 5),
+            entry("1.21.3", "release", 4),
+            entry("24w01a", "snapshot", 3),
+            entry("1.21.2", "release", 2),
+            entry("1.21.1", "release", 1),
+        ],
+    }))
+    .unwrap();
+
+    let resolve = |arg: &str| {
+        let versions = arg.parse::<VersionArg>().unwrap().resolve_with(&manifest).unwrap();
+        versions.iter().map(Version::to_long_string).collect::<Vec<_>>()
+    };
+    assert_eq!(resolve("1.21.3"), ["1.21.3"]);
+    assert_eq!(resolve("1.21.1..1.21.4"), ["1.21.1", "1.21.2", "1.21.3"]);
+    assert_eq!(resolve("1.21.2..=1.21.4"), ["1.21.2", "1.21.3", "1.21.4"]);
+    assert!("1.21.4..1.21.1".parse::<VersionArg>().unwrap().resolve_with(&manifest).is_err());
+}
@@ -1,9 +1,29 @@
-//! TODO
+//! Data generator reports as a single JSON document.
+//!
+//! This is a reports-only export: everything is read from the reports of
+//! Minecraft's data generator, so the output can be used without any
+//! generated Rust code.
+//!
+//! Entity types, status effects and packets are only listed with their
+//! protocol ids. Their properties and packet fields are extracted from
+//! bytecode by `froglight-tools`, which this crate cannot call.
+//!
+//! TODO: Move the bytecode extractors into this crate and export
+//! entity, status effect and packet definitions alongside the ids.
 
-use froglight_dependency::{container::DependencyContainer, version::Version};
+use std::path::Path;
+
+use froglight_dependency::{
+    container::DependencyContainer,
+    dependency::minecraft::{DataGenerator, GeneratorOutput},
+    version::Version,
+};
 use froglight_tool_macros::{Dependency, ExtractModule};
+use serde_json::{Map, Value};
 
-/// A module that extracts data as JSON.
+/// A module that combines the data generator reports into JSON.
+///
+/// See the [module documentation](crate::json) for what is included.
 #[derive(Clone, Copy, PartialEq, Eq, Hash, ExtractModule)]
 #[module(path = crate, name = "json", function = JsonModule::extract)]
 pub struct JsonModule;
@@ -13,6 +33,150 @@ pub struct JsonModule;
 pub struct JsonOutput(pub serde_json::Value);
 
 impl JsonModule {
-    #[expect(clippy::unused_async)]
-    async fn extract(_v: &Version, _d: &mut DependencyContainer) -> anyhow::Result<()> { todo!() }
+    const ENTITY_REGISTRY: &str = "minecraft:entity_type";
+    const ITEM_REGISTRY: &str = "minecraft:item";
+    const STATUS_EFFECT_REGISTRY: &str = "minecraft:mob_effect";
+
+    async fn extract(version: &Version, deps: &mut DependencyContainer) -> anyhow::Result<()> {
+        deps.get_or_retrieve::<DataGenerator>().await?;
+        let document = deps
+            .scoped_fut::<DataGenerator, anyhow::Result<Value>>(
+                async |data: &mut DataGenerator, deps: &mut DependencyContainer| {
+                    let path = data.get_output(version, GeneratorOutput::Reports, deps).await?;
+                    let reports = path.join("reports");
+
+                    Ok(Self::document(
+                        version,
+                        Self::read(&reports, "registries.json").await?,
+                        Self::read(&reports, "blocks.json").await?,
+                        Self::read_optional(&reports, "items.json").await?,
+                        Self::read(&reports, "packets.json").await?,
+                    ))
+                },
+            )
+            .await
+            .map_err(|err| anyhow::anyhow!("JsonModule: {err}"))?;
+
+        deps.insert(JsonOutput(document));
+        Ok(())
+    }
+
+    /// Build the output document from the data generator reports.
+    ///
+    /// Items, entity types, status effects and packets are listed with their
+    /// protocol ids, items also include their default components if `items`
+    /// is present.
+    #[must_use]
+    pub fn document(
+        version: &Version,
+        registries: Value,
+        blocks: Value,
+        items: Option<Value>,
+        packets: Value,
+    ) -> Value {
+        let mut item_entries = Self::registry_ids(&registries, Self::ITEM_REGISTRY);
+        if let Some(Value::Object(mut components)) = items {
+            for (item, entry) in &mut item_entries {
+                let Some(report) = components.get_mut(item) else { continue };
+                if let (Value::Object(entry), Some(components)) =
+                    (entry, report.get_mut("components").map(Value::take))
+                {
+                    entry.insert(String::from("components"), components);
+                }
+            }
+        }
+
+        let mut document = Map::new();
+        document.insert(String::from("version"), Value::String(version.to_long_string()));
+        document.insert(String::from("blocks"), blocks);
+        document.insert(String::from("items"), Value::Object(item_entries));
+        document.insert(
+            String::from("entity_type_ids"),
+            Value::Object(Self::registry_ids(&registries, Self::ENTITY_REGISTRY)),
+        );
+        document.insert(
+            String::from("status_effect_ids"),
+            Value::Object(Self::registry_ids(&registries, Self::STATUS_EFFECT_REGISTRY)),
+        );
+        document.insert(String::from("packet_ids"), packets);
+        document.insert(String::from("registries"), registries);
+        Value::Object(document)
+    }
+
+    /// List the entries of a registry as `{ "name": { "id": 0 } }`.
+    fn registry_ids(registries: &Value, registry: &str) -> Map<String, Value> {
+        let Some(Value::Object(entries)) = registries.get(registry).and_then(|r| r.get("entries"))
+        else {
+            tracing::warn!("JsonModule: Missing registry \"{registry}\"");
+            return Map::new();
+        };
+
+        entries
+            .iter()
+            .map(|(name, entry)| {
+                let id = entry.get("protocol_id").cloned().unwrap_or_default();
+                (name.clone(), Value::Object(Map::from_iter([(String::from("id"), id)])))
+            })
+            .collect()
+    }
+
+    /// Read and parse a report.
+    async fn read(reports: &Path, file: &str) -> anyhow::Result<Value> {
+        let path = reports.join(file);
+        tracing::debug!("Parsing \"{}\"", path.display());
+
+        let contents = tokio::fs::read_to_string(&path).await?;
+        serde_json::from_str(&contents)
+            .map_err(|err| anyhow::anyhow!("Failed to parse \"{}\": {err}", path.display()))
+    }
+
+    /// Read and parse a report that only exists in some versions.
+    async fn read_optional(reports: &Path, file: &str) -> anyhow::Result<Option<Value>> {
+        if tokio::fs::try_exists(reports.join(file)).await? {
+            Self::read(reports, file).await.map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+#[test]
+#[cfg(test)]
+fn document() {
+    use serde_json::json;
+
+    let registries = json!({
+        "minecraft:entity_type": { "entries": { "minecraft:pig": { "protocol_id": 95 } } },
+        "minecraft:item": { "entries": {
+            "minecraft:air": { "protocol_id": 0 },
+            "minecraft:stone": { "protocol_id": 1 },
+        } },
+        "minecraft:mob_effect": { "entries": { "minecraft:speed": { "protocol_id": 0 } } },
+    });
+    let blocks = json!({ "minecraft:air": { "states": [{ "id": 0, "default": true }] } });
+    let items = json!({ "minecraft:stone": { "components": { "minecraft:max_stack_size": 64 } } });
+    let packets =
+        json!({ "play": { "clientbound": { "minecraft:bundle": { "protocol_id": 0 } } } });
+
+    let document = JsonModule::document(
+        &Version::new_release(1, 21, 5),
+        registries.clone(),
+        blocks.clone(),
+        Some(items),
+        packets.clone(),
+    );
+
+    assert_eq!(document["version"], "1.21.5");
+    assert_eq!(document["blocks"], blocks);
+    assert_eq!(document["packet_ids"], packets);
+    assert_eq!(document["registries"], registries);
+    assert_eq!(
+        document["items"],
+        json!({
+            "minecraft:air": { "id": 0 },
+            "minecraft:stone": { "id": 1, "components": { "minecraft:max_stack_size": 64 } },
+        })
+    );
+    assert_eq!(document["entity_type_ids"], json!({ "minecraft:pig": { "id": 95 } }));
+    assert_eq!(document["status_effect_ids"], json!({ "minecraft:speed": { "id": 0 } }));
 }
@@ -1,12 +1,21 @@
 //! TODO
 
-use std::{collections::HashMap, future::Future, pin::Pin};
+use std::{
+    any::TypeId,
+    collections::HashMap,
+    future::Future,
+    hash::{Hash, Hasher},
+    pin::Pin,
+};
 
+pub use froglight_dependency::dependency::minecraft::CodeSide;
 use froglight_dependency::{
-    container::{DependencyContainer, SharedDependencies},
+    container::{Dependency, DependencyContainer, SharedDependencies},
     version::Version,
 };
 pub use froglight_tool_macros::ExtractModule;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
 
 /// The extract function.
 ///
@@ -29,29 +38,45 @@ pub async fn extract(
     modules: &[String],
     dependencies: SharedDependencies,
 ) -> anyhow::Result<()> {
-    let map = ExtractModule::map();
+    // Check the modules and their dependencies before running anything.
+    let modules = ExtractModule::sort(modules)?;
 
-    // Iterate over the specified modules and run them.
+    // Iterate over the sorted modules and run them.
     // Reacquire the lock for each module to prevent deadlocks.
     for module in modules {
-        if let Some(extract) = map.get(module.as_str()) {
-            tracing::info!("Running module \"{module}\"");
-            extract.run(&version, &mut *dependencies.write().await).await?;
-        } else {
-            tracing::error!("Unknown module \"{module}\"");
-        }
+        tracing::info!("Running module \"{}\"", module.name());
+        module.run(&version, &mut *dependencies.write().await).await?;
     }
 
     Ok(())
 }
 
 /// A module that can be run by name.
-#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+///
+/// Modules are compared and hashed by name only.
+#[derive(Clone, Copy)]
 pub struct ExtractModule {
     name: &'static str,
+    dependencies: &'static [&'static str],
+    requires: &'static [DependencyType],
+    provides: &'static [DependencyType],
+    side: CodeSide,
+    config: Option<ConfigFn>,
     function: ExtractFn,
 }
 
+impl PartialEq for ExtractModule {
+    fn eq(&self, other: &Self) -> bool { self.name == other.name }
+}
+
+impl Eq for ExtractModule {}
+
+impl Hash for ExtractModule {
+    fn hash<H: Hasher>(&self, state: &mut H) { self.name.hash(state); }
+}
+
+type ConfigFn = fn(Value, &mut DependencyContainer) -> anyhow::Result<()>;
+
 type ExtractFn = for<'a> fn(
     &'a Version,
     &'a mut DependencyContainer,
@@ -61,13 +86,117 @@ impl ExtractModule {
     /// Create a new [`ExtractModule`] instance.
     #[inline]
     #[must_use]
-    pub const fn new(name: &'static str, function: ExtractFn) -> Self { Self { name, function } }
+    pub const fn new(name: &'static str, function: ExtractFn) -> Self {
+        Self {
+            name,
+            dependencies: &[],
+            requires: &[],
+            provides: &[],
+            side: CodeSide::Client,
+            config: None,
+            function,
+        }
+    }
+
+    /// Set the function used to configure the module.
+    ///
+    /// See [`insert_config`] for the default implementation.
+    #[inline]
+    #[must_use]
+    pub const fn with_config(mut self, config: ConfigFn) -> Self {
+        self.config = Some(config);
+        self
+    }
+
+    /// Set the names of the modules that must run before this one.
+    #[inline]
+    #[must_use]
+    pub const fn with_dependencies(mut self, dependencies: &'static [&'static str]) -> Self {
+        self.dependencies = dependencies;
+        self
+    }
+
+    /// Set the types the module reads from the [`DependencyContainer`].
+    #[inline]
+    #[must_use]
+    pub const fn with_requires(mut self, requires: &'static [DependencyType]) -> Self {
+        self.requires = requires;
+        self
+    }
+
+    /// Set the types the module fills in the [`DependencyContainer`].
+    #[inline]
+    #[must_use]
+    pub const fn with_provides(mut self, provides: &'static [DependencyType]) -> Self {
+        self.provides = provides;
+        self
+    }
+
+    /// Set which side of the game the module reads code from.
+    #[inline]
+    #[must_use]
+    pub const fn with_side(mut self, side: CodeSide) -> Self {
+        self.side = side;
+        self
+    }
 
     /// Get the name of the [`ExtractModule`].
     #[inline]
     #[must_use]
     pub const fn name(&self) -> &'static str { self.name }
 
+    /// Get the names of the modules that must run before this one.
+    #[inline]
+    #[must_use]
+    pub const fn dependencies(&self) -> &'static [&'static str] { self.dependencies }
+
+    /// Get the types the module reads from the [`DependencyContainer`].
+    #[inline]
+    #[must_use]
+    pub const fn requires(&self) -> &'static [DependencyType] { self.requires }
+
+    /// Get the types the module fills in the [`DependencyContainer`].
+    #[inline]
+    #[must_use]
+    pub const fn provides(&self) -> &'static [DependencyType] { self.provides }
+
+    /// Get which side of the game the module reads code from.
+    #[inline]
+    #[must_use]
+    pub const fn side(&self) -> CodeSide { self.side }
+
+    /// Returns `true` if the [`ExtractModule`] accepts a configuration.
+    #[inline]
+    #[must_use]
+    pub const fn is_configurable(&self) -> bool { self.config.is_some() }
+
+    /// Configure the [`ExtractModule`] using its section of a configuration
+    /// file.
+    ///
+    /// If no section is given the module uses its default configuration.
+    ///
+    /// # Errors
+    /// Returns an error if the section is invalid,
+    /// or the module does not accept a configuration.
+    pub fn configure(
+        &self,
+        section: Option<Value>,
+        container: &mut DependencyContainer,
+    ) -> anyhow::Result<()> {
+        match (self.config, section) {
+            (Some(config), section) => {
+                let section = section.unwrap_or_else(|| Value::Object(serde_json::Map::new()));
+                config(section, container).map_err(|err| {
+                    anyhow::anyhow!("Invalid configuration for \"{}\": {err}", self.name)
+                })
+            }
+            (None, None) => Ok(()),
+            (None, Some(_)) => {
+                anyhow::bail!("Module \"{}\" does not accept a configuration", self.name)
+            }
+        }
+    }
+
     /// Run the [`ExtractModule`] on the given [`DependencyContainer`].
     ///
     /// # Errors
@@ -91,6 +220,208 @@ impl ExtractModule {
     pub fn iter() -> impl Iterator<Item = &'static ExtractModule> {
         inventory::iter::<ExtractModule>.into_iter()
     }
+
+    /// Look up the named [`ExtractModule`]s and sort them
+    /// so every module runs after its dependencies.
+    ///
+    /// Dependencies that were not selected are added before the first module
+    /// that needs them, otherwise modules keep the given order.
+    ///
+    /// # Errors
+    /// Returns an error if a module or dependency is unknown,
+    /// is part of a dependency cycle, or requires a type
+    /// that no module running before it provides.
+    pub fn sort(modules: &[String]) -> anyhow::Result<Vec<&'static Self>> {
+        let map = Self::map();
+        let order = sort_modules(modules, |name| map.get(name).map(|m| m.dependencies()))?;
+        let order = order.into_iter().map(|name| map[name]).collect::<Vec<_>>();
+        check_requirements(&order)?;
+        Ok(order)
+    }
+}
+
+/// A type in the [`DependencyContainer`] that modules require or provide.
+///
+/// Used by `#[module(requires = [...], provides = [...])]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DependencyType {
+    name: &'static str,
+    id: TypeId,
+}
+
+impl DependencyType {
+    /// Create a new [`DependencyType`] for `T`, displayed as `name`.
+    #[inline]
+    #[must_use]
+    pub const fn of<T: Dependency>(name: &'static str) -> Self {
+        Self { name, id: TypeId::of::<T>() }
+    }
+
+    /// Get the name of the type.
+    #[inline]
+    #[must_use]
+    pub const fn name(&self) -> &'static str { self.name }
+}
+
+/// Deserialize a module configuration and insert it into the
+/// [`DependencyContainer`].
+///
+/// Used by `#[module(config = ...)]`.
+///
+/// # Errors
+/// Returns an error if the configuration could not be deserialized.
+pub fn insert_config<T: Dependency + DeserializeOwned>(
+    section: Value,
+    container: &mut DependencyContainer,
+) -> anyhow::Result<()> {
+    container.insert(serde_json::from_value::<T>(section)?);
+    Ok(())
+}
+
+/// Sort module names so every module comes after its dependencies,
+/// adding any dependencies that were not selected.
+///
+/// Returns every unknown and cyclic module at once.
+fn sort_modules<'a>(
+    modules: &'a [String],
+    dependencies: impl Fn(&str) -> Option<&'a [&'a str]>,
+) -> anyhow::Result<Vec<&'a str>> {
+    fn visit<'a>(
+        module: &'a str,
+        dependencies: &impl Fn(&str) -> Option<&'a [&'a str]>,
+        stack: &mut Vec<&'a str>,
+        order: &mut Vec<&'a str>,
+        errors: &mut Vec<String>,
+    ) {
+        if order.contains(&module) {
+            return;
+        }
+        if let Some(index) = stack.iter().position(|m| *m == module) {
+            let cycle = stack[index..].iter().chain([&module]).copied().collect::<Vec<_>>();
+            errors.push(format!("Modules form a cycle: {}", cycle.join(" -> ")));
+            return;
+        }
+        let Some(required) = dependencies(module) else {
+            errors.push(format!("Unknown module \"{module}\""));
+            return;
+        };
+
+        stack.push(module);
+        for dependency in required {
+            visit(dependency, dependencies, stack, order, errors);
+        }
+        stack.pop();
+
+        if !order.contains(&module) {
+            order.push(module);
+        }
+    }
+
+    let (mut stack, mut order, mut errors) = (Vec::new(), Vec::new(), Vec::new());
+    for module in modules {
+        visit(module, &dependencies, &mut stack, &mut order, &mut errors);
+    }
+
+    errors.dedup();
+    if errors.is_empty() {
+        Ok(order)
+    } else {
+        anyhow::bail!("{}", errors.join("\n"))
+    }
+}
+
+/// Check that every type a module requires
+/// is provided by a module running before it.
+///
+/// Returns every missing type at once.
+fn check_requirements(modules: &[&ExtractModule]) -> anyhow::Result<()> {
+    let mut errors = Vec::new();
+    for (index, module) in modules.iter().enumerate() {
+        for required in module.requires() {
+            if !modules[..index].iter().any(|m| m.provides().contains(required)) {
+                errors.push(format!(
+                    "Module \"{}\" requires \"{}\", but no module running before it provides it",
+                    module.name(),
+                    required.name()
+                ));
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        anyhow::bail!("{}", errors.join("\n"))
+    }
 }
 
 inventory::collect!(ExtractModule);
+
+#[test]
+#[cfg(test)]
+fn sort() {
+    let dependencies = |module: &str| -> Option<&[&str]> {
+        match module {
+            "blocks" | "registry" => Some(&[]),
+            "items" => Some(&["blocks"]),
+            "json" => Some(&["items", "registry"]),
+            "a" => Some(&["b"]),
+            "b" => Some(&["a"]),
+            _ => None,
+        }
+    };
+    let modules = |names: &[&str]| names.iter().map(ToString::to_string).collect::<Vec<_>>();
+
+    let selected = modules(&["json", "items", "registry", "blocks"]);
+    assert_eq!(
+        sort_modules(&selected, dependencies).unwrap(),
+        ["blocks", "items", "registry", "json"]
+    );
+
+    let selected = modules(&["registry", "blocks"]);
+    assert_eq!(sort_modules(&selected, dependencies).unwrap(), ["registry", "blocks"]);
+
+    let selected = modules(&["registry", "items"]);
+    assert_eq!(sort_modules(&selected, dependencies).unwrap(), ["registry", "blocks", "items"]);
+
+    let selected = modules(&["items", "unknown"]);
+    assert_eq!(
+        sort_modules(&selected, dependencies).unwrap_err().to_string(),
+        "Unknown module \"unknown\""
+    );
+
+    let selected = modules(&["a", "b"]);
+    assert_eq!(
+        sort_modules(&selected, dependencies).unwrap_err().to_string(),
+        "Modules form a cycle: a -> b -> a"
+    );
+}
+
+#[test]
+#[cfg(test)]
+fn requirements() {
+    use froglight_dependency::container::Dependency;
+
+    #[derive(Dependency)]
+    struct BlockReports;
+    #[derive(Dependency)]
+    struct ItemReports;
+
+    const BLOCK_REPORTS: DependencyType = DependencyType::of::<BlockReports>("BlockReports");
+    const ITEM_REPORTS: DependencyType = DependencyType::of::<ItemReports>("ItemReports");
+
+    let module = |name| ExtractModule::new(name, |_, _| Box::pin(async { Ok(()) }));
+    let blocks = module("blocks").with_provides(&[BLOCK_REPORTS]);
+    let items = module("items").with_requires(&[BLOCK_REPORTS, ITEM_REPORTS]);
+
+    assert!(check_requirements(&[&blocks]).is_ok());
+    assert_eq!(
+        check_requirements(&[&blocks, &items]).unwrap_err().to_string(),
+        "Module \"items\" requires \"ItemReports\", but no module running before it provides it"
+    );
+    assert_eq!(
+        check_requirements(&[&items, &blocks]).unwrap_err().to_string(),
+        "Module \"items\" requires \"BlockReports\", but no module running before it provides it\n\
+         Module \"items\" requires \"ItemReports\", but no module running before it provides it"
+    );
+}
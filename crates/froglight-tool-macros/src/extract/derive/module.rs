@@ -1,7 +1,7 @@
 use darling::FromDeriveInput;
 use proc_macro2::TokenStream;
 use quote::quote;
-use syn::DeriveInput;
+use syn::{DeriveInput, Expr, ExprArray};
 
 #[derive(FromDeriveInput)]
 #[darling(attributes(module))]
@@ -10,27 +10,83 @@ struct DependencyMacro {
     path: Option<syn::Path>,
     #[darling(default)]
     name: Option<String>,
+    #[darling(default)]
+    depends_on: Option<ExprArray>,
+    #[darling(default)]
+    requires: Option<ExprArray>,
+    #[darling(default)]
+    provides: Option<ExprArray>,
+    #[darling(default)]
+    config: Option<syn::Path>,
+    #[darling(default)]
+    side: Option<syn::Ident>,
     function: syn::Path,
 }
 
 pub(crate) fn derive_module(input: TokenStream) -> TokenStream {
     let input: DeriveInput = syn::parse2(input).unwrap();
 
-    let DependencyMacro { path, name, function } =
+    let DependencyMacro { path, name, depends_on, requires, provides, config, side, function } =
         DependencyMacro::from_derive_input(&input).unwrap();
     let DeriveInput { ident, .. } = input;
 
     let path = path.unwrap_or_else(|| syn::parse_quote!(froglight_extract));
     let name = name.unwrap_or_else(|| ident.to_string()).to_lowercase();
 
+    // Dependencies are module types, so they are checked at compile time.
+    let mut dependencies = Vec::new();
+    for expr in depends_on.into_iter().flat_map(|array| array.elems) {
+        match expr {
+            Expr::Path(module) => dependencies.push(quote! { <#module>::MODULE_NAME }),
+            other => {
+                return syn::Error::new_spanned(other, "Expected the type of an `ExtractModule`")
+                    .to_compile_error();
+            }
+        }
+    }
+
+    // Required and provided types are matched by their `TypeId`.
+    let mut types = [Vec::new(), Vec::new()];
+    for (array, types) in [requires, provides].into_iter().zip(&mut types) {
+        for expr in array.into_iter().flat_map(|array| array.elems) {
+            match expr {
+                Expr::Path(ty) => {
+                    let name = quote!(#ty).to_string().replace(' ', "");
+                    types.push(quote! { #path::module::DependencyType::of::<#ty>(#name) });
+                }
+                other => {
+                    return syn::Error::new_spanned(other, "Expected the type of a `Dependency`")
+                        .to_compile_error();
+                }
+            }
+        }
+    }
+    let [requires, provides] = types;
+
+    // Configurations are deserialized from the module's section of the config file.
+    let config = config.map(|config| {
+        quote! { .with_config(#path::module::insert_config::<#config>) }
+    });
+
+    // Modules read client code unless they ask for another side.
+    let side = side.unwrap_or_else(|| syn::parse_quote!(Client));
+
     quote! {
         impl #ident {
             /// The name of the associated [`ExtractModule`](#path::module::ExtractModule).
             pub const MODULE_NAME: &'static str = #name;
+            /// The side of the game the associated
+            /// [`ExtractModule`](#path::module::ExtractModule) reads code from.
+            pub const CODE_SIDE: #path::module::CodeSide = #path::module::CodeSide::#side;
         }
 
         #path::inventory::submit! {
             #path::module::ExtractModule::new(#name, |v, d| Box::pin(#function(v, d)))
+                .with_dependencies(&[#(#dependencies),*])
+                .with_requires(&[#(#requires),*])
+                .with_provides(&[#(#provides),*])
+                .with_side(<#ident>::CODE_SIDE)
+                #config
         }
     }
 }
@@ -57,10 +57,23 @@ mod extract;
 /// ```rust,ignore
 /// use froglight_extract::module::ExtractModule;
 ///
+/// // `MyReports` is filled in by this module.
 /// #[derive(ExtractModule)]
-/// #[module(function = Self::run_module)]
+/// #[module(function = Self::run_module, provides = [MyReports])]
 /// struct MyModule;
 ///
+/// // Modules listed in `depends_on` always run first, even if not selected,
+/// // and `MyConfig` is deserialized from the module's configuration section.
+/// // Types listed in `requires` must be provided by a module that runs first.
+/// #[derive(ExtractModule)]
+/// #[module(
+///     function = Self::run_module,
+///     depends_on = [MyModule],
+///     requires = [MyReports],
+///     config = MyConfig
+/// )]
+/// struct MyOtherModule;
+///
 /// impl MyModule {
 ///     fn run_module(deps: &mut DependencyContainer) {
 ///         todo!()
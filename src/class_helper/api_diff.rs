@@ -0,0 +1,438 @@
+//! Compare the classes of two versions.
+//!
+//! Used to decide which extract modules need to be checked
+//! when a new version is released.
+
+use std::{
+    collections::{BTreeMap, HashSet},
+    fmt::Write,
+};
+
+use cafebabe::{ClassFile, MethodInfo, attributes::AttributeData, bytecode::Opcode};
+use froglight_dependency::{
+    dependency::minecraft::minecraft_code::{Classes, CodeBundle},
+    version::Version,
+};
+
+use super::{
+    disassemble::{Disassembler, header},
+    inline::{self, Frame, InlineOptions},
+};
+use crate::module::Symbols;
+
+/// Methods read by the extract modules, as `(class, name, descriptor)`.
+///
+/// Built from the classes the extract modules read, resolved through the
+/// [`Symbols`] table, and every method they follow from there.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct Watched(HashSet<Frame>);
+
+impl Watched {
+    /// Classes whose static initializer is read by the extract modules.
+    const HOLDERS: &[&str] =
+        &["blocks", "items", "entity_type", "entity_attributes", "status_effects"];
+    /// Methods of the `network_state_builder` called by network state classes.
+    const STATE_BUILDER_METHODS: &[&str] = &[
+        "network_state_builder_c2s",
+        "network_state_builder_context_aware_c2s",
+        "network_state_builder_s2c",
+    ];
+
+    /// Add the methods read by the extract modules in a version.
+    pub(crate) fn extend(&mut self, classes: &Classes<'_>, symbols: &Symbols, version: &Version) {
+        let mut roots = Self::HOLDERS
+            .iter()
+            .filter_map(|symbol| symbols.get(symbol, version))
+            .map(|class| (class.to_string(), String::from("<clinit>"), String::from("()V")))
+            .collect::<Vec<_>>();
+
+        // Network state classes are found by their calls to the state builder.
+        if let Some(builder) = symbols.get("network_state_builder", version) {
+            for method in Self::STATE_BUILDER_METHODS.iter().filter_map(|s| symbols.get(s, version))
+            {
+                roots.extend(classes.xrefs().callers(builder, method, None).into_iter().map(
+                    |caller| {
+                        (caller.class.clone(), caller.method.clone(), caller.descriptor.clone())
+                    },
+                ));
+            }
+        }
+
+        let codec = symbols.descriptor("packet_codec", version);
+        self.extend_from(classes, roots, codec.as_deref());
+    }
+
+    /// Add methods and every method followed from them.
+    ///
+    /// Reading a static field of the `codec` type also adds the
+    /// static initializer that creates it, such as `SomePacket.CODEC`.
+    fn extend_from(&mut self, classes: &Classes<'_>, mut roots: Vec<Frame>, codec: Option<&str>) {
+        while let Some(root) = roots.pop() {
+            if !self.0.insert(root.clone()) {
+                continue;
+            }
+
+            let Some((class, method)) = classes.resolve_method(&root.0, &root.1, Some(&root.2))
+            else {
+                continue;
+            };
+            let Some(bytecode) = method.attributes.iter().find_map(|attr| match &attr.data {
+                AttributeData::Code(code) => code.bytecode.as_ref(),
+                _ => None,
+            }) else {
+                continue;
+            };
+
+            let initial = bytecode.opcodes.iter().map(|(_, opcode)| opcode).collect::<Vec<_>>();
+            let followed = inline::followed_methods(
+                class,
+                &initial,
+                classes,
+                InlineOptions::default(),
+                &mut |opcode| {
+                    if let Opcode::Getstatic(member) = opcode
+                        && codec.is_some_and(|codec| member.name_and_type.descriptor == codec)
+                    {
+                        let class = member.class_name.to_string();
+                        roots.push((class, String::from("<clinit>"), String::from("()V")));
+                    }
+                },
+            );
+            self.0.extend(followed);
+        }
+    }
+
+    /// Returns `true` if a method is read by the extract modules.
+    #[must_use]
+    pub(crate) fn contains(&self, class: &str, method: &MethodInfo<'_>) -> bool {
+        self.0.iter().any(|(c, name, descriptor)| {
+            c == class && *name == method.name && *descriptor == method.descriptor.to_string()
+        })
+    }
+}
+
+/// Added, removed and changed classes between two versions.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct ApiDiff {
+    pub(crate) added: Vec<String>,
+    pub(crate) removed: Vec<String>,
+    pub(crate) changed: Vec<ClassDiff>,
+}
+
+/// The changes to a class present in both versions.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct ClassDiff {
+    pub(crate) name: String,
+    /// The old and new declaration, if it changed.
+    pub(crate) header: Option<(String, String)>,
+    pub(crate) fields: Vec<MemberChange>,
+    pub(crate) methods: Vec<MemberChange>,
+}
+
+/// A change to a field or method.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum MemberChange {
+    Added(String),
+    Removed(String),
+    /// The descriptor of a field changed.
+    Changed {
+        from: String,
+        to: String,
+    },
+    /// The bytecode of a watched method changed.
+    Bytecode(String),
+}
+
+impl ApiDiff {
+    /// Compare the classes of two [`CodeBundle`]s.
+    ///
+    /// Changed bytecode is only reported for [`Watched`] methods.
+    ///
+    /// # Errors
+    /// Returns an error if a class could not be parsed.
+    pub(crate) fn new(
+        from: &CodeBundle,
+        to: &CodeBundle,
+        watched: &Watched,
+    ) -> anyhow::Result<Self> {
+        from.with_classes(|from| to.with_classes(|to| Self::from_classes(from, to, watched)))
+    }
+
+    fn from_classes(
+        from: &Classes<'_>,
+        to: &Classes<'_>,
+        watched: &Watched,
+    ) -> anyhow::Result<Self> {
+        let mut diff = Self::default();
+
+        for name in from.class_names() {
+            if !to.contains(name) {
+                diff.removed.push(name.to_string());
+            }
+        }
+        for name in to.class_names() {
+            let Some(new) = to.try_get(name)? else { continue };
+            let Some(old) = from.try_get(name)? else {
+                diff.added.push(name.to_string());
+                continue;
+            };
+
+            let class = ClassDiff::new(old, from, new, to, watched);
+            if !class.is_empty() {
+                diff.changed.push(class);
+            }
+        }
+
+        diff.added.sort_unstable();
+        diff.removed.sort_unstable();
+        diff.changed.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+        Ok(diff)
+    }
+
+    /// Get the names of changed classes with watched methods.
+    pub(crate) fn watched(&self) -> impl Iterator<Item = &str> {
+        self.changed
+            .iter()
+            .filter(|class| class.methods.iter().any(|m| matches!(m, MemberChange::Bytecode(_))))
+            .map(|class| class.name.as_str())
+    }
+
+    /// Render a report listing all changes.
+    #[must_use]
+    pub(crate) fn render(&self, from: &Version, to: &Version) -> String {
+        let mut report = format!("# Changes from {from} to {to}\n");
+
+        let watched = self.watched().collect::<Vec<_>>();
+        if !watched.is_empty() {
+            report.push_str("\n## Watched\n\n");
+            for class in watched {
+                writeln!(report, "- `{class}`").unwrap();
+            }
+        }
+
+        for (title, classes) in [("Added", &self.added), ("Removed", &self.removed)] {
+            if !classes.is_empty() {
+                write!(report, "\n## {title}\n\n").unwrap();
+                for class in classes {
+                    writeln!(report, "- `{class}`").unwrap();
+                }
+            }
+        }
+
+        if !self.changed.is_empty() {
+            report.push_str("\n## Changed\n");
+            for class in &self.changed {
+                write!(report, "\n### `{}`\n\n", class.name).unwrap();
+                if let Some((from, to)) = &class.header {
+                    writeln!(report, "- changed `{from}` -> `{to}`").unwrap();
+                }
+                for change in class.fields.iter().chain(&class.methods) {
+                    match change {
+                        MemberChange::Added(name) => writeln!(report, "- added `{name}`"),
+                        MemberChange::Removed(name) => writeln!(report, "- removed `{name}`"),
+                        MemberChange::Changed { from, to } => {
+                            writeln!(report, "- changed `{from}` -> `{to}`")
+                        }
+                        MemberChange::Bytecode(name) => {
+                            writeln!(report, "- **bytecode changed** `{name}`")
+                        }
+                    }
+                    .unwrap();
+                }
+            }
+        }
+
+        report
+    }
+}
+
+impl ClassDiff {
+    fn new(
+        old: &ClassFile<'_>,
+        old_classes: &Classes<'_>,
+        new: &ClassFile<'_>,
+        new_classes: &Classes<'_>,
+        watched: &Watched,
+    ) -> Self {
+        let (old_header, new_header) = (header(old), header(new));
+
+        let fields = |class: &ClassFile<'_>| {
+            class
+                .fields
+                .iter()
+                .map(|f| (f.name.to_string(), format!("{}: {}", f.name, f.descriptor)))
+                .collect()
+        };
+        let methods = |class: &ClassFile<'_>| {
+            class
+                .methods
+                .iter()
+                .map(|m| {
+                    let method = format!("{}{}", m.name, m.descriptor);
+                    (method.clone(), method)
+                })
+                .collect()
+        };
+
+        let mut methods = diff_members(&methods(old), &methods(new));
+        for method in &new.methods {
+            let Some(old_method) = old
+                .methods
+                .iter()
+                .find(|m| m.name == method.name && m.descriptor == method.descriptor)
+            else {
+                continue;
+            };
+
+            if watched.contains(&new.this_class, method)
+                && Disassembler::new(old_classes, InlineOptions::NONE)
+                    .method_listing(old, old_method)
+                    != Disassembler::new(new_classes, InlineOptions::NONE)
+                        .method_listing(new, method)
+            {
+                methods
+                    .push(MemberChange::Bytecode(format!("{}{}", method.name, method.descriptor)));
+            }
+        }
+
+        Self {
+            name: new.this_class.to_string(),
+            header: (old_header != new_header).then_some((old_header, new_header)),
+            fields: diff_members(&fields(old), &fields(new)),
+            methods,
+        }
+    }
+
+    /// Returns `true` if nothing in the class changed.
+    #[must_use]
+    pub(crate) fn is_empty(&self) -> bool {
+        self.header.is_none() && self.fields.is_empty() && self.methods.is_empty()
+    }
+}
+
+/// Compare two maps of member keys to their displayed names.
+fn diff_members(
+    from: &BTreeMap<String, String>,
+    to: &BTreeMap<String, String>,
+) -> Vec<MemberChange> {
+    let mut changes = Vec::new();
+
+    for (key, old) in from {
+        match to.get(key) {
+            Some(new) if new != old => {
+                changes.push(MemberChange::Changed { from: old.clone(), to: new.clone() });
+            }
+            Some(_) => {}
+            None => changes.push(MemberChange::Removed(old.clone())),
+        }
+    }
+    for (key, new) in to {
+        if !from.contains_key(key) {
+            changes.push(MemberChange::Added(new.clone()));
+        }
+    }
+
+    changes
+}
+
+#[test]
+#[cfg(test)]
+fn api_diff() {
+    let map = |fields: &[(&str, &str)]| {
+        fields
+            .iter()
+            .map(|(name, descriptor)| (name.to_string(), format!("{name}: {descriptor}")))
+            .collect::<BTreeMap<_, _>>()
+    };
+    assert_eq!(
+        diff_members(
+            &map(&[("STONE", "Lnet/minecraft/item/Item;"), ("DIRT", "Lnet/minecraft/item/Item;")]),
+            &map(&[
+                ("STONE", "Lnet/minecraft/item/BlockItem;"),
+                ("GRASS", "Lnet/minecraft/item/Item;")
+            ]),
+        ),
+        [
+            MemberChange::Removed(String::from("DIRT: Lnet/minecraft/item/Item;")),
+            MemberChange::Changed {
+                from: String::from("STONE: Lnet/minecraft/item/Item;"),
+                to: String::from("STONE: Lnet/minecraft/item/BlockItem;"),
+            },
+            MemberChange::Added(String::from("GRASS: Lnet/minecraft/item/Item;")),
+        ]
+    );
+}
+
+#[test]
+#[cfg(test)]
+fn watched() {
+    use cafebabe::MethodAccessFlags;
+    use froglight_dependency::dependency::minecraft::class_builder::ClassBuilder;
+
+    let bundle = |registry: &str, codec: &str, other: &str| {
+        let items = ClassBuilder::new("Items")
+            .method(MethodAccessFlags::STATIC, "<clinit>", "()V", |code| {
+                code.ldc_string("stone");
+                code.invokestatic("Items", "register", "(Ljava/lang/String;)LItem;");
+                code.putstatic("Items", "STONE", "LItem;");
+                code.return_();
+            })
+            .method(MethodAccessFlags::STATIC, "register", "(Ljava/lang/String;)LItem;", |code| {
+                code.aload(0);
+                code.invokestatic("Registry", registry, "(Ljava/lang/String;)LItem;");
+                code.areturn();
+            })
+            .method(MethodAccessFlags::STATIC, "other", "()V", |code| {
+                code.ldc_string(other);
+                code.pop();
+                code.return_();
+            })
+            .build();
+        let state = ClassBuilder::new("PlayState")
+            .method(MethodAccessFlags::STATIC, "<clinit>", "()V", |code| {
+                code.getstatic("SetYawPacket", "CODEC", "LPacketCodec;");
+                code.pop();
+                code.return_();
+            })
+            .build();
+        let packet = ClassBuilder::new("SetYawPacket")
+            .method(MethodAccessFlags::STATIC, "<clinit>", "()V", |code| {
+                code.ldc_string(codec);
+                code.pop();
+                code.return_();
+            })
+            .build();
+
+        CodeBundle::from_classes([
+            (String::from("Items"), items),
+            (String::from("PlayState"), state),
+            (String::from("SetYawPacket"), packet),
+        ])
+    };
+    let old = bundle("register", "yaw", "old");
+    let new = bundle("registerItem", "pitch", "new");
+
+    let mut watched = Watched::default();
+    for bundle in [&old, &new] {
+        bundle.with_classes(|classes| {
+            watched.extend_from(
+                classes,
+                vec![
+                    (String::from("Items"), String::from("<clinit>"), String::from("()V")),
+                    (String::from("PlayState"), String::from("<clinit>"), String::from("()V")),
+                ],
+                Some("LPacketCodec;"),
+            );
+        });
+    }
+
+    // `Items.register` is followed and `SetYawPacket.CODEC` is created by its
+    // static initializer, but `Items.other` is never read.
+    let diff = ApiDiff::new(&old, &new, &watched).unwrap();
+    assert_eq!(diff.watched().collect::<Vec<_>>(), ["Items", "SetYawPacket"]);
+    assert_eq!(
+        diff.changed[0].methods,
+        [MemberChange::Bytecode(String::from("register(Ljava/lang/String;)LItem;"))]
+    );
+    assert_eq!(diff.changed[1].methods, [MemberChange::Bytecode(String::from("<clinit>()V"))]);
+}
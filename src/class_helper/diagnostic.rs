@@ -0,0 +1,145 @@
+//! Problems found while extracting data from bytecode.
+
+use std::fmt;
+
+use cafebabe::{
+    ClassFile, MethodInfo,
+    attributes::AttributeData,
+    bytecode::{ByteCode, Opcode},
+};
+
+use super::registration::Registration;
+
+/// A problem found while extracting data from bytecode,
+/// such as an unknown builder method or a missing identifier.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Diagnostic {
+    /// A description of the problem.
+    pub(crate) message: String,
+    /// The class being extracted.
+    pub(crate) class: Option<String>,
+    /// The method containing the opcode, with its descriptor.
+    pub(crate) method: Option<String>,
+    /// The bytecode offset of the opcode.
+    pub(crate) offset: Option<usize>,
+    /// The opcodes surrounding the offset.
+    pub(crate) context: Vec<(usize, String)>,
+}
+
+impl Diagnostic {
+    /// The number of opcodes to include on each side of the offset.
+    const CONTEXT: usize = 3;
+
+    /// Create a new [`Diagnostic`] without a location.
+    pub(crate) fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            class: None,
+            method: None,
+            offset: None,
+            context: Vec::new(),
+        }
+    }
+
+    /// Set the class being extracted.
+    #[must_use]
+    pub(crate) fn class(mut self, class: &str) -> Self {
+        self.class = Some(class.to_string());
+        self
+    }
+
+    /// Set the location to an opcode inside of a class.
+    ///
+    /// If the opcode does not belong to any method in the class,
+    /// such as when it was reached through a bootstrap method,
+    /// only the class is recorded.
+    #[must_use]
+    pub(crate) fn at(self, class: &ClassFile<'_>, opcode: &Opcode<'_>) -> Self {
+        self.locate(class, |method| {
+            let opcodes = &Self::bytecode(method)?.opcodes;
+            opcodes.iter().position(|(_, op)| core::ptr::eq(op, opcode))
+        })
+    }
+
+    /// Set the location to the opcode at an offset inside of a method.
+    ///
+    /// If the method does not have an opcode at that offset,
+    /// only the class is recorded.
+    #[must_use]
+    pub(crate) fn at_offset(self, class: &ClassFile<'_>, method: &str, offset: usize) -> Self {
+        self.locate(class, |info| {
+            let opcodes = &Self::bytecode(info).filter(|_| info.name == method)?.opcodes;
+            opcodes.iter().position(|(o, _)| *o == offset)
+        })
+    }
+
+    /// Set the location to the `putstatic` that stored a [`Registration`].
+    ///
+    /// If the field was stored by a followed call,
+    /// only the class is recorded.
+    #[must_use]
+    pub(crate) fn at_registration(
+        self,
+        class: &ClassFile<'_>,
+        registration: &Registration,
+    ) -> Self {
+        match registration.offset {
+            Some(offset) => self.at_offset(class, "<clinit>", offset),
+            None => self.class(&class.this_class),
+        }
+    }
+
+    /// Set the location to the first method where `find` returns
+    /// the index of an opcode.
+    fn locate(
+        mut self,
+        class: &ClassFile<'_>,
+        mut find: impl FnMut(&MethodInfo<'_>) -> Option<usize>,
+    ) -> Self {
+        self.class = Some(class.this_class.to_string());
+
+        for method in &class.methods {
+            let Some(index) = find(method) else { continue };
+            let opcodes = &Self::bytecode(method).unwrap().opcodes;
+
+            let start = index.saturating_sub(Self::CONTEXT);
+            let end = (index + Self::CONTEXT + 1).min(opcodes.len());
+
+            self.method = Some(format!("{}{}", method.name, method.descriptor));
+            self.offset = Some(opcodes[index].0);
+            self.context =
+                opcodes[start..end].iter().map(|(o, op)| (*o, format!("{op:?}"))).collect();
+            break;
+        }
+
+        self
+    }
+
+    fn bytecode<'a>(method: &'a MethodInfo<'_>) -> Option<&'a ByteCode<'a>> {
+        method.attributes.iter().find_map(|attr| match &attr.data {
+            AttributeData::Code(code) => code.bytecode.as_ref(),
+            _ => None,
+        })
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)?;
+
+        match (&self.class, &self.method, self.offset) {
+            (Some(class), Some(method), Some(offset)) => {
+                write!(f, "\n    at {class}.{method} @ {offset}")?;
+            }
+            (Some(class), ..) => write!(f, "\n    in {class}")?,
+            _ => {}
+        }
+
+        for (offset, opcode) in &self.context {
+            let marker = if Some(*offset) == self.offset { ">" } else { " " };
+            write!(f, "\n    {marker} {offset:>5}: {opcode}")?;
+        }
+
+        Ok(())
+    }
+}
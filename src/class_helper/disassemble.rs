@@ -0,0 +1,505 @@
+//! A `javap`-style disassembler for inspecting classes.
+//!
+//! Useful when a new version breaks an extractor and the
+//! bytecode it is looking at needs to be checked by hand.
+
+use std::fmt::Write;
+
+use cafebabe::{
+    attributes::{AttributeData, BootstrapMethodEntry},
+    bytecode::Opcode,
+    constant_pool::{
+        BootstrapArgument, InvokeDynamic, LiteralConstant, Loadable, MemberRef, MethodHandle,
+        ObjectArrayType,
+    },
+    ClassAccessFlags, ClassFile, FieldAccessFlags, FieldInfo, MethodAccessFlags, MethodInfo,
+};
+use froglight_dependency::dependency::minecraft::minecraft_code::Classes;
+
+use super::{inline::InlineOptions, interpreter::InvokeKind};
+
+/// Disassembles classes and methods into a readable listing.
+pub(crate) struct Disassembler<'c, 'a> {
+    classes: &'c Classes<'a>,
+    /// Which calls to disassemble inline.
+    options: InlineOptions,
+    /// The number of nested calls currently being disassembled.
+    depth: usize,
+    /// The methods currently being disassembled, as `(class, name,
+    /// descriptor)`.
+    stack: Vec<(String, String, String)>,
+    output: String,
+}
+
+impl<'c, 'a> Disassembler<'c, 'a> {
+    /// Create a new [`Disassembler`].
+    ///
+    /// Methods are disassembled inline wherever
+    /// [`iter_code_recursive`](super::ClassHelper::iter_code_recursive)
+    /// would follow them with the same [`InlineOptions`]:
+    /// `invokedynamic` targets are always followed,
+    /// other calls only as allowed by the options.
+    pub(crate) fn new(classes: &'c Classes<'a>, options: InlineOptions) -> Self {
+        Self { classes, options, depth: 0, stack: Vec::new(), output: String::new() }
+    }
+
+    /// Disassemble a class.
+    ///
+    /// If `method` is set, only methods with that name are included.
+    pub(crate) fn class(mut self, class: &ClassFile<'_>, method: Option<&str>) -> String {
+        self.output.push_str(&header(class));
+        self.output.push('\n');
+
+        if method.is_none() {
+            for field in &class.fields {
+                writeln!(
+                    self.output,
+                    "  {}{}: {}",
+                    field_access(field),
+                    field.name,
+                    field.descriptor
+                )
+                .unwrap();
+            }
+        }
+
+        for info in class.methods.iter().filter(|m| method.is_none_or(|name| m.name == name)) {
+            self.output.push('\n');
+            self.method(class, info, 2);
+        }
+
+        self.output
+    }
+
+    /// Disassemble a single method.
+    pub(crate) fn method_listing(
+        mut self,
+        class: &ClassFile<'_>,
+        method: &MethodInfo<'_>,
+    ) -> String {
+        self.method(class, method, 0);
+        self.output
+    }
+
+    /// Disassemble a method and any targets it reaches.
+    fn method(&mut self, class: &ClassFile<'_>, method: &MethodInfo<'_>, indent: usize) {
+        let pad = " ".repeat(indent);
+        writeln!(self.output, "{pad}{}{}{}", method_access(method), method.name, method.descriptor)
+            .unwrap();
+
+        let Some(bytecode) = method.attributes.iter().find_map(|attr| match &attr.data {
+            AttributeData::Code(code) => code.bytecode.as_ref(),
+            _ => None,
+        }) else {
+            return;
+        };
+
+        for (offset, opcode) in &bytecode.opcodes {
+            writeln!(self.output, "{pad}  {offset:>5}: {}", instruction(*offset, opcode)).unwrap();
+            match opcode {
+                Opcode::Invokedynamic(invoke) => self.bootstrap(class, invoke, indent + 9),
+                Opcode::Invokestatic(member) | Opcode::Invokespecial(member) => {
+                    let kind = if matches!(opcode, Opcode::Invokestatic(_)) {
+                        InvokeKind::Static
+                    } else {
+                        InvokeKind::Special
+                    };
+                    let name = &member.name_and_type.name;
+                    if self.options.follows(kind, name) && self.depth < self.options.max_depth {
+                        let pad = " ".repeat(indent + 9);
+                        writeln!(self.output, "{pad}// call: {}", member_ref(member)).unwrap();
+
+                        self.depth += 1;
+                        self.target(
+                            &member.class_name,
+                            name,
+                            &member.name_and_type.descriptor,
+                            indent + 11,
+                        );
+                        self.depth -= 1;
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Resolve the bootstrap method of an `invokedynamic` instruction.
+    fn bootstrap(&mut self, class: &ClassFile<'_>, invoke: &InvokeDynamic<'_>, indent: usize) {
+        let pad = " ".repeat(indent);
+        let Some(entry) = bootstrap_entry(class, invoke.attr_index) else {
+            writeln!(self.output, "{pad}// missing bootstrap method #{}", invoke.attr_index)
+                .unwrap();
+            return;
+        };
+
+        writeln!(self.output, "{pad}// bootstrap: {}", method_handle(&entry.method)).unwrap();
+        for argument in &entry.arguments {
+            match argument {
+                BootstrapArgument::MethodHandle(handle) => {
+                    writeln!(self.output, "{pad}// target: {}", method_handle(handle)).unwrap();
+                    let member = &handle.member_ref;
+                    self.target(&handle.class_name, &member.name, &member.descriptor, indent + 2);
+                }
+                BootstrapArgument::LiteralConstant(constant) => {
+                    writeln!(self.output, "{pad}// argument: {}", literal(constant)).unwrap();
+                }
+                BootstrapArgument::ClassInfo(class) => {
+                    writeln!(self.output, "{pad}// argument: class {class}").unwrap();
+                }
+                BootstrapArgument::MethodType(descriptor) => {
+                    writeln!(self.output, "{pad}// argument: methodtype {descriptor}").unwrap();
+                }
+            }
+        }
+    }
+
+    /// Disassemble a method reached through a call or method handle,
+    /// unless it is already being disassembled.
+    fn target(&mut self, class: &str, name: &str, descriptor: &str, indent: usize) {
+        let pad = " ".repeat(indent);
+        let key = (class.to_string(), name.to_string(), descriptor.to_string());
+        if self.stack.contains(&key) {
+            writeln!(self.output, "{pad}// recursive call, not followed").unwrap();
+            return;
+        }
+
+        let Some((class, method)) = self.classes.resolve_method(class, name, Some(descriptor))
+        else {
+            writeln!(self.output, "{pad}// target not found").unwrap();
+            return;
+        };
+
+        self.stack.push(key);
+        self.method(class, method, indent);
+        self.stack.pop();
+    }
+}
+
+/// Find a bootstrap method entry by index.
+fn bootstrap_entry<'a>(
+    class: &'a ClassFile<'_>,
+    index: u16,
+) -> Option<&'a BootstrapMethodEntry<'a>> {
+    class.attributes.iter().find_map(|attr| match &attr.data {
+        AttributeData::BootstrapMethods(entries) => entries.get(usize::from(index)),
+        _ => None,
+    })
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Format the declaration of a class,
+/// such as `public class Items extends Object`.
+pub(crate) fn header(class: &ClassFile<'_>) -> String {
+    let kind = if class.access_flags.contains(ClassAccessFlags::INTERFACE) {
+        "interface"
+    } else if class.access_flags.contains(ClassAccessFlags::ENUM) {
+        "enum"
+    } else {
+        "class"
+    };
+
+    let mut header = format!("{}{kind} {}", class_access(class), class.this_class);
+    if let Some(super_class) = &class.super_class {
+        write!(header, " extends {super_class}").unwrap();
+    }
+    if !class.interfaces.is_empty() {
+        let interfaces = class.interfaces.iter().map(|i| &**i).collect::<Vec<_>>();
+        write!(header, " implements {}", interfaces.join(", ")).unwrap();
+    }
+    header
+}
+
+fn class_access(class: &ClassFile<'_>) -> String {
+    let flags = class.access_flags;
+    let mut access = String::new();
+    if flags.contains(ClassAccessFlags::PUBLIC) {
+        access.push_str("public ");
+    }
+    if flags.contains(ClassAccessFlags::ABSTRACT) && !flags.contains(ClassAccessFlags::INTERFACE) {
+        access.push_str("abstract ");
+    }
+    if flags.contains(ClassAccessFlags::FINAL) {
+        access.push_str("final ");
+    }
+    access
+}
+
+fn field_access(field: &FieldInfo<'_>) -> String {
+    let flags = field.access_flags;
+    [
+        (FieldAccessFlags::PUBLIC, "public "),
+        (FieldAccessFlags::PRIVATE, "private "),
+        (FieldAccessFlags::PROTECTED, "protected "),
+        (FieldAccessFlags::STATIC, "static "),
+        (FieldAccessFlags::FINAL, "final "),
+        (FieldAccessFlags::VOLATILE, "volatile "),
+        (FieldAccessFlags::TRANSIENT, "transient "),
+    ]
+    .into_iter()
+    .filter_map(|(flag, name)| flags.contains(flag).then_some(name))
+    .collect()
+}
+
+fn method_access(method: &MethodInfo<'_>) -> String {
+    let flags = method.access_flags;
+    [
+        (MethodAccessFlags::PUBLIC, "public "),
+        (MethodAccessFlags::PRIVATE, "private "),
+        (MethodAccessFlags::PROTECTED, "protected "),
+        (MethodAccessFlags::STATIC, "static "),
+        (MethodAccessFlags::FINAL, "final "),
+        (MethodAccessFlags::SYNCHRONIZED, "synchronized "),
+        (MethodAccessFlags::NATIVE, "native "),
+        (MethodAccessFlags::ABSTRACT, "abstract "),
+        (MethodAccessFlags::SYNTHETIC, "synthetic "),
+    ]
+    .into_iter()
+    .filter_map(|(flag, name)| flags.contains(flag).then_some(name))
+    .collect()
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Format an instruction, resolving jump offsets to their targets.
+fn instruction(offset: usize, opcode: &Opcode<'_>) -> String {
+    let jump = |jump: &i32| {
+        isize::try_from(*jump)
+            .ok()
+            .and_then(|jump| offset.checked_add_signed(jump))
+            .map_or_else(|| String::from("?"), |target| target.to_string())
+    };
+
+    let operand = match opcode {
+        Opcode::Aload(index)
+        | Opcode::Astore(index)
+        | Opcode::Dload(index)
+        | Opcode::Dstore(index)
+        | Opcode::Fload(index)
+        | Opcode::Fstore(index)
+        | Opcode::Iload(index)
+        | Opcode::Istore(index)
+        | Opcode::Lload(index)
+        | Opcode::Lstore(index)
+        | Opcode::Ret(index) => index.to_string(),
+        Opcode::Bipush(value) => value.to_string(),
+        Opcode::Sipush(value) => value.to_string(),
+        Opcode::Iinc(index, value) => format!("{index}, {value}"),
+        Opcode::Goto(offset)
+        | Opcode::Jsr(offset)
+        | Opcode::IfAcmpeq(offset)
+        | Opcode::IfAcmpne(offset)
+        | Opcode::IfIcmpeq(offset)
+        | Opcode::IfIcmpge(offset)
+        | Opcode::IfIcmpgt(offset)
+        | Opcode::IfIcmple(offset)
+        | Opcode::IfIcmplt(offset)
+        | Opcode::IfIcmpne(offset)
+        | Opcode::Ifeq(offset)
+        | Opcode::Ifge(offset)
+        | Opcode::Ifgt(offset)
+        | Opcode::Ifle(offset)
+        | Opcode::Iflt(offset)
+        | Opcode::Ifne(offset)
+        | Opcode::Ifnonnull(offset)
+        | Opcode::Ifnull(offset) => jump(offset),
+        Opcode::Getfield(member)
+        | Opcode::Getstatic(member)
+        | Opcode::Putfield(member)
+        | Opcode::Putstatic(member)
+        | Opcode::Invokespecial(member)
+        | Opcode::Invokestatic(member)
+        | Opcode::Invokevirtual(member) => member_ref(member),
+        Opcode::Invokeinterface(member, count) => format!("{}, {count}", member_ref(member)),
+        Opcode::Invokedynamic(InvokeDynamic { attr_index, name_and_type }) => {
+            format!("#{attr_index} {}:{}", name_and_type.name, name_and_type.descriptor)
+        }
+        Opcode::Ldc(loadable) | Opcode::LdcW(loadable) | Opcode::Ldc2W(loadable) => {
+            match loadable {
+                Loadable::LiteralConstant(constant) => literal(constant),
+                Loadable::ClassInfo(class) => format!("class {class}"),
+                Loadable::MethodHandle(handle) => method_handle(handle),
+                Loadable::MethodType(descriptor) => format!("methodtype {descriptor}"),
+                Loadable::Dynamic(dynamic) => format!(
+                    "dynamic #{} {}:{}",
+                    dynamic.attr_index,
+                    dynamic.name_and_type.name,
+                    dynamic.name_and_type.descriptor
+                ),
+            }
+        }
+        Opcode::New(class) => class.to_string(),
+        Opcode::Anewarray(ty) | Opcode::Checkcast(ty) | Opcode::Instanceof(ty) => object_type(ty),
+        Opcode::Multianewarray(ty, dimensions) => format!("{}, {dimensions}", object_type(ty)),
+        Opcode::Newarray(ty) => format!("{ty:?}").to_lowercase(),
+        Opcode::Lookupswitch(table) => {
+            let mut cases = table
+                .match_offsets
+                .iter()
+                .map(|(key, offset)| format!("{key}: {}", jump(offset)))
+                .collect::<Vec<_>>();
+            cases.push(format!("default: {}", jump(&table.default)));
+            format!("{{ {} }}", cases.join(", "))
+        }
+        Opcode::Tableswitch(table) => {
+            let mut cases = (table.low..=table.high)
+                .zip(&table.jumps)
+                .map(|(key, offset)| format!("{key}: {}", jump(offset)))
+                .collect::<Vec<_>>();
+            cases.push(format!("default: {}", jump(&table.default)));
+            format!("{{ {} }}", cases.join(", "))
+        }
+        _ => String::new(),
+    };
+
+    let mnemonic = mnemonic(opcode);
+    if operand.is_empty() {
+        return mnemonic;
+    }
+    format!("{mnemonic} {operand}")
+}
+
+/// Get the `javap` mnemonic of an opcode, such as `iconst_0` or `if_icmpne`.
+fn mnemonic(opcode: &Opcode<'_>) -> String {
+    let debug = format!("{opcode:?}");
+    let name = debug.split(['(', ' ']).next().unwrap_or_default();
+
+    let mut mnemonic = String::with_capacity(name.len() + 2);
+    for (index, c) in name.char_indices() {
+        if index > 0
+            && (c.is_ascii_uppercase() || (c.is_ascii_digit() && name[..index].ends_with("const")))
+        {
+            mnemonic.push('_');
+        }
+        mnemonic.push(c.to_ascii_lowercase());
+    }
+    mnemonic
+}
+
+fn member_ref(member: &MemberRef<'_>) -> String {
+    format!(
+        "{}.{}:{}",
+        member.class_name, member.name_and_type.name, member.name_and_type.descriptor
+    )
+}
+
+fn method_handle(handle: &MethodHandle<'_>) -> String {
+    format!(
+        "{} {}.{}:{}",
+        format!("{:?}", handle.kind).to_lowercase(),
+        handle.class_name,
+        handle.member_ref.name,
+        handle.member_ref.descriptor
+    )
+}
+
+fn object_type(ty: &ObjectArrayType<'_>) -> String {
+    match ty {
+        ObjectArrayType::ArrayType(descriptor) => descriptor.to_string(),
+        ObjectArrayType::BinaryName(name) => name.to_string(),
+    }
+}
+
+fn literal(constant: &LiteralConstant<'_>) -> String {
+    match constant {
+        LiteralConstant::Integer(value) => value.to_string(),
+        LiteralConstant::Float(value) => format!("{value:?}f"),
+        LiteralConstant::Long(value) => format!("{value}l"),
+        LiteralConstant::Double(value) => format!("{value:?}d"),
+        LiteralConstant::String(value) => format!("{value:?}"),
+        LiteralConstant::StringBytes(bytes) => format!("bytes {bytes:?}"),
+    }
+}
+
+#[test]
+#[cfg(test)]
+fn disassemble() {
+    use std::borrow::Cow;
+
+    use cafebabe::{bytecode::LookupTable, constant_pool::NameAndType};
+
+    let member = MemberRef {
+        class_name: Cow::Borrowed("net/minecraft/item/Items"),
+        name_and_type: NameAndType {
+            name: Cow::Borrowed("register"),
+            descriptor: Cow::Borrowed("(Ljava/lang/String;)Lnet/minecraft/item/Item;"),
+        },
+    };
+
+    assert_eq!(instruction(0, &Opcode::Iconst0), "iconst_0");
+    assert_eq!(instruction(0, &Opcode::IconstM1), "iconst_m1");
+    assert_eq!(instruction(0, &Opcode::AconstNull), "aconst_null");
+    assert_eq!(instruction(0, &Opcode::Dup2X1), "dup2_x1");
+    assert_eq!(instruction(0, &Opcode::I2f), "i2f");
+    assert_eq!(instruction(0, &Opcode::Aload(0)), "aload 0");
+    assert_eq!(instruction(10, &Opcode::IfIcmpne(-4)), "if_icmpne 6");
+    assert_eq!(
+        instruction(
+            0,
+            &Opcode::LdcW(Loadable::LiteralConstant(LiteralConstant::String(Cow::Borrowed(
+                "stone"
+            ))))
+        ),
+        "ldc_w \"stone\""
+    );
+    assert_eq!(
+        instruction(0, &Opcode::Ldc2W(Loadable::LiteralConstant(LiteralConstant::Double(1.0)))),
+        "ldc2_w 1.0d"
+    );
+    assert_eq!(
+        instruction(0, &Opcode::Invokestatic(member)),
+        "invokestatic net/minecraft/item/Items.register:\
+         (Ljava/lang/String;)Lnet/minecraft/item/Item;"
+    );
+    assert_eq!(
+        instruction(
+            20,
+            &Opcode::Lookupswitch(LookupTable { default: 30, match_offsets: vec![(1, 10)] })
+        ),
+        "lookupswitch { 1: 30, default: 50 }"
+    );
+}
+
+#[test]
+#[cfg(test)]
+fn inline_calls() {
+    use froglight_dependency::dependency::minecraft::{
+        class_builder::ClassBuilder, minecraft_code::CodeBundle,
+    };
+
+    const STRING: &str = "(Ljava/lang/String;)Ljava/lang/String;";
+
+    let class = ClassBuilder::new("Blocks")
+        .method(MethodAccessFlags::STATIC, "<clinit>", "()V", |code| {
+            code.ldc_string("stone");
+            code.invokestatic("Blocks", "register", STRING);
+            code.putstatic("Blocks", "STONE", "Ljava/lang/String;");
+            code.return_();
+        })
+        .method(MethodAccessFlags::STATIC, "register", STRING, |code| {
+            code.aload(0);
+            code.invokestatic("Blocks", "register", STRING);
+            code.areturn();
+        })
+        .build();
+    let bundle = CodeBundle::from_classes([(String::from("Blocks"), class)]);
+
+    let listing = |options: InlineOptions| {
+        bundle.with_classes(|classes| {
+            let class = classes.get("Blocks").unwrap();
+            Disassembler::new(classes, options).class(class, Some("<clinit>"))
+        })
+    };
+
+    // Static calls are followed the same way extractors follow them...
+    let followed = listing(InlineOptions::default());
+    assert!(followed.contains("// call: Blocks.register:"), "{followed}");
+    assert!(followed.contains("static register(Ljava/lang/String;)"), "{followed}");
+    assert!(followed.contains("// recursive call, not followed"), "{followed}");
+
+    // ...unless the options do not allow them.
+    let listing = listing(InlineOptions::NONE);
+    assert!(!listing.contains("// call:"), "{listing}");
+    assert!(!listing.contains("static register"), "{listing}");
+}
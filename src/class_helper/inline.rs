@@ -0,0 +1,364 @@
+//! Following method calls while iterating over bytecode.
+//!
+//! Registrations frequently go through private static helpers,
+//! such as `Items.register(String, Item)`, whose bodies would
+//! otherwise never be seen by an extractor.
+
+use cafebabe::{
+    attributes::{AttributeData, CodeData},
+    bytecode::Opcode,
+    ClassFile, MethodInfo,
+};
+use froglight_dependency::dependency::minecraft::minecraft_code::Classes;
+
+use super::{
+    interpreter::{Effect, Interpreter, InvokeKind, Value},
+    ClassHelper,
+};
+
+/// Which method calls to follow when iterating over bytecode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct InlineOptions {
+    /// The maximum number of nested calls to follow.
+    ///
+    /// `invokedynamic` bootstrap methods do not count towards this limit.
+    pub(crate) max_depth: usize,
+    /// Follow `invokestatic` calls.
+    pub(crate) statics: bool,
+    /// Follow `invokespecial` calls to private and super methods.
+    pub(crate) specials: bool,
+    /// Follow `invokespecial` calls to constructors.
+    pub(crate) constructors: bool,
+}
+
+impl Default for InlineOptions {
+    fn default() -> Self {
+        Self { max_depth: 4, statics: true, specials: true, constructors: false }
+    }
+}
+
+impl InlineOptions {
+    /// Only follow `invokedynamic` bootstrap methods.
+    pub(crate) const NONE: Self =
+        Self { max_depth: 0, statics: false, specials: false, constructors: false };
+
+    /// Set the maximum number of nested calls to follow.
+    #[must_use]
+    pub(crate) const fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Returns `true` if a call of this kind should be followed,
+    /// ignoring the depth limit.
+    pub(super) fn follows(&self, kind: InvokeKind, name: &str) -> bool {
+        match kind {
+            InvokeKind::Static => self.statics,
+            InvokeKind::Special if name == "<init>" => self.constructors,
+            InvokeKind::Special => self.specials,
+            InvokeKind::Virtual | InvokeKind::Interface => false,
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Iterate over opcodes, following calls into methods inside the [`Classes`].
+pub(super) fn iter_code(
+    class: &ClassFile<'_>,
+    initial: &[&Opcode<'_>],
+    classes: &Classes<'_>,
+    options: InlineOptions,
+    f: &mut impl FnMut(&Opcode<'_>),
+) {
+    Inliner::new(classes, options).walk(class, initial, f);
+}
+
+/// Iterate over opcodes the same way as [`iter_code`],
+/// returning every method that was followed.
+pub(super) fn followed_methods(
+    class: &ClassFile<'_>,
+    initial: &[&Opcode<'_>],
+    classes: &Classes<'_>,
+    options: InlineOptions,
+    f: &mut impl FnMut(&Opcode<'_>),
+) -> Vec<Frame> {
+    let mut inliner = Inliner::new(classes, options);
+    inliner.walk(class, initial, f);
+    inliner.followed
+}
+
+/// Interpret opcodes, following calls into methods inside the [`Classes`].
+///
+/// Followed calls are interpreted with their arguments bound,
+/// and their return value replaces the result of the call.
+pub(super) fn interpret_code(
+    class: &ClassFile<'_>,
+    initial: &[&Opcode<'_>],
+    classes: &Classes<'_>,
+    options: InlineOptions,
+    interpreter: &mut Interpreter,
+    f: &mut impl FnMut(&Opcode<'_>, Option<&Effect>),
+) {
+    Inliner::new(classes, options).interpret(class, initial, interpreter, f);
+}
+
+/// A method being followed, as `(class, name, descriptor)`.
+pub(super) type Frame = (String, String, String);
+
+struct Inliner<'c, 'a> {
+    classes: &'c Classes<'a>,
+    options: InlineOptions,
+    /// The methods currently being followed, used to detect cycles.
+    frames: Vec<Frame>,
+    /// The number of nested calls currently being followed.
+    depth: usize,
+    /// Every method that was followed, in the order they were entered.
+    followed: Vec<Frame>,
+}
+
+impl<'c, 'a> Inliner<'c, 'a> {
+    fn new(classes: &'c Classes<'a>, options: InlineOptions) -> Self {
+        Self { classes, options, frames: Vec::new(), depth: 0, followed: Vec::new() }
+    }
+
+    fn walk(
+        &mut self,
+        class: &ClassFile<'_>,
+        opcodes: &[&Opcode<'_>],
+        f: &mut impl FnMut(&Opcode<'_>),
+    ) {
+        for opcode in opcodes {
+            f(opcode);
+            match opcode {
+                Opcode::Invokedynamic(invoke) => {
+                    for handle in class.class_bootstrap_methods(invoke.attr_index) {
+                        let member = &handle.member_ref;
+                        if let Some((callee, method)) =
+                            self.enter(&handle.class_name, &member.name, &member.descriptor)
+                        {
+                            self.walk(callee, &Self::opcodes(method), f);
+                            self.frames.pop();
+                        }
+                    }
+                }
+                Opcode::Invokestatic(member) | Opcode::Invokespecial(member) => {
+                    let kind = if matches!(opcode, Opcode::Invokestatic(_)) {
+                        InvokeKind::Static
+                    } else {
+                        InvokeKind::Special
+                    };
+                    let (name, descriptor) =
+                        (&member.name_and_type.name, &member.name_and_type.descriptor);
+
+                    if let Some((callee, method)) =
+                        self.enter_call(kind, &member.class_name, name, descriptor)
+                    {
+                        self.walk(callee, &Self::opcodes(method), f);
+                        self.leave_call();
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Interpret opcodes, returning the value of every `return` reached.
+    fn interpret(
+        &mut self,
+        class: &ClassFile<'_>,
+        opcodes: &[&Opcode<'_>],
+        interpreter: &mut Interpreter,
+        f: &mut impl FnMut(&Opcode<'_>, Option<&Effect>),
+    ) -> Vec<Option<Value>> {
+        let mut returns = Vec::new();
+
+        for opcode in opcodes {
+            let effect = interpreter.execute(opcode);
+            f(opcode, effect.as_ref());
+
+            match effect {
+                Some(Effect::Invoke(invocation)) => {
+                    if let Some((callee, method)) = self.enter_call(
+                        invocation.kind,
+                        &invocation.class,
+                        &invocation.name,
+                        &invocation.descriptor,
+                    ) {
+                        let mut frame = interpreter.frame(Interpreter::with_arguments(
+                            &invocation.descriptor,
+                            invocation.receiver.clone(),
+                            invocation.args.clone(),
+                        ));
+                        let values = self.interpret(callee, &Self::opcodes(method), &mut frame, f);
+                        interpreter.return_from(&frame, &invocation, &values);
+                        self.leave_call();
+                    }
+                }
+                Some(Effect::Dynamic(call)) => {
+                    for handle in class.class_bootstrap_methods(call.attr_index) {
+                        let member = &handle.member_ref;
+                        let Some((callee, method)) =
+                            self.enter(&handle.class_name, &member.name, &member.descriptor)
+                        else {
+                            continue;
+                        };
+
+                        // Lambda arguments are not known until the lambda is invoked.
+                        let mut frame = interpreter.frame(Interpreter::for_method(callee, method));
+                        self.interpret(callee, &Self::opcodes(method), &mut frame, f);
+                        interpreter.leave_frame(&frame);
+                        self.frames.pop();
+                    }
+                }
+                Some(Effect::Return(value)) => returns.push(value),
+                _ => {}
+            }
+        }
+
+        returns
+    }
+
+    /// Enter a call to a method, if it should be followed.
+    fn enter_call(
+        &mut self,
+        kind: InvokeKind,
+        class: &str,
+        name: &str,
+        descriptor: &str,
+    ) -> Option<(&'c ClassFile<'a>, &'c MethodInfo<'a>)> {
+        if !self.options.follows(kind, name) || self.depth >= self.options.max_depth {
+            return None;
+        }
+
+        let entered = self.enter(class, name, descriptor)?;
+        self.depth += 1;
+        Some(entered)
+    }
+
+    fn leave_call(&mut self) {
+        self.depth -= 1;
+        self.frames.pop();
+    }
+
+    /// Enter a method, if it is inside the [`Classes`]
+    /// and is not already being followed.
+    fn enter(
+        &mut self,
+        class: &str,
+        name: &str,
+        descriptor: &str,
+    ) -> Option<(&'c ClassFile<'a>, &'c MethodInfo<'a>)> {
+        let (class, method) = self.classes.resolve_method(class, name, Some(descriptor))?;
+        if !method.attributes.iter().any(|attr| matches!(attr.data, AttributeData::Code(_))) {
+            return None;
+        }
+
+        let frame = (class.this_class.to_string(), name.to_string(), descriptor.to_string());
+        if self.frames.contains(&frame) {
+            tracing::warn!("Breaking recursive call to `{}.{name}{descriptor}`", class.this_class);
+            return None;
+        }
+
+        self.followed.push(frame.clone());
+        self.frames.push(frame);
+        Some((class, method))
+    }
+
+    fn opcodes(method: &'c MethodInfo<'a>) -> Vec<&'c Opcode<'a>> {
+        method
+            .attributes
+            .iter()
+            .filter_map(|attr| match &attr.data {
+                AttributeData::Code(CodeData { bytecode: Some(bytecode), .. }) => Some(bytecode),
+                _ => None,
+            })
+            .flat_map(|bytecode| &bytecode.opcodes)
+            .map(|(_, opcode)| opcode)
+            .collect()
+    }
+}
+
+#[test]
+#[cfg(test)]
+fn inline() {
+    use cafebabe::MethodAccessFlags;
+    use froglight_dependency::dependency::minecraft::{
+        class_builder::ClassBuilder, minecraft_code::CodeBundle,
+    };
+
+    use super::{interpreter::Invocation, OwnedConstant};
+
+    const STRING: &str = "(Ljava/lang/String;)Ljava/lang/String;";
+
+    // `STONE = register("stone")`, where `register` returns `identity(id)`
+    let class = ClassBuilder::new("Blocks")
+        .method(MethodAccessFlags::STATIC, "<clinit>", "()V", |code| {
+            code.ldc_string("stone");
+            code.invokestatic("Blocks", "register", STRING);
+            code.putstatic("Blocks", "STONE", "Ljava/lang/String;");
+            code.return_();
+        })
+        .method(MethodAccessFlags::STATIC, "register", STRING, |code| {
+            code.aload(0);
+            code.invokestatic("Blocks", "identity", STRING);
+            code.areturn();
+        })
+        .method(MethodAccessFlags::STATIC, "identity", STRING, |code| {
+            code.aload(0);
+            code.areturn();
+        })
+        .method(MethodAccessFlags::STATIC, "loop", "()V", |code| {
+            code.invokestatic("Blocks", "loop", "()V");
+            code.return_();
+        })
+        .build();
+    let bundle = CodeBundle::from_classes([(String::from("Blocks"), class)]);
+    bundle.with_classes(|classes| {
+        let class = classes.get("Blocks").unwrap();
+        let method = |name: &str| {
+            Inliner::opcodes(class.methods.iter().find(|method| method.name == name).unwrap())
+        };
+
+        let stored = |options: InlineOptions| {
+            let mut stored = None;
+            interpret_code(
+                class,
+                &method("<clinit>"),
+                classes,
+                options,
+                &mut Interpreter::new(),
+                &mut |_, effect| {
+                    if let Some(Effect::PutStatic(_, value)) = effect {
+                        stored = Some(value.clone());
+                    }
+                },
+            );
+            stored.unwrap()
+        };
+        let invoke = |name: &str| {
+            Value::Invoke(Box::new(Invocation {
+                kind: InvokeKind::Static,
+                class: String::from("Blocks"),
+                name: name.to_string(),
+                descriptor: String::from(STRING),
+                receiver: None,
+                args: vec![Value::Constant(OwnedConstant::String(String::from("stone")))],
+            }))
+        };
+
+        // Bound arguments are returned through every followed call.
+        let stone = Value::Constant(OwnedConstant::String(String::from("stone")));
+        assert_eq!(stored(InlineOptions::default()), stone);
+
+        // Calls past the maximum depth are left as invocations,
+        // with the arguments bound by the calls that were followed.
+        assert_eq!(stored(InlineOptions::default().with_max_depth(1)), invoke("identity"));
+        assert_eq!(stored(InlineOptions::NONE), invoke("register"));
+
+        // Recursive calls are only followed once.
+        let mut count = 0;
+        iter_code(class, &method("loop"), classes, InlineOptions::default(), &mut |_| count += 1);
+        assert_eq!(count, 4);
+    });
+}
@@ -0,0 +1,969 @@
+//! A symbolic bytecode interpreter.
+//!
+//! Simulates the operand stack and local variables of a method
+//! using symbolic [`Value`]s, so extractors can read the arguments
+//! of any invocation without tracking constants by hand.
+//!
+//! Control flow is not followed, opcodes are interpreted in the order given.
+
+use std::collections::HashMap;
+
+use cafebabe::{
+    ClassFile, MethodAccessFlags, MethodInfo,
+    bytecode::{Opcode, PrimitiveArrayType},
+    constant_pool::{Loadable, MemberRef, ObjectArrayType},
+};
+
+use super::OwnedConstant;
+
+/// A symbolic value on the operand stack or in a local variable.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Value {
+    /// A literal constant.
+    Constant(OwnedConstant),
+    /// The `null` reference.
+    Null,
+    /// A class literal, such as `Blocks.class`.
+    Class(String),
+    /// An unbound method argument, by local variable index and descriptor.
+    Argument(u16, String),
+    /// The value of a static field.
+    Static(FieldRef),
+    /// The value of an instance field.
+    Field(Box<Value>, FieldRef),
+    /// A new object.
+    Object(Object),
+    /// A new array.
+    Array(Array),
+    /// The result of a method invocation.
+    Invoke(Box<Invocation>),
+    /// The result of an `invokedynamic`, such as a lambda or method reference.
+    Dynamic(Box<DynamicCall>),
+    /// The result of an operation that could not be folded into a constant.
+    Operation(&'static str, Vec<Value>),
+    /// A value that could not be determined.
+    Unknown,
+}
+
+impl Value {
+    /// Returns the value as an integer, if it is an integer constant.
+    pub(crate) fn as_int(&self) -> Option<i32> {
+        if let Value::Constant(OwnedConstant::Integer(value)) = self {
+            Some(*value)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the value as a float, if it is any numeric constant.
+    #[expect(clippy::cast_precision_loss)]
+    pub(crate) fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Constant(OwnedConstant::Integer(value)) => Some(f64::from(*value)),
+            Value::Constant(OwnedConstant::Float(value)) => Some(f64::from(*value)),
+            Value::Constant(OwnedConstant::Long(value)) => Some(*value as f64),
+            Value::Constant(OwnedConstant::Double(value)) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Returns the value as a string, if it is a string constant.
+    pub(crate) fn as_str(&self) -> Option<&str> {
+        if let Value::Constant(OwnedConstant::String(value)) = self {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    /// Returns `true` if the value takes up two stack slots.
+    fn is_wide(&self) -> bool {
+        match self {
+            Value::Constant(constant) => {
+                matches!(constant, OwnedConstant::Long(_) | OwnedConstant::Double(_))
+            }
+            Value::Argument(_, descriptor) => is_wide_descriptor(descriptor),
+            Value::Static(field) | Value::Field(_, field) => is_wide_descriptor(&field.descriptor),
+            Value::Invoke(invoke) => method_descriptor(&invoke.descriptor)
+                .is_some_and(|(_, ret)| is_wide_descriptor(ret)),
+            Value::Operation(op, _) => WIDE_OPERATIONS.contains(op),
+            _ => false,
+        }
+    }
+
+    /// The id of the object or array, if any.
+    fn id(&self) -> Option<usize> {
+        match self {
+            Value::Object(object) => Some(object.id),
+            Value::Array(array) => Some(array.id),
+            _ => None,
+        }
+    }
+}
+
+/// Operations that produce a `long` or `double`.
+const WIDE_OPERATIONS: &[&str] = &[
+    "ladd", "lsub", "lmul", "ldiv", "lrem", "lneg", "land", "lor", "lxor", "lshl", "lshr", "lushr",
+    "dadd", "dsub", "dmul", "ddiv", "drem", "dneg", "i2l", "i2d", "f2l", "f2d", "l2d", "d2l",
+    "laload", "daload",
+];
+
+/// A reference to a field.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct FieldRef {
+    pub(crate) class: String,
+    pub(crate) name: String,
+    pub(crate) descriptor: String,
+}
+
+impl From<&MemberRef<'_>> for FieldRef {
+    fn from(member: &MemberRef<'_>) -> Self {
+        Self {
+            class: member.class_name.to_string(),
+            name: member.name_and_type.name.to_string(),
+            descriptor: member.name_and_type.descriptor.to_string(),
+        }
+    }
+}
+
+/// A new object.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Object {
+    /// A unique id, used to track the object across copies.
+    pub(crate) id: usize,
+    /// The class of the object.
+    pub(crate) class: String,
+    /// The constructor descriptor and arguments,
+    /// or `None` if the constructor has not been called yet.
+    pub(crate) constructor: Option<(String, Vec<Value>)>,
+}
+
+/// A new array.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Array {
+    /// A unique id, used to track the array across copies.
+    pub(crate) id: usize,
+    /// The type of the array elements.
+    pub(crate) component: String,
+    /// The length of the array.
+    pub(crate) length: Box<Value>,
+    /// The elements stored at constant indices.
+    pub(crate) elements: Vec<Value>,
+}
+
+/// The kind of a method invocation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum InvokeKind {
+    Virtual,
+    Special,
+    Static,
+    Interface,
+}
+
+/// A method invocation.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Invocation {
+    pub(crate) kind: InvokeKind,
+    pub(crate) class: String,
+    pub(crate) name: String,
+    pub(crate) descriptor: String,
+    /// The object the method was invoked on, if not static.
+    pub(crate) receiver: Option<Value>,
+    pub(crate) args: Vec<Value>,
+}
+
+/// An `invokedynamic` call.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct DynamicCall {
+    /// The index of the bootstrap method in the class.
+    pub(crate) attr_index: u16,
+    pub(crate) name: String,
+    pub(crate) descriptor: String,
+    pub(crate) args: Vec<Value>,
+}
+
+/// A side effect of interpreting an [`Opcode`].
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Effect {
+    /// A method was invoked.
+    Invoke(Invocation),
+    /// An `invokedynamic` call was made.
+    Dynamic(DynamicCall),
+    /// A static field was set.
+    PutStatic(FieldRef, Value),
+    /// An instance field was set.
+    PutField(Value, FieldRef, Value),
+    /// The method returned, with a value if not `void`.
+    Return(Option<Value>),
+}
+
+/// A symbolic bytecode interpreter.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct Interpreter {
+    pub(crate) stack: Vec<Value>,
+    pub(crate) locals: HashMap<u16, Value>,
+    next_id: usize,
+}
+
+impl Interpreter {
+    /// Create an [`Interpreter`] with no arguments bound.
+    pub(crate) fn new() -> Self { Self::default() }
+
+    /// Create an [`Interpreter`] for a method of a class,
+    /// with each argument as a [`Value::Argument`].
+    pub(crate) fn for_method(class: &ClassFile<'_>, method: &MethodInfo<'_>) -> Self {
+        let descriptor = method.descriptor.to_string();
+        let is_static = method.access_flags.contains(MethodAccessFlags::STATIC);
+
+        let mut interpreter = Self::new();
+        let mut slot = 0;
+        if !is_static {
+            let receiver = format!("L{};", class.this_class);
+            interpreter.locals.insert(0, Value::Argument(0, receiver));
+            slot = 1;
+        }
+        for arg in method_descriptor(&descriptor).map(|(args, _)| args).unwrap_or_default() {
+            interpreter.locals.insert(slot, Value::Argument(slot, arg.to_string()));
+            slot += if is_wide_descriptor(arg) { 2 } else { 1 };
+        }
+
+        interpreter
+    }
+
+    /// Create an [`Interpreter`] for a method with the given arguments bound.
+    ///
+    /// The receiver is bound to local `0` if present,
+    /// and `long` and `double` arguments take two local slots.
+    pub(crate) fn with_arguments(
+        descriptor: &str,
+        receiver: Option<Value>,
+        args: impl IntoIterator<Item = Value>,
+    ) -> Self {
+        let mut interpreter = Self::new();
+        let mut slot = 0;
+        if let Some(receiver) = receiver {
+            interpreter.locals.insert(0, receiver);
+            slot = 1;
+        }
+
+        let types = method_descriptor(descriptor).map(|(args, _)| args).unwrap_or_default();
+        for (arg, ty) in args.into_iter().zip(types) {
+            interpreter.locals.insert(slot, arg);
+            slot += if is_wide_descriptor(ty) { 2 } else { 1 };
+        }
+
+        interpreter
+    }
+
+    /// Prepare an [`Interpreter`] for a called method,
+    /// continuing object ids so objects from both can be told apart.
+    pub(crate) fn frame(&self, mut callee: Self) -> Self {
+        callee.next_id = self.next_id;
+        callee
+    }
+
+    /// Leave a frame created by [`Interpreter::frame`].
+    pub(crate) fn leave_frame(&mut self, callee: &Self) { self.next_id = callee.next_id; }
+
+    /// Return from an invocation interpreted in a frame
+    /// created by [`Interpreter::frame`].
+    ///
+    /// If every `return` in the callee produced the same value,
+    /// it replaces the result of the invocation on the stack.
+    pub(crate) fn return_from(
+        &mut self,
+        callee: &Self,
+        invocation: &Invocation,
+        returns: &[Option<Value>],
+    ) {
+        self.leave_frame(callee);
+
+        if let [Some(value), rest @ ..] = returns
+            && rest.iter().all(|other| other.as_ref() == Some(value))
+            && let Some(Value::Invoke(result)) = self.stack.last()
+            && **result == *invocation
+        {
+            *self.stack.last_mut().unwrap() = value.clone();
+        }
+    }
+
+    /// Pop a value from the stack.
+    ///
+    /// Returns [`Value::Unknown`] if the stack is empty.
+    pub(crate) fn pop(&mut self) -> Value { self.stack.pop().unwrap_or(Value::Unknown) }
+
+    /// Pop `count` values from the stack, in the order they were pushed.
+    pub(crate) fn pop_n(&mut self, count: usize) -> Vec<Value> {
+        let mut values: Vec<Value> = (0..count).map(|_| self.pop()).collect();
+        values.reverse();
+        values
+    }
+
+    fn push(&mut self, value: Value) { self.stack.push(value); }
+
+    fn load(&self, index: u16) -> Value {
+        self.locals.get(&index).cloned().unwrap_or(Value::Unknown)
+    }
+
+    fn next_id(&mut self) -> usize {
+        self.next_id += 1;
+        self.next_id
+    }
+
+    /// Apply a function to every copy of an object or array.
+    fn update(&mut self, id: usize, f: impl Fn(&mut Value)) {
+        for value in self.stack.iter_mut().chain(self.locals.values_mut()) {
+            if value.id() == Some(id) {
+                f(value);
+            }
+        }
+    }
+
+    /// Interpret a single [`Opcode`].
+    ///
+    /// Returns any side effects of the opcode.
+    #[expect(clippy::too_many_lines, clippy::match_same_arms)]
+    pub(crate) fn execute(&mut self, opcode: &Opcode<'_>) -> Option<Effect> {
+        match opcode {
+            // Constants
+            Opcode::AconstNull => self.push(Value::Null),
+            Opcode::IconstM1 => self.push(int(-1)),
+            Opcode::Iconst0 => self.push(int(0)),
+            Opcode::Iconst1 => self.push(int(1)),
+            Opcode::Iconst2 => self.push(int(2)),
+            Opcode::Iconst3 => self.push(int(3)),
+            Opcode::Iconst4 => self.push(int(4)),
+            Opcode::Iconst5 => self.push(int(5)),
+            Opcode::Lconst0 => self.push(Value::Constant(OwnedConstant::Long(0))),
+            Opcode::Lconst1 => self.push(Value::Constant(OwnedConstant::Long(1))),
+            Opcode::Fconst0 => self.push(Value::Constant(OwnedConstant::Float(0.0))),
+            Opcode::Fconst1 => self.push(Value::Constant(OwnedConstant::Float(1.0))),
+            Opcode::Fconst2 => self.push(Value::Constant(OwnedConstant::Float(2.0))),
+            Opcode::Dconst0 => self.push(Value::Constant(OwnedConstant::Double(0.0))),
+            Opcode::Dconst1 => self.push(Value::Constant(OwnedConstant::Double(1.0))),
+            Opcode::Bipush(value) => self.push(int(i32::from(*value))),
+            Opcode::Sipush(value) => self.push(int(i32::from(*value))),
+            Opcode::Ldc(loadable) | Opcode::LdcW(loadable) | Opcode::Ldc2W(loadable) => {
+                self.push(match loadable {
+                    Loadable::LiteralConstant(constant) => Value::Constant(constant.into()),
+                    Loadable::ClassInfo(class) => Value::Class(class.to_string()),
+                    _ => Value::Unknown,
+                });
+            }
+
+            // Locals
+            Opcode::Aload(index)
+            | Opcode::Iload(index)
+            | Opcode::Lload(index)
+            | Opcode::Fload(index)
+            | Opcode::Dload(index) => self.push(self.load(*index)),
+            Opcode::Astore(index)
+            | Opcode::Istore(index)
+            | Opcode::Lstore(index)
+            | Opcode::Fstore(index)
+            | Opcode::Dstore(index) => {
+                let value = self.pop();
+                self.locals.insert(*index, value);
+            }
+            Opcode::Iinc(index, delta) => {
+                let value = match self.load(*index).as_int() {
+                    Some(value) => int(value.wrapping_add(i32::from(*delta))),
+                    None => {
+                        Value::Operation("iinc", vec![self.load(*index), int(i32::from(*delta))])
+                    }
+                };
+                self.locals.insert(*index, value);
+            }
+
+            // Stack manipulation
+            Opcode::Pop => {
+                self.pop();
+            }
+            Opcode::Pop2 => {
+                if !self.pop().is_wide() {
+                    self.pop();
+                }
+            }
+            Opcode::Dup => {
+                let value = self.pop();
+                self.push(value.clone());
+                self.push(value);
+            }
+            Opcode::DupX1 => {
+                let [v2, v1] = self.pop_array();
+                self.extend([v1.clone(), v2, v1]);
+            }
+            Opcode::DupX2 => {
+                let v1 = self.pop();
+                if self.stack.last().is_some_and(Value::is_wide) {
+                    let v2 = self.pop();
+                    self.extend([v1.clone(), v2, v1]);
+                } else {
+                    let [v3, v2] = self.pop_array();
+                    self.extend([v1.clone(), v3, v2, v1]);
+                }
+            }
+            Opcode::Dup2 => {
+                if self.stack.last().is_some_and(Value::is_wide) {
+                    let v1 = self.pop();
+                    self.extend([v1.clone(), v1]);
+                } else {
+                    let [v2, v1] = self.pop_array();
+                    self.extend([v2.clone(), v1.clone(), v2, v1]);
+                }
+            }
+            Opcode::Dup2X1 => {
+                if self.stack.last().is_some_and(Value::is_wide) {
+                    let [v2, v1] = self.pop_array();
+                    self.extend([v1.clone(), v2, v1]);
+                } else {
+                    let [v3, v2, v1] = self.pop_array();
+                    self.extend([v2.clone(), v1.clone(), v3, v2, v1]);
+                }
+            }
+            Opcode::Dup2X2 => {
+                let v1 = self.pop();
+                if v1.is_wide() {
+                    if self.stack.last().is_some_and(Value::is_wide) {
+                        let v2 = self.pop();
+                        self.extend([v1.clone(), v2, v1]);
+                    } else {
+                        let [v3, v2] = self.pop_array();
+                        self.extend([v1.clone(), v3, v2, v1]);
+                    }
+                } else {
+                    let v2 = self.pop();
+                    if self.stack.last().is_some_and(Value::is_wide) {
+                        let v3 = self.pop();
+                        self.extend([v2.clone(), v1.clone(), v3, v2, v1]);
+                    } else {
+                        let [v4, v3] = self.pop_array();
+                        self.extend([v2.clone(), v1.clone(), v4, v3, v2, v1]);
+                    }
+                }
+            }
+            Opcode::Swap => {
+                let [v2, v1] = self.pop_array();
+                self.extend([v1, v2]);
+            }
+
+            // Arithmetic
+            Opcode::Iadd => self.binary("iadd", |a, b| fold_int(a, b, i32::wrapping_add)),
+            Opcode::Isub => self.binary("isub", |a, b| fold_int(a, b, i32::wrapping_sub)),
+            Opcode::Imul => self.binary("imul", |a, b| fold_int(a, b, i32::wrapping_mul)),
+            Opcode::Idiv => self.binary("idiv", |a, b| fold_int(a, b, i32::checked_div)),
+            Opcode::Irem => self.binary("irem", |a, b| fold_int(a, b, i32::checked_rem)),
+            Opcode::Iand => self.binary("iand", |a, b| fold_int(a, b, |a, b| a & b)),
+            Opcode::Ior => self.binary("ior", |a, b| fold_int(a, b, |a, b| a | b)),
+            Opcode::Ixor => self.binary("ixor", |a, b| fold_int(a, b, |a, b| a ^ b)),
+            #[expect(clippy::cast_sign_loss)]
+            Opcode::Ishl => {
+                self.binary("ishl", |a, b| fold_int(a, b, |a, b| a.wrapping_shl(b as u32)));
+            }
+            #[expect(clippy::cast_sign_loss)]
+            Opcode::Ishr => {
+                self.binary("ishr", |a, b| fold_int(a, b, |a, b| a.wrapping_shr(b as u32)));
+            }
+            #[expect(clippy::cast_sign_loss, clippy::cast_possible_wrap)]
+            Opcode::Iushr => self.binary("iushr", |a, b| {
+                fold_int(a, b, |a, b| (a as u32).wrapping_shr(b as u32) as i32)
+            }),
+            Opcode::Ineg => self.unary("ineg", |a| a.as_int().map(|a| int(a.wrapping_neg()))),
+            Opcode::Fadd => self.binary("fadd", |a, b| fold_float(a, b, |a, b| a + b)),
+            Opcode::Fsub => self.binary("fsub", |a, b| fold_float(a, b, |a, b| a - b)),
+            Opcode::Fmul => self.binary("fmul", |a, b| fold_float(a, b, |a, b| a * b)),
+            Opcode::Fdiv => self.binary("fdiv", |a, b| fold_float(a, b, |a, b| a / b)),
+            Opcode::Frem => self.binary("frem", |a, b| fold_float(a, b, |a, b| a % b)),
+            Opcode::Fneg => self.unary("fneg", |a| match a {
+                Value::Constant(OwnedConstant::Float(a)) => Some(float(-a)),
+                _ => None,
+            }),
+            Opcode::Dadd => self.binary("dadd", |a, b| fold_double(a, b, |a, b| a + b)),
+            Opcode::Dsub => self.binary("dsub", |a, b| fold_double(a, b, |a, b| a - b)),
+            Opcode::Dmul => self.binary("dmul", |a, b| fold_double(a, b, |a, b| a * b)),
+            Opcode::Ddiv => self.binary("ddiv", |a, b| fold_double(a, b, |a, b| a / b)),
+            Opcode::Drem => self.binary("drem", |a, b| fold_double(a, b, |a, b| a % b)),
+            Opcode::Dneg => self.unary("dneg", |a| match a {
+                Value::Constant(OwnedConstant::Double(a)) => Some(double(-a)),
+                _ => None,
+            }),
+            Opcode::Ladd => self.binary("ladd", |a, b| fold_long(a, b, i64::wrapping_add)),
+            Opcode::Lsub => self.binary("lsub", |a, b| fold_long(a, b, i64::wrapping_sub)),
+            Opcode::Lmul => self.binary("lmul", |a, b| fold_long(a, b, i64::wrapping_mul)),
+            Opcode::Ldiv => self.binary("ldiv", |a, b| fold_long(a, b, i64::checked_div)),
+            Opcode::Lrem => self.binary("lrem", |a, b| fold_long(a, b, i64::checked_rem)),
+            Opcode::Land => self.binary("land", |a, b| fold_long(a, b, |a, b| a & b)),
+            Opcode::Lor => self.binary("lor", |a, b| fold_long(a, b, |a, b| a | b)),
+            Opcode::Lxor => self.binary("lxor", |a, b| fold_long(a, b, |a, b| a ^ b)),
+            Opcode::Lshl => self.binary("lshl", |_, _| None),
+            Opcode::Lshr => self.binary("lshr", |_, _| None),
+            Opcode::Lushr => self.binary("lushr", |_, _| None),
+            Opcode::Lneg => self.unary("lneg", |a| match a {
+                Value::Constant(OwnedConstant::Long(a)) => {
+                    Some(Value::Constant(OwnedConstant::Long(a.wrapping_neg())))
+                }
+                _ => None,
+            }),
+            Opcode::Lcmp => self.binary("lcmp", |_, _| None),
+            Opcode::Fcmpl => self.binary("fcmpl", |_, _| None),
+            Opcode::Fcmpg => self.binary("fcmpg", |_, _| None),
+            Opcode::Dcmpl => self.binary("dcmpl", |_, _| None),
+            Opcode::Dcmpg => self.binary("dcmpg", |_, _| None),
+
+            // Conversions
+            #[expect(clippy::cast_precision_loss)]
+            Opcode::I2f => self.unary("i2f", |a| a.as_int().map(|a| float(a as f32))),
+            Opcode::I2d => self.unary("i2d", |a| a.as_int().map(|a| double(f64::from(a)))),
+            Opcode::I2l => self.unary("i2l", |a| {
+                a.as_int().map(|a| Value::Constant(OwnedConstant::Long(i64::from(a))))
+            }),
+            #[expect(clippy::cast_possible_truncation)]
+            Opcode::I2b => self.unary("i2b", |a| a.as_int().map(|a| int(i32::from(a as i8)))),
+            #[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            Opcode::I2c => self.unary("i2c", |a| a.as_int().map(|a| int(i32::from(a as u16)))),
+            #[expect(clippy::cast_possible_truncation)]
+            Opcode::I2s => self.unary("i2s", |a| a.as_int().map(|a| int(i32::from(a as i16)))),
+            Opcode::F2d => self.unary("f2d", |a| match a {
+                Value::Constant(OwnedConstant::Float(a)) => Some(double(f64::from(*a))),
+                _ => None,
+            }),
+            #[expect(clippy::cast_possible_truncation)]
+            Opcode::D2f => self.unary("d2f", |a| match a {
+                Value::Constant(OwnedConstant::Double(a)) => Some(float(*a as f32)),
+                _ => None,
+            }),
+            Opcode::F2i => self.unary("f2i", |_| None),
+            Opcode::F2l => self.unary("f2l", |_| None),
+            Opcode::D2i => self.unary("d2i", |_| None),
+            Opcode::D2l => self.unary("d2l", |_| None),
+            #[expect(clippy::cast_precision_loss)]
+            Opcode::L2d => self.unary("l2d", |a| match a {
+                Value::Constant(OwnedConstant::Long(a)) => Some(double(*a as f64)),
+                _ => None,
+            }),
+            #[expect(clippy::cast_precision_loss)]
+            Opcode::L2f => self.unary("l2f", |a| match a {
+                Value::Constant(OwnedConstant::Long(a)) => Some(float(*a as f32)),
+                _ => None,
+            }),
+            #[expect(clippy::cast_possible_truncation)]
+            Opcode::L2i => self.unary("l2i", |a| match a {
+                Value::Constant(OwnedConstant::Long(a)) => Some(int(*a as i32)),
+                _ => None,
+            }),
+            Opcode::Checkcast(_) => {}
+            Opcode::Instanceof(_) => self.unary("instanceof", |_| None),
+
+            // Objects and arrays
+            Opcode::New(class) => {
+                let id = self.next_id();
+                self.push(Value::Object(Object {
+                    id,
+                    class: class.to_string(),
+                    constructor: None,
+                }));
+            }
+            Opcode::Newarray(ty) => {
+                let component = primitive_name(*ty).to_string();
+                self.new_array(component);
+            }
+            Opcode::Anewarray(ty) => self.new_array(object_array_name(ty)),
+            Opcode::Multianewarray(ty, dimensions) => {
+                let lengths = self.pop_n(usize::from(*dimensions));
+                let id = self.next_id();
+                self.push(Value::Array(Array {
+                    id,
+                    component: object_array_name(ty),
+                    length: Box::new(lengths.into_iter().next().unwrap_or(Value::Unknown)),
+                    elements: Vec::new(),
+                }));
+            }
+            Opcode::Arraylength => self.unary("arraylength", |a| match a {
+                Value::Array(array) => Some((*array.length).clone()),
+                _ => None,
+            }),
+            Opcode::Aaload
+            | Opcode::Baload
+            | Opcode::Caload
+            | Opcode::Saload
+            | Opcode::Iaload
+            | Opcode::Laload
+            | Opcode::Faload
+            | Opcode::Daload => {
+                let [array, index] = self.pop_array();
+                let element = match (&array, index.as_int()) {
+                    (Value::Array(array), Some(index)) => usize::try_from(index)
+                        .ok()
+                        .and_then(|index| array.elements.get(index))
+                        .cloned(),
+                    _ => None,
+                };
+                self.push(element.unwrap_or_else(|| {
+                    Value::Operation(array_load_name(opcode), vec![array, index])
+                }));
+            }
+            Opcode::Aastore
+            | Opcode::Bastore
+            | Opcode::Castore
+            | Opcode::Sastore
+            | Opcode::Iastore
+            | Opcode::Lastore
+            | Opcode::Fastore
+            | Opcode::Dastore => {
+                let [array, index, value] = self.pop_array();
+                if let (Some(id), Some(Ok(index))) =
+                    (array.id(), index.as_int().map(usize::try_from))
+                {
+                    self.update(id, |array| {
+                        if let Value::Array(array) = array {
+                            if array.elements.len() <= index {
+                                array.elements.resize(index + 1, Value::Unknown);
+                            }
+                            array.elements[index] = value.clone();
+                        }
+                    });
+                }
+            }
+
+            // Fields
+            Opcode::Getstatic(member) => self.push(Value::Static(member.into())),
+            Opcode::Getfield(member) => {
+                let object = self.pop();
+                self.push(Value::Field(Box::new(object), member.into()));
+            }
+            Opcode::Putstatic(member) => {
+                let value = self.pop();
+                return Some(Effect::PutStatic(member.into(), value));
+            }
+            Opcode::Putfield(member) => {
+                let [object, value] = self.pop_array();
+                return Some(Effect::PutField(object, member.into(), value));
+            }
+
+            // Invocations
+            Opcode::Invokevirtual(member) => return Some(self.invoke(InvokeKind::Virtual, member)),
+            Opcode::Invokespecial(member) => return Some(self.invoke(InvokeKind::Special, member)),
+            Opcode::Invokestatic(member) => return Some(self.invoke(InvokeKind::Static, member)),
+            Opcode::Invokeinterface(member, _) => {
+                return Some(self.invoke(InvokeKind::Interface, member));
+            }
+            Opcode::Invokedynamic(dynamic) => {
+                let descriptor = dynamic.name_and_type.descriptor.to_string();
+                let (args, ret) = method_descriptor(&descriptor).unwrap_or_default();
+                let call = DynamicCall {
+                    attr_index: dynamic.attr_index,
+                    name: dynamic.name_and_type.name.to_string(),
+                    args: self.pop_n(args.len()),
+                    descriptor: descriptor.clone(),
+                };
+                if ret != "V" {
+                    self.push(Value::Dynamic(Box::new(call.clone())));
+                }
+                return Some(Effect::Dynamic(call));
+            }
+
+            // Control flow
+            Opcode::Ifeq(_)
+            | Opcode::Ifne(_)
+            | Opcode::Iflt(_)
+            | Opcode::Ifge(_)
+            | Opcode::Ifgt(_)
+            | Opcode::Ifle(_)
+            | Opcode::Ifnull(_)
+            | Opcode::Ifnonnull(_)
+            | Opcode::Tableswitch(_)
+            | Opcode::Lookupswitch(_)
+            | Opcode::Monitorenter
+            | Opcode::Monitorexit
+            | Opcode::Athrow => {
+                self.pop();
+            }
+            Opcode::IfIcmpeq(_)
+            | Opcode::IfIcmpne(_)
+            | Opcode::IfIcmplt(_)
+            | Opcode::IfIcmpge(_)
+            | Opcode::IfIcmpgt(_)
+            | Opcode::IfIcmple(_)
+            | Opcode::IfAcmpeq(_)
+            | Opcode::IfAcmpne(_) => {
+                self.pop_n(2);
+            }
+            Opcode::Jsr(_) => self.push(Value::Unknown),
+            Opcode::Ireturn
+            | Opcode::Lreturn
+            | Opcode::Freturn
+            | Opcode::Dreturn
+            | Opcode::Areturn => {
+                let value = self.pop();
+                return Some(Effect::Return(Some(value)));
+            }
+            Opcode::Return => return Some(Effect::Return(None)),
+            Opcode::Goto(_)
+            | Opcode::Ret(_)
+            | Opcode::Nop
+            | Opcode::Breakpoint
+            | Opcode::Impdep1
+            | Opcode::Impdep2 => {}
+        }
+
+        None
+    }
+
+    fn invoke(&mut self, kind: InvokeKind, member: &MemberRef<'_>) -> Effect {
+        let descriptor = member.name_and_type.descriptor.to_string();
+        let (args, ret) = method_descriptor(&descriptor).unwrap_or_default();
+
+        let args = self.pop_n(args.len());
+        let receiver = (kind != InvokeKind::Static).then(|| self.pop());
+
+        // Mark objects as constructed
+        if let (
+            InvokeKind::Special,
+            "<init>",
+            Some(Value::Object(Object { id, constructor: None, .. })),
+        ) = (kind, &*member.name_and_type.name, &receiver)
+        {
+            let constructor = (descriptor.clone(), args.clone());
+            self.update(*id, |object| {
+                if let Value::Object(object) = object {
+                    object.constructor = Some(constructor.clone());
+                }
+            });
+        }
+
+        let invocation = Invocation {
+            kind,
+            class: member.class_name.to_string(),
+            name: member.name_and_type.name.to_string(),
+            descriptor: descriptor.clone(),
+            receiver,
+            args,
+        };
+        if ret != "V" {
+            self.push(Value::Invoke(Box::new(invocation.clone())));
+        }
+
+        Effect::Invoke(invocation)
+    }
+
+    fn new_array(&mut self, component: String) {
+        let length = self.pop();
+        let id = self.next_id();
+        self.push(Value::Array(Array {
+            id,
+            component,
+            length: Box::new(length),
+            elements: Vec::new(),
+        }));
+    }
+
+    fn pop_array<const N: usize>(&mut self) -> [Value; N] {
+        let mut values = core::array::from_fn(|_| Value::Unknown);
+        for value in values.iter_mut().rev() {
+            *value = self.pop();
+        }
+        values
+    }
+
+    fn extend<const N: usize>(&mut self, values: [Value; N]) { self.stack.extend(values); }
+
+    fn unary(&mut self, name: &'static str, f: impl FnOnce(&Value) -> Option<Value>) {
+        let a = self.pop();
+        self.push(f(&a).unwrap_or_else(|| Value::Operation(name, vec![a])));
+    }
+
+    fn binary(&mut self, name: &'static str, f: impl FnOnce(&Value, &Value) -> Option<Value>) {
+        let [a, b] = self.pop_array();
+        self.push(f(&a, &b).unwrap_or_else(|| Value::Operation(name, vec![a, b])));
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+fn int(value: i32) -> Value { Value::Constant(OwnedConstant::Integer(value)) }
+fn float(value: f32) -> Value { Value::Constant(OwnedConstant::Float(value)) }
+fn double(value: f64) -> Value { Value::Constant(OwnedConstant::Double(value)) }
+
+fn fold_int<R: Into<Option<i32>>>(
+    a: &Value,
+    b: &Value,
+    f: impl FnOnce(i32, i32) -> R,
+) -> Option<Value> {
+    f(a.as_int()?, b.as_int()?).into().map(int)
+}
+
+fn fold_long<R: Into<Option<i64>>>(
+    a: &Value,
+    b: &Value,
+    f: impl FnOnce(i64, i64) -> R,
+) -> Option<Value> {
+    match (a, b) {
+        (Value::Constant(OwnedConstant::Long(a)), Value::Constant(OwnedConstant::Long(b))) => {
+            f(*a, *b).into().map(|v| Value::Constant(OwnedConstant::Long(v)))
+        }
+        _ => None,
+    }
+}
+
+fn fold_float(a: &Value, b: &Value, f: impl FnOnce(f32, f32) -> f32) -> Option<Value> {
+    match (a, b) {
+        (Value::Constant(OwnedConstant::Float(a)), Value::Constant(OwnedConstant::Float(b))) => {
+            Some(float(f(*a, *b)))
+        }
+        _ => None,
+    }
+}
+
+fn fold_double(a: &Value, b: &Value, f: impl FnOnce(f64, f64) -> f64) -> Option<Value> {
+    match (a, b) {
+        (Value::Constant(OwnedConstant::Double(a)), Value::Constant(OwnedConstant::Double(b))) => {
+            Some(double(f(*a, *b)))
+        }
+        _ => None,
+    }
+}
+
+fn primitive_name(ty: PrimitiveArrayType) -> &'static str {
+    match ty {
+        PrimitiveArrayType::Boolean => "Z",
+        PrimitiveArrayType::Char => "C",
+        PrimitiveArrayType::Float => "F",
+        PrimitiveArrayType::Double => "D",
+        PrimitiveArrayType::Byte => "B",
+        PrimitiveArrayType::Short => "S",
+        PrimitiveArrayType::Int => "I",
+        PrimitiveArrayType::Long => "J",
+    }
+}
+
+fn object_array_name(ty: &ObjectArrayType<'_>) -> String {
+    match ty {
+        ObjectArrayType::ArrayType(descriptor) => descriptor.to_string(),
+        ObjectArrayType::BinaryName(name) => format!("L{name};"),
+    }
+}
+
+fn array_load_name(opcode: &Opcode<'_>) -> &'static str {
+    match opcode {
+        Opcode::Baload => "baload",
+        Opcode::Caload => "caload",
+        Opcode::Saload => "saload",
+        Opcode::Iaload => "iaload",
+        Opcode::Laload => "laload",
+        Opcode::Faload => "faload",
+        Opcode::Daload => "daload",
+        _ => "aaload",
+    }
+}
+
+/// Returns `true` if a field descriptor is a `long` or `double`.
+fn is_wide_descriptor(descriptor: &str) -> bool { descriptor == "J" || descriptor == "D" }
+
+/// Split a method descriptor into its argument and return types.
+///
+/// For example, `(ILjava/lang/String;[J)V` becomes
+/// `(["I", "Ljava/lang/String;", "[J"], "V")`.
+pub(crate) fn method_descriptor(descriptor: &str) -> Option<(Vec<&str>, &str)> {
+    let (params, ret) = descriptor.strip_prefix('(')?.split_once(')')?;
+
+    let mut types = Vec::new();
+    let mut remaining = params;
+    while !remaining.is_empty() {
+        let dims = remaining.len() - remaining.trim_start_matches('[').len();
+        let len = match remaining[dims..].chars().next()? {
+            'L' => remaining[dims..].find(';')? + 1,
+            _ => 1,
+        };
+        types.push(&remaining[..dims + len]);
+        remaining = &remaining[dims + len..];
+    }
+
+    Some((types, ret))
+}
+
+#[test]
+#[cfg(test)]
+fn interpret() {
+    use std::borrow::Cow;
+
+    use cafebabe::constant_pool::{LiteralConstant, NameAndType};
+    use froglight_dependency::dependency::minecraft::class_builder::ClassBuilder;
+
+    assert_eq!(
+        method_descriptor("(ILjava/lang/String;[JD)V"),
+        Some((vec!["I", "Ljava/lang/String;", "[J", "D"], "V"))
+    );
+
+    let member = |class: &'static str, name: &'static str, descriptor: &'static str| MemberRef {
+        class_name: Cow::Borrowed(class),
+        name_and_type: NameAndType {
+            name: Cow::Borrowed(name),
+            descriptor: Cow::Borrowed(descriptor),
+        },
+    };
+
+    // `SIZE = new Size(16 * 2, 0.5f)`
+    let mut interpreter = Interpreter::new();
+    let mut effects = Vec::new();
+    for opcode in [
+        Opcode::New(Cow::Borrowed("Size")),
+        Opcode::Dup,
+        Opcode::Bipush(16),
+        Opcode::Iconst2,
+        Opcode::Imul,
+        Opcode::Ldc(Loadable::LiteralConstant(LiteralConstant::Float(0.5))),
+        Opcode::Invokespecial(member("Size", "<init>", "(IF)V")),
+        Opcode::Putstatic(member("Sizes", "SIZE", "LSize;")),
+    ] {
+        effects.extend(interpreter.execute(&opcode));
+    }
+
+    assert!(interpreter.stack.is_empty());
+    let Some(Effect::PutStatic(field, Value::Object(object))) = effects.last() else {
+        panic!("Expected a static field to be set, got: {effects:?}");
+    };
+    assert_eq!(field.name, "SIZE");
+    assert_eq!(object.constructor, Some((String::from("(IF)V"), vec![int(32), float(0.5)])));
+
+    // `STONE = register("stone")`, where `register` returns its argument
+    let register = member("Blocks", "register", "(Ljava/lang/String;)Ljava/lang/String;");
+    let mut interpreter = Interpreter::new();
+    interpreter.execute(&Opcode::Ldc(Loadable::LiteralConstant(LiteralConstant::String(
+        Cow::Borrowed("stone"),
+    ))));
+    let Some(Effect::Invoke(invocation)) = interpreter.execute(&Opcode::Invokestatic(register))
+    else {
+        panic!("Expected a method to be invoked");
+    };
+
+    let mut frame = interpreter.frame(Interpreter::with_arguments(
+        &invocation.descriptor,
+        None,
+        invocation.args.clone(),
+    ));
+    frame.execute(&Opcode::Aload(0));
+    let returned = frame.execute(&Opcode::Areturn);
+    let Some(Effect::Return(value)) = returned else { panic!("Expected a return") };
+
+    interpreter.return_from(&frame, &invocation, &[value]);
+    assert_eq!(interpreter.stack.last().and_then(Value::as_str), Some("stone"));
+
+    // `long` and `double` arguments take two stack slots
+    let class = ClassBuilder::new("Blocks")
+        .abstract_method(MethodAccessFlags::STATIC, "register", "(JLjava/lang/String;)V")
+        .build();
+    let class = cafebabe::parse_class(&class).unwrap();
+    let mut interpreter = Interpreter::for_method(&class, &class.methods[0]);
+    assert_eq!(
+        interpreter.locals.get(&2),
+        Some(&Value::Argument(2, String::from("Ljava/lang/String;")))
+    );
+
+    interpreter.execute(&Opcode::Lload(0));
+    interpreter.execute(&Opcode::Dup2);
+    let long = Value::Argument(0, String::from("J"));
+    assert_eq!(interpreter.stack, [long.clone(), long]);
+    interpreter.execute(&Opcode::Pop2);
+    interpreter.execute(&Opcode::Pop2);
+    assert!(interpreter.stack.is_empty());
+}
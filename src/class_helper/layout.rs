@@ -0,0 +1,286 @@
+//! The field layout of classes and records.
+//!
+//! Fields are read in declaration order, using their generic `Signature`
+//! when present so `List<ItemStack>` is not reduced to `List`.
+
+use cafebabe::{
+    ClassFile, FieldAccessFlags,
+    attributes::{AttributeData, AttributeInfo},
+};
+use convert_case::{Case, Casing};
+
+/// The instance fields of a class, in declaration order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ClassLayout {
+    /// The class, such as `net/minecraft/util/math/BlockPos`.
+    pub(crate) class: String,
+    /// Whether the class is a `record`.
+    ///
+    /// Fields of records are taken from the record components.
+    pub(crate) record: bool,
+    /// The fields of the class.
+    pub(crate) fields: Vec<FieldLayout>,
+}
+
+/// A field of a [`ClassLayout`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct FieldLayout {
+    /// The name of the field.
+    pub(crate) name: String,
+    /// The type of the field, including generic arguments if known.
+    pub(crate) ty: JavaType,
+}
+
+impl FieldLayout {
+    /// The name of the field in `snake_case`.
+    pub(crate) fn rust_name(&self) -> String { self.name.to_case(Case::Snake) }
+}
+
+impl ClassLayout {
+    /// Read the layout of a class.
+    pub(crate) fn new(class: &ClassFile<'_>) -> Self {
+        let components = class.attributes.iter().find_map(|attr| match &attr.data {
+            AttributeData::Record(components) => Some(components),
+            _ => None,
+        });
+
+        let fields = if let Some(components) = components {
+            components
+                .iter()
+                .map(|component| {
+                    FieldLayout::new(&component.name, &component.descriptor, &component.attributes)
+                })
+                .collect()
+        } else {
+            class
+                .fields
+                .iter()
+                .filter(|field| !field.access_flags.contains(FieldAccessFlags::STATIC))
+                .map(|field| FieldLayout::new(&field.name, &field.descriptor, &field.attributes))
+                .collect()
+        };
+
+        Self { class: class.this_class.to_string(), record: components.is_some(), fields }
+    }
+}
+
+impl FieldLayout {
+    fn new(name: &str, descriptor: &impl ToString, attributes: &[AttributeInfo<'_>]) -> Self {
+        let descriptor = descriptor.to_string();
+        let signature = attributes.iter().find_map(|attr| match &attr.data {
+            AttributeData::Signature(signature) => Some(signature.as_ref()),
+            _ => None,
+        });
+
+        let ty = signature
+            .and_then(JavaType::parse)
+            .or_else(|| JavaType::parse(&descriptor))
+            .unwrap_or_else(|| {
+                tracing::warn!("Could not parse type of field \"{name}\": \"{descriptor}\"");
+                JavaType::object("java/lang/Object")
+            });
+
+        Self { name: name.to_string(), ty }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// A Java type, parsed from a field descriptor or signature.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) enum JavaType {
+    Boolean,
+    Byte,
+    Char,
+    Short,
+    Int,
+    Long,
+    Float,
+    Double,
+    /// A class and its generic arguments.
+    Object {
+        class: String,
+        args: Vec<JavaType>,
+    },
+    /// An array of a type.
+    Array(Box<JavaType>),
+    /// A type variable, such as `T`.
+    Variable(String),
+}
+
+impl JavaType {
+    /// Create a [`JavaType::Object`] without generic arguments.
+    pub(crate) fn object(class: &str) -> Self {
+        Self::Object { class: class.to_string(), args: Vec::new() }
+    }
+
+    /// Parse a field descriptor or signature,
+    /// such as `I` or `Ljava/util/List<Lnet/minecraft/item/ItemStack;>;`.
+    pub(crate) fn parse(signature: &str) -> Option<Self> {
+        match Self::parse_prefix(signature)? {
+            (ty, "") => Some(ty),
+            _ => None,
+        }
+    }
+
+    /// Parse a type from the start of a signature, returning the remainder.
+    fn parse_prefix(signature: &str) -> Option<(Self, &str)> {
+        let mut chars = signature.chars();
+        let ty = match chars.next()? {
+            'Z' => Self::Boolean,
+            'B' => Self::Byte,
+            'C' => Self::Char,
+            'S' => Self::Short,
+            'I' => Self::Int,
+            'J' => Self::Long,
+            'F' => Self::Float,
+            'D' => Self::Double,
+            '[' => {
+                let (component, rest) = Self::parse_prefix(chars.as_str())?;
+                return Some((Self::Array(Box::new(component)), rest));
+            }
+            'T' => {
+                let (name, rest) = chars.as_str().split_once(';')?;
+                return Some((Self::Variable(name.to_string()), rest));
+            }
+            'L' => return Self::parse_class(chars.as_str()),
+            _ => return None,
+        };
+        Some((ty, chars.as_str()))
+    }
+
+    /// Parse a class type after the leading `L`, including any
+    /// generic arguments and inner classes, such as `Outer<TT;>.Inner;`.
+    fn parse_class(mut signature: &str) -> Option<(Self, &str)> {
+        let mut class = String::new();
+        let mut args = Vec::new();
+
+        loop {
+            let end = signature.find([';', '<', '.'])?;
+            class.push_str(&signature[..end]);
+            signature = &signature[end..];
+
+            if let Some(rest) = signature.strip_prefix('<') {
+                // Inner classes only keep the arguments of the innermost class.
+                args.clear();
+                signature = rest;
+                while let Some(rest) = signature.strip_prefix(|c| c != '>') {
+                    let (arg, rest) = match signature.chars().next()? {
+                        '*' => (Self::object("java/lang/Object"), rest),
+                        '+' | '-' => Self::parse_prefix(rest)?,
+                        _ => Self::parse_prefix(signature)?,
+                    };
+                    args.push(arg);
+                    signature = rest;
+                }
+                signature = signature.strip_prefix('>')?;
+            }
+
+            if let Some(rest) = signature.strip_prefix('.') {
+                class.push('$');
+                signature = rest;
+            } else {
+                return Some((Self::Object { class, args }, signature.strip_prefix(';')?));
+            }
+        }
+    }
+
+    /// Get the Rust type that best matches this type.
+    ///
+    /// Boxed primitives, strings and common collections are mapped to
+    /// their Rust equivalents, other classes use their simple name.
+    pub(crate) fn rust_type(&self) -> String {
+        match self {
+            Self::Boolean => String::from("bool"),
+            Self::Byte => String::from("i8"),
+            // Java `char`s are UTF-16 code units, not Unicode scalar values
+            Self::Char => String::from("u16"),
+            Self::Short => String::from("i16"),
+            Self::Int => String::from("i32"),
+            Self::Long => String::from("i64"),
+            Self::Float => String::from("f32"),
+            Self::Double => String::from("f64"),
+            Self::Array(component) if **component == Self::Byte => String::from("Vec<u8>"),
+            Self::Array(component) => format!("Vec<{}>", component.rust_type()),
+            Self::Variable(name) => name.clone(),
+            Self::Object { class, args } => {
+                let arg = |index: usize| {
+                    args.get(index).map_or_else(|| String::from("Object"), Self::rust_type)
+                };
+                match class.as_str() {
+                    "java/lang/Boolean" => Self::Boolean.rust_type(),
+                    "java/lang/Byte" => Self::Byte.rust_type(),
+                    "java/lang/Character" => Self::Char.rust_type(),
+                    "java/lang/Short" => Self::Short.rust_type(),
+                    "java/lang/Integer" => Self::Int.rust_type(),
+                    "java/lang/Long" => Self::Long.rust_type(),
+                    "java/lang/Float" => Self::Float.rust_type(),
+                    "java/lang/Double" => Self::Double.rust_type(),
+                    "java/lang/String" => String::from("String"),
+                    "java/util/UUID" => String::from("Uuid"),
+                    "java/util/Optional" => format!("Option<{}>", arg(0)),
+                    "java/util/OptionalInt" => String::from("Option<i32>"),
+                    "java/util/OptionalLong" => String::from("Option<i64>"),
+                    "java/util/OptionalDouble" => String::from("Option<f64>"),
+                    "java/util/List" | "java/util/Collection" => format!("Vec<{}>", arg(0)),
+                    "java/util/Set" => format!("HashSet<{}>", arg(0)),
+                    "java/util/Map" => format!("HashMap<{}, {}>", arg(0), arg(1)),
+                    _ => {
+                        let name = class.rsplit('/').next().unwrap_or(class).replace('$', "");
+                        if args.is_empty() {
+                            name
+                        } else {
+                            let args = args.iter().map(Self::rust_type).collect::<Vec<_>>();
+                            format!("{name}<{}>", args.join(", "))
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+#[cfg(test)]
+fn layout() {
+    let rust_type = |signature: &str| JavaType::parse(signature).map(|ty| ty.rust_type());
+
+    assert_eq!(rust_type("I"), Some(String::from("i32")));
+    assert_eq!(rust_type("C"), Some(String::from("u16")));
+    assert_eq!(rust_type("Ljava/lang/Character;"), Some(String::from("u16")));
+    assert_eq!(rust_type("[B"), Some(String::from("Vec<u8>")));
+    assert_eq!(rust_type("[[F"), Some(String::from("Vec<Vec<f32>>")));
+    assert_eq!(rust_type("Ljava/lang/String;"), Some(String::from("String")));
+    assert_eq!(
+        rust_type("Lnet/minecraft/block/Block$Settings;"),
+        Some(String::from("BlockSettings"))
+    );
+    assert_eq!(
+        rust_type("Ljava/util/List<Lnet/minecraft/item/ItemStack;>;"),
+        Some(String::from("Vec<ItemStack>"))
+    );
+    assert_eq!(
+        rust_type("Ljava/util/Map<Ljava/lang/String;Ljava/util/Optional<Ljava/lang/Integer;>;>;"),
+        Some(String::from("HashMap<String, Option<i32>>"))
+    );
+    assert_eq!(
+        rust_type("Ljava/util/List<+Lnet/minecraft/recipe/Ingredient;>;"),
+        Some(String::from("Vec<Ingredient>"))
+    );
+    assert_eq!(
+        rust_type("Lnet/minecraft/registry/entry/RegistryEntry<TT;>;"),
+        Some(String::from("RegistryEntry<T>"))
+    );
+    assert_eq!(
+        JavaType::parse("Lcom/example/Outer<TT;>.Inner<*>;"),
+        Some(JavaType::Object {
+            class: String::from("com/example/Outer$Inner"),
+            args: vec![JavaType::object("java/lang/Object")],
+        })
+    );
+    assert_eq!(JavaType::parse("Ljava/lang/String;I"), None);
+    assert_eq!(JavaType::parse("Ljava/lang/String"), None);
+
+    let field = FieldLayout { name: String::from("maxStackSize"), ty: JavaType::Int };
+    assert_eq!(field.rust_name(), "max_stack_size");
+}
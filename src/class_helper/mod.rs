@@ -6,7 +6,26 @@ use cafebabe::{
     constant_pool::{BootstrapArgument, LiteralConstant, MethodHandle},
     ClassFile,
 };
-use froglight_dependency::dependency::minecraft::minecraft_code::CodeBundle;
+use froglight_dependency::dependency::minecraft::minecraft_code::Classes;
+
+pub(crate) mod api_diff;
+
+mod diagnostic;
+pub(crate) use diagnostic::Diagnostic;
+
+pub(crate) mod disassemble;
+
+pub(crate) mod inline;
+use inline::InlineOptions;
+
+pub(crate) mod interpreter;
+use interpreter::{Effect, Interpreter};
+
+pub(crate) mod layout;
+use layout::ClassLayout;
+
+pub(crate) mod registration;
+use registration::Registration;
 
 pub(crate) trait ClassHelper {
     fn class_code(&self) -> &CodeData<'_> { self.class_method_code("<clinit>") }
@@ -21,46 +40,47 @@ pub(crate) trait ClassHelper {
         &self,
         index: u16,
     ) -> Box<dyn Iterator<Item = &MethodHandle<'_>> + '_>;
-    fn class_bootstrap_code(
-        &self,
-        index: u16,
-        classes: &CodeBundle,
-        f: &mut impl FnMut(&Opcode<'_>),
-    ) {
-        for method in self.class_bootstrap_methods(index) {
-            if let Some(class) = classes.get(&method.class_name) {
-                if let Some(code) = class.class_optional_method_code(&method.member_ref.name) {
-                    for (_, opcode) in &code.bytecode.as_ref().unwrap().opcodes {
-                        f(opcode);
-                        if let Opcode::Invokedynamic(invoke) = opcode {
-                            if index == invoke.attr_index {
-                                tracing::warn!(
-                                    "Breaking recursive bootstrap for class `{}`",
-                                    class.this_class
-                                );
-                            } else {
-                                self.class_bootstrap_code(invoke.attr_index, classes, f);
-                            }
-                        }
-                    }
-                }
-            }
-        }
-    }
 
+    /// Iterate over opcodes, following `invokedynamic` bootstrap methods
+    /// and any calls allowed by the [`InlineOptions`].
     fn iter_code_recursive(
         &self,
         initial: &[&Opcode<'_>],
-        classes: &CodeBundle,
-        mut f: impl FnMut(&Opcode<'_>),
-    ) {
-        for opcode in initial {
-            f(opcode);
-            if let Opcode::Invokedynamic(invoke) = opcode {
-                self.class_bootstrap_code(invoke.attr_index, classes, &mut f);
-            }
-        }
-    }
+        classes: &Classes<'_>,
+        options: InlineOptions,
+        f: impl FnMut(&Opcode<'_>),
+    );
+
+    /// Interpret opcodes, following `invokedynamic` bootstrap methods
+    /// and any calls allowed by the [`InlineOptions`].
+    ///
+    /// Followed calls have their arguments bound,
+    /// and their return value replaces the result of the call.
+    fn interpret_code_recursive(
+        &self,
+        initial: &[&Opcode<'_>],
+        classes: &Classes<'_>,
+        options: InlineOptions,
+        interpreter: &mut Interpreter,
+        f: impl FnMut(&Opcode<'_>, Option<&Effect>),
+    );
+
+    /// Get every value stored in a static field by the static initializer,
+    /// such as `Blocks.STONE = register("stone", ...)`.
+    ///
+    /// Static helper methods, such as `register`, are followed
+    /// with their arguments bound.
+    ///
+    /// If `descriptor` is set, only fields of that type are included.
+    fn class_registrations(
+        &self,
+        classes: &Classes<'_>,
+        descriptor: Option<&str>,
+    ) -> Vec<Registration>;
+
+    /// Get the instance fields of the class in declaration order,
+    /// or the components if the class is a `record`.
+    fn class_layout(&self) -> ClassLayout;
 }
 
 impl ClassHelper for ClassFile<'_> {
@@ -109,12 +129,43 @@ impl ClassHelper for ClassFile<'_> {
             Box::new(core::iter::empty())
         }
     }
+
+    fn iter_code_recursive(
+        &self,
+        initial: &[&Opcode<'_>],
+        classes: &Classes<'_>,
+        options: InlineOptions,
+        mut f: impl FnMut(&Opcode<'_>),
+    ) {
+        inline::iter_code(self, initial, classes, options, &mut f);
+    }
+
+    fn interpret_code_recursive(
+        &self,
+        initial: &[&Opcode<'_>],
+        classes: &Classes<'_>,
+        options: InlineOptions,
+        interpreter: &mut Interpreter,
+        mut f: impl FnMut(&Opcode<'_>, Option<&Effect>),
+    ) {
+        inline::interpret_code(self, initial, classes, options, interpreter, &mut f);
+    }
+
+    fn class_registrations(
+        &self,
+        classes: &Classes<'_>,
+        descriptor: Option<&str>,
+    ) -> Vec<Registration> {
+        registration::extract(self, classes, descriptor)
+    }
+
+    fn class_layout(&self) -> ClassLayout { ClassLayout::new(self) }
 }
 
 // -------------------------------------------------------------------------------------------------
 
 #[allow(dead_code)]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub(crate) enum OwnedConstant {
     Integer(i32),
     Float(f32),
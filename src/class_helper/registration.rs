@@ -0,0 +1,272 @@
+//! Static registrations in registry holder classes.
+//!
+//! Classes such as `Items`, `Blocks` and `EntityType` register every entry
+//! in their static initializer, as `FIELD = register(id, builder-chain)`.
+
+use cafebabe::ClassFile;
+use froglight_dependency::dependency::minecraft::minecraft_code::Classes;
+
+use super::{
+    ClassHelper, OwnedConstant,
+    inline::InlineOptions,
+    interpreter::{Effect, FieldRef, Interpreter, Invocation, InvokeKind, Value},
+};
+
+/// The class of namespaced identifiers.
+const IDENTIFIER: &str = "net/minecraft/util/Identifier";
+
+/// A value stored in a static field of a registry holder class.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Registration {
+    /// The static field, such as `Blocks.STONE`.
+    pub(crate) field: FieldRef,
+    /// The registered identifier.
+    ///
+    /// This is the path passed to the first `Identifier` created,
+    /// or the first string constant used to build the value.
+    pub(crate) identifier: Option<String>,
+    /// Every method and constructor call used to build the value,
+    /// in the order they were evaluated.
+    pub(crate) calls: Vec<Invocation>,
+    /// Every static field read to build the value,
+    /// in the order they were evaluated.
+    pub(crate) statics: Vec<FieldRef>,
+    /// The value stored in the field.
+    pub(crate) value: Value,
+    /// The bytecode offset of the `putstatic` in the static initializer,
+    /// or `None` if the field was stored by a followed call.
+    pub(crate) offset: Option<usize>,
+}
+
+impl Registration {
+    /// Create a [`Registration`] from a value stored in a static field.
+    pub(crate) fn new(field: FieldRef, value: Value, offset: Option<usize>) -> Self {
+        let mut registration = Self {
+            field,
+            identifier: None,
+            calls: Vec::new(),
+            statics: Vec::new(),
+            value: Value::Unknown,
+            offset,
+        };
+        registration.visit(&value);
+        registration.value = value;
+
+        // Prefer the path of an `Identifier` over a namespace passed before it.
+        if let Some(path) = registration
+            .calls
+            .iter()
+            .find(|call| call.class == IDENTIFIER)
+            .and_then(|call| call.args.iter().rev().find_map(Value::as_str))
+        {
+            registration.identifier = Some(path.to_string());
+        }
+
+        registration
+    }
+
+    /// Get the first call to a method by name.
+    pub(crate) fn call(&self, name: &str) -> Option<&Invocation> {
+        self.calls.iter().find(|call| call.name == name)
+    }
+
+    /// Visit a value in evaluation order.
+    fn visit(&mut self, value: &Value) {
+        match value {
+            Value::Constant(OwnedConstant::String(string)) => {
+                self.identifier.get_or_insert_with(|| string.clone());
+            }
+            Value::Static(field) => self.statics.push(field.clone()),
+            Value::Field(object, _) => self.visit(object),
+            Value::Object(object) => {
+                if let Some((descriptor, args)) = &object.constructor {
+                    for arg in args {
+                        self.visit(arg);
+                    }
+                    self.calls.push(Invocation {
+                        kind: InvokeKind::Special,
+                        class: object.class.clone(),
+                        name: String::from("<init>"),
+                        descriptor: descriptor.clone(),
+                        receiver: None,
+                        args: args.clone(),
+                    });
+                }
+            }
+            Value::Array(array) => {
+                self.visit(&array.length);
+                for element in &array.elements {
+                    self.visit(element);
+                }
+            }
+            Value::Invoke(invocation) => {
+                for value in invocation.receiver.iter().chain(&invocation.args) {
+                    self.visit(value);
+                }
+                self.calls.push(invocation.as_ref().clone());
+            }
+            Value::Dynamic(call) => {
+                for arg in &call.args {
+                    self.visit(arg);
+                }
+            }
+            Value::Operation(_, values) => {
+                for value in values {
+                    self.visit(value);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Extract all values stored in static fields of a class by its static
+/// initializer.
+///
+/// If `descriptor` is set, only fields of that type are included.
+pub(super) fn extract(
+    class: &ClassFile<'_>,
+    classes: &Classes<'_>,
+    descriptor: Option<&str>,
+) -> Vec<Registration> {
+    let Some(bytecode) =
+        class.class_optional_method_code("<clinit>").and_then(|code| code.bytecode.as_ref())
+    else {
+        return Vec::new();
+    };
+
+    let mut registrations = Vec::new();
+    let initial = bytecode.opcodes.iter().map(|(_, opcode)| opcode).collect::<Vec<_>>();
+    class.interpret_code_recursive(
+        &initial,
+        classes,
+        InlineOptions::default(),
+        &mut Interpreter::new(),
+        |opcode, effect| {
+            if let Some(Effect::PutStatic(field, value)) = effect
+                && field.class == *class.this_class
+                && descriptor.is_none_or(|descriptor| field.descriptor == descriptor)
+            {
+                let offset = bytecode
+                    .opcodes
+                    .iter()
+                    .find_map(|(offset, op)| core::ptr::eq(op, opcode).then_some(*offset));
+                registrations.push(Registration::new(field.clone(), value.clone(), offset));
+            }
+        },
+    );
+
+    registrations
+}
+
+#[test]
+#[cfg(test)]
+fn registration() {
+    use std::borrow::Cow;
+
+    use cafebabe::{
+        bytecode::Opcode,
+        constant_pool::{LiteralConstant, Loadable, MemberRef, NameAndType},
+        MethodAccessFlags,
+    };
+    use froglight_dependency::dependency::minecraft::{
+        class_builder::ClassBuilder, minecraft_code::CodeBundle,
+    };
+
+    use super::Diagnostic;
+
+    let member = |class: &'static str, name: &'static str, descriptor: &'static str| MemberRef {
+        class_name: Cow::Borrowed(class),
+        name_and_type: NameAndType {
+            name: Cow::Borrowed(name),
+            descriptor: Cow::Borrowed(descriptor),
+        },
+    };
+
+    // `STONE = register("stone", Settings.create().strength(1.5f))`
+    let mut interpreter = Interpreter::new();
+    let mut effects = Vec::new();
+    for opcode in [
+        Opcode::Ldc(Loadable::LiteralConstant(LiteralConstant::String(Cow::Borrowed("stone")))),
+        Opcode::Invokestatic(member("Settings", "create", "()LSettings;")),
+        Opcode::Ldc(Loadable::LiteralConstant(LiteralConstant::Float(1.5))),
+        Opcode::Invokevirtual(member("Settings", "strength", "(F)LSettings;")),
+        Opcode::Invokestatic(member("Blocks", "register", "(Ljava/lang/String;LSettings;)LBlock;")),
+        Opcode::Putstatic(member("Blocks", "STONE", "LBlock;")),
+    ] {
+        effects.extend(interpreter.execute(&opcode));
+    }
+
+    let Some(Effect::PutStatic(field, value)) = effects.pop() else {
+        panic!("Expected a static field to be set, got: {effects:?}");
+    };
+    let registration = Registration::new(field, value, None);
+
+    assert_eq!(registration.field.name, "STONE");
+    assert_eq!(registration.identifier.as_deref(), Some("stone"));
+    assert_eq!(
+        registration.calls.iter().map(|call| call.name.as_str()).collect::<Vec<_>>(),
+        ["create", "strength", "register"]
+    );
+    assert_eq!(
+        registration.call("strength").map(|call| call.args.clone()),
+        Some(vec![Value::Constant(OwnedConstant::Float(1.5))])
+    );
+
+    // `STONE = register("stone")`,
+    // where `register` returns `create(Identifier.of("minecraft", id), AIR)`
+    let class = ClassBuilder::new("Blocks")
+        .method(MethodAccessFlags::STATIC, "<clinit>", "()V", |code| {
+            code.ldc_string("stone");
+            code.invokestatic("Blocks", "register", "(Ljava/lang/String;)LBlock;");
+            code.putstatic("Blocks", "STONE", "LBlock;");
+            code.return_();
+        })
+        .method(MethodAccessFlags::STATIC, "register", "(Ljava/lang/String;)LBlock;", |code| {
+            code.ldc_string("minecraft");
+            code.aload(0);
+            code.invokestatic(
+                IDENTIFIER,
+                "of",
+                "(Ljava/lang/String;Ljava/lang/String;)Lnet/minecraft/util/Identifier;",
+            );
+            code.getstatic("Blocks", "AIR", "LBlock;");
+            code.invokestatic(
+                "Blocks",
+                "create",
+                "(Lnet/minecraft/util/Identifier;LBlock;)LBlock;",
+            );
+            code.areturn();
+        })
+        .build();
+    let bundle = CodeBundle::from_classes([(String::from("Blocks"), class)]);
+
+    // The path of the `Identifier` is used, not the namespace passed first.
+    let (registrations, diagnostic) = bundle.with_classes(|classes| {
+        let class = classes.get("Blocks").unwrap();
+        let registrations = extract(class, classes, Some("LBlock;"));
+        let diagnostic = registrations
+            .first()
+            .map(|registration| Diagnostic::new("").at_registration(class, registration));
+        (registrations, diagnostic)
+    });
+    let [registration] = registrations.as_slice() else {
+        panic!("Expected a single registration, got: {registrations:?}");
+    };
+    assert_eq!(registration.field.name, "STONE");
+    assert_eq!(registration.identifier.as_deref(), Some("stone"));
+    assert_eq!(
+        registration.calls.iter().map(|call| call.name.as_str()).collect::<Vec<_>>(),
+        ["of", "create"]
+    );
+    assert_eq!(
+        registration.statics.iter().map(|field| field.name.as_str()).collect::<Vec<_>>(),
+        ["AIR"]
+    );
+
+    // Diagnostics point at the `putstatic` storing the registration.
+    assert_eq!(registration.offset, Some(6));
+    let diagnostic = diagnostic.unwrap();
+    assert_eq!(diagnostic.method.as_deref(), Some("<clinit>()V"));
+    assert_eq!(diagnostic.offset, Some(6));
+}
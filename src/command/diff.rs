@@ -0,0 +1,70 @@
+use std::path::PathBuf;
+
+use clap::Args;
+use froglight_dependency::{
+    container::DependencyContainer,
+    dependency::minecraft::{CodeSide, MinecraftCode},
+    version::Version,
+};
+
+use crate::{
+    class_helper::api_diff::{ApiDiff, Watched},
+    module::Symbols,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Args)]
+pub(crate) struct DiffArgs {
+    /// The older version
+    pub(crate) from: Version,
+    /// The newer version
+    pub(crate) to: Version,
+    /// Write the report to a file instead of the console
+    #[clap(short, long)]
+    pub(crate) output: Option<PathBuf>,
+    /// Which side of the game to use: `client`, `server` or `merged`
+    #[clap(short, long, default_value = "client")]
+    pub(crate) side: CodeSide,
+}
+
+impl DiffArgs {
+    /// Compare the classes of both versions and print the result.
+    ///
+    /// # Errors
+    /// Returns an error if the code could not be retrieved.
+    pub(crate) async fn run(&self, deps: &mut DependencyContainer) -> anyhow::Result<()> {
+        deps.get_or_retrieve::<MinecraftCode>().await?;
+        let diff = deps
+            .scoped_fut::<MinecraftCode, anyhow::Result<ApiDiff>>(
+                async |code: &mut MinecraftCode, deps| {
+                    code.get_side(&self.from, self.side, deps).await?;
+                    code.get_side(&self.to, self.side, deps).await?;
+
+                    let (Some(from), Some(to)) =
+                        (code.side(&self.from, self.side), code.side(&self.to, self.side))
+                    else {
+                        unreachable!("Both versions were just retrieved")
+                    };
+
+                    let symbols = deps.get_or_retrieve::<Symbols>().await?;
+                    let mut watched = Watched::default();
+                    from.with_classes(|classes| watched.extend(classes, symbols, &self.from));
+                    to.with_classes(|classes| watched.extend(classes, symbols, &self.to));
+
+                    ApiDiff::new(from, to, &watched)
+                },
+            )
+            .await?;
+
+        for class in diff.watched() {
+            tracing::warn!("Diff: Watched class \"{class}\" changed");
+        }
+
+        let report = diff.render(&self.from, &self.to);
+        match &self.output {
+            Some(path) => tokio::fs::write(path, report).await?,
+            None => print!("{report}"),
+        }
+
+        Ok(())
+    }
+}
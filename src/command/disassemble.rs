@@ -0,0 +1,66 @@
+use clap::Args;
+use froglight_dependency::{
+    container::DependencyContainer,
+    dependency::minecraft::{CodeSide, MinecraftCode},
+    version::Version,
+};
+
+use crate::class_helper::{disassemble::Disassembler, inline::InlineOptions};
+
+#[derive(Debug, Clone, PartialEq, Eq, Args)]
+pub(crate) struct DisassembleArgs {
+    /// The version to disassemble
+    #[clap(short, long)]
+    pub(crate) version: Version,
+    /// The mapped class, such as `net/minecraft/entity/EntityType`
+    pub(crate) class: String,
+    /// Only disassemble methods with this name, such as `<clinit>`
+    #[clap(short, long)]
+    pub(crate) method: Option<String>,
+    /// Also disassemble static and private calls inline,
+    /// the same way extractors follow them
+    ///
+    /// Lambda and bootstrap targets are always disassembled inline.
+    #[clap(short, long)]
+    pub(crate) recursive: bool,
+    /// How many calls deep to follow when recursing
+    #[clap(long, default_value_t = 4)]
+    pub(crate) depth: usize,
+    /// Which side of the game to use: `client`, `server` or `merged`
+    #[clap(short, long, default_value = "client")]
+    pub(crate) side: CodeSide,
+}
+
+impl DisassembleArgs {
+    /// Disassemble the class and print it to the console.
+    ///
+    /// # Errors
+    /// Returns an error if the class could not be found.
+    pub(crate) async fn run(&self, deps: &mut DependencyContainer) -> anyhow::Result<()> {
+        let name = self.class.replace('.', "/");
+        let options = if self.recursive {
+            InlineOptions::default().with_max_depth(self.depth)
+        } else {
+            InlineOptions::NONE
+        };
+
+        deps.get_or_retrieve::<MinecraftCode>().await?;
+        deps.scoped_fut::<MinecraftCode, anyhow::Result<()>>(
+            async |code: &mut MinecraftCode, deps| {
+                let bundle = code.get_side(&self.version, self.side, deps).await?;
+                let disassembly = bundle.with_classes(|classes| {
+                    let class = classes.get(&name).ok_or_else(|| {
+                        anyhow::anyhow!("Could not find class \"{name}\" in {}", self.version)
+                    })?;
+                    anyhow::Ok(
+                        Disassembler::new(classes, options).class(class, self.method.as_deref()),
+                    )
+                })?;
+                print!("{disassembly}");
+
+                Ok(())
+            },
+        )
+        .await
+    }
+}
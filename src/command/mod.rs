@@ -0,0 +1,43 @@
+//! Subcommands that run instead of the extract modules.
+
+use clap::Subcommand;
+use froglight_dependency::container::DependencyContainer;
+
+mod diff;
+pub(crate) use diff::DiffArgs;
+
+mod disassemble;
+pub(crate) use disassemble::DisassembleArgs;
+
+mod renames;
+pub(crate) use renames::RenamesArgs;
+
+mod source_diff;
+pub(crate) use source_diff::SourceDiffArgs;
+
+#[derive(Debug, Clone, PartialEq, Eq, Subcommand)]
+pub(crate) enum ToolCommand {
+    /// List added, removed and changed classes between two versions
+    Diff(DiffArgs),
+    /// Print a `javap`-style disassembly of a class
+    Disassemble(DisassembleArgs),
+    /// List renamed, added and removed classes and members between two versions
+    Renames(RenamesArgs),
+    /// Write a unified diff of the decompiled sources of two versions
+    SourceDiff(SourceDiffArgs),
+}
+
+impl ToolCommand {
+    /// Run the subcommand.
+    ///
+    /// # Errors
+    /// Returns an error if the subcommand fails.
+    pub(crate) async fn run(&self, deps: &mut DependencyContainer) -> anyhow::Result<()> {
+        match self {
+            Self::Diff(args) => args.run(deps).await,
+            Self::Disassemble(args) => args.run(deps).await,
+            Self::Renames(args) => args.run(deps).await,
+            Self::SourceDiff(args) => args.run(deps).await,
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use std::path::PathBuf;
+
+use clap::Args;
+use froglight_dependency::{container::DependencyContainer, version::Version};
+
+use crate::module::Renames;
+
+#[derive(Debug, Clone, PartialEq, Eq, Args)]
+pub(crate) struct RenamesArgs {
+    /// The older version
+    pub(crate) from: Version,
+    /// The newer version
+    pub(crate) to: Version,
+    /// Find the name of a class from the older version in the newer version
+    #[clap(short, long)]
+    pub(crate) class: Option<String>,
+    /// Write the report to a file instead of the console
+    #[clap(short, long)]
+    pub(crate) output: Option<PathBuf>,
+}
+
+impl RenamesArgs {
+    /// Compare the mappings of both versions and print the result.
+    ///
+    /// # Errors
+    /// Returns an error if the mappings could not be retrieved,
+    /// or the class does not exist in both versions.
+    pub(crate) async fn run(&self, deps: &mut DependencyContainer) -> anyhow::Result<()> {
+        let renames = Renames::between(&self.from, &self.to, deps).await?;
+
+        if let Some(class) = &self.class {
+            let class = class.replace('.', "/");
+            let found = renames.find_class(&class).ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Could not find class \"{class}\" in both {} and {}",
+                    self.from,
+                    self.to
+                )
+            })?;
+            println!("{found}");
+            return Ok(());
+        }
+
+        if renames.is_empty() {
+            tracing::info!("Renames: No changes between {} and {}", self.from, self.to);
+        }
+
+        let report = renames.render(&self.from, &self.to);
+        match &self.output {
+            Some(path) => tokio::fs::write(path, report).await?,
+            None => print!("{report}"),
+        }
+
+        Ok(())
+    }
+}
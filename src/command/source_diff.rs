@@ -0,0 +1,49 @@
+use std::path::PathBuf;
+
+use clap::Args;
+use froglight_dependency::{
+    container::DependencyContainer, dependency::minecraft::CodeSide, version::Version,
+};
+
+use crate::module::{DiffFormat, SourceDiff};
+
+#[derive(Debug, Clone, PartialEq, Eq, Args)]
+pub(crate) struct SourceDiffArgs {
+    /// The older version
+    pub(crate) from: Version,
+    /// The newer version
+    pub(crate) to: Version,
+    /// Only compare sources in these packages, such as
+    /// `net/minecraft/network/packet`
+    #[clap(short, long)]
+    pub(crate) package: Vec<String>,
+    /// The format of the diff
+    #[clap(short, long, value_enum, default_value_t)]
+    pub(crate) format: DiffFormat,
+    /// Write the diff to a file instead of the console
+    #[clap(short, long)]
+    pub(crate) output: Option<PathBuf>,
+    /// Which side of the game to use: `client`, `server` or `merged`
+    #[clap(short, long, default_value = "client")]
+    pub(crate) side: CodeSide,
+}
+
+impl SourceDiffArgs {
+    /// Compare the decompiled sources of both versions and print the result.
+    ///
+    /// # Errors
+    /// Returns an error if either version could not be decompiled.
+    pub(crate) async fn run(&self, deps: &mut DependencyContainer) -> anyhow::Result<()> {
+        let diff =
+            SourceDiff::between(&self.from, &self.to, self.side, &self.package, deps).await?;
+        tracing::info!("SourceDiff: {} files changed", diff.files.len());
+
+        let output = diff.render(self.format, &self.from, &self.to);
+        match &self.output {
+            Some(path) => tokio::fs::write(path, output).await?,
+            None => print!("{output}"),
+        }
+
+        Ok(())
+    }
+}
@@ -4,32 +4,57 @@
 
 use froglight_dependency::container::SharedDependencies;
 use froglight_extract::module::ExtractModule;
-use module::ToolConfig;
+use module::{Diagnostics, ToolArgs, ToolConfig};
 
 mod class_helper;
+mod command;
 mod module;
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     froglight_extract::cmd::logging();
 
-    // Setup the shared dependencies and modules
+    // Setup the shared dependencies
     let deps = SharedDependencies::from_rust_env();
-    let modules = ExtractModule::map();
 
-    // Parse the command line arguments and load the configuration file
+    // Parse the command line arguments and run a subcommand if one was given
+    let args = deps.write().await.get_or_retrieve::<ToolArgs>().await?.clone();
+    if let Some(command) = args.command {
+        return command.run(&mut *deps.write().await).await;
+    }
+
+    // Load the configuration file and check the modules before doing any work
     let config = ToolConfig::get(&deps).await?;
+    let modules = ExtractModule::sort(&config.modules)?;
 
     // Run the modules for each version
-    for version in config.versions {
+    let result = run(&config, &modules, &deps).await;
+
+    // Write a report of anything that could not be interpreted
+    let deps = deps.read().await;
+    if let Some(diagnostics) = deps.get::<Diagnostics>()
+        && !diagnostics.is_empty()
+    {
+        if let Some(path) = deps.get::<ToolArgs>().and_then(|args| args.report.as_ref()) {
+            diagnostics.write_report(path).await?;
+        } else {
+            tracing::warn!("Some code could not be interpreted, see `--report`");
+        }
+    }
+
+    result
+}
+
+async fn run(
+    config: &ToolConfig,
+    modules: &[&'static ExtractModule],
+    deps: &SharedDependencies,
+) -> anyhow::Result<()> {
+    for version in &config.versions {
         tracing::info!("Version: {version}");
-        for module in &config.modules {
-            if let Some(extract) = modules.get(module.as_str()) {
-                tracing::info!("Running module: {}", module);
-                extract.run(&version, &mut *deps.write().await).await?;
-            } else {
-                tracing::error!("Unknown module: {}", module);
-            }
+        for module in modules {
+            tracing::info!("Running module: {}", module.name());
+            module.run(version, &mut *deps.write().await).await?;
         }
     }
 
@@ -2,7 +2,7 @@ use std::{collections::HashMap, ops::RangeInclusive, path::Path};
 
 use froglight_dependency::{
     container::{Dependency, DependencyContainer},
-    dependency::minecraft::DataGenerator,
+    dependency::minecraft::{DataGenerator, GeneratorOutput},
     version::Version,
 };
 use indexmap::IndexMap;
@@ -35,7 +35,7 @@ impl BlockReports {
             deps.get_or_retrieve::<DataGenerator>().await?;
             deps.scoped_fut::<DataGenerator, anyhow::Result<()>>(
                 async |data: &mut DataGenerator, deps| {
-                    let path = data.get_version(version, deps).await?;
+                    let path = data.get_output(version, GeneratorOutput::Reports, deps).await?;
                     self.0.insert(version.clone(), Self::parse_report(path).await?);
                     Ok(())
                 },
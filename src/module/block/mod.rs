@@ -1,11 +1,15 @@
 #![expect(unused_imports)]
 
-use std::{fmt::Write, path::Path, sync::Once};
+use std::{
+    fmt::Write,
+    path::{Path, PathBuf},
+    sync::Once,
+};
 
 use attribute::{BlockAttributeData, ParsedBlockReport};
 use convert_case::{Case, Casing};
 use froglight_dependency::{
-    container::DependencyContainer,
+    container::{Dependency, DependencyContainer},
     dependency::{
         minecraft::{DataGenerator, Translations, TranslationsFile},
         vineflower::DecompiledJar,
@@ -13,6 +17,7 @@ use froglight_dependency::{
     version::Version,
 };
 use froglight_extract::module::ExtractModule;
+use serde::Deserialize;
 use tokio::sync::OnceCell;
 use tracing::Level;
 
@@ -22,13 +27,35 @@ mod attribute;
 pub(crate) use attribute::{BlockAttributes, BlockReports};
 
 #[derive(ExtractModule)]
-#[module(function = Blocks::generate)]
+#[module(function = Blocks::generate, provides = [BlockReports], config = BlocksConfig)]
 pub(crate) struct Blocks;
 
+/// The `[modules.blocks]` section of the configuration file.
+#[derive(Debug, Clone, PartialEq, Eq, Dependency, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct BlocksConfig {
+    /// The path to the `froglight-block` crate.
+    pub(crate) path: PathBuf,
+    /// The width block names are padded to.
+    pub(crate) name_padding: usize,
+    /// The width block properties are padded to.
+    pub(crate) property_padding: usize,
+}
+
+impl Default for BlocksConfig {
+    fn default() -> Self {
+        Self {
+            path: PathBuf::from("crates/froglight-block"),
+            name_padding: 36,
+            property_padding: 96,
+        }
+    }
+}
+
 impl Blocks {
     async fn generate(version: &Version, deps: &mut DependencyContainer) -> anyhow::Result<()> {
-        let mut directory = std::env::current_dir()?;
-        directory.push("crates/froglight-block");
+        let config = deps.get_or_retrieve::<BlocksConfig>().await?;
+        let directory = std::env::current_dir()?.join(&config.path);
 
         if !tokio::fs::try_exists(&directory).await? {
             anyhow::bail!("Could not find \"froglight-block\" at \"{}\"", directory.display());
@@ -154,9 +181,6 @@ froglight_macros::blocks! {{
 }
 
 impl Blocks {
-    const BLOCK_NAME_PADDING: usize = 36;
-    const BLOCK_PROPERTY_PADDING: usize = 96;
-
     /// Generate block trait implementations.
     async fn generate_block_traits(
         version: &Version,
@@ -166,6 +190,9 @@ impl Blocks {
         let version_ident =
             format!("froglight_common::version::V{}", version.to_long_string().replace('.', "_"));
 
+        let BlocksConfig { name_padding, property_padding, .. } =
+            deps.get_or_retrieve::<BlocksConfig>().await?.clone();
+
         let attributes = deps.get::<BlockAttributes>().unwrap();
 
         let report = deps.get::<BlockReports>().unwrap();
@@ -196,7 +223,7 @@ impl Blocks {
                 acc.push_str(&block_name);
 
                 // Pad the block name with spaces.
-                for _ in 0..Self::BLOCK_NAME_PADDING.saturating_sub(block_name.len()) {
+                for _ in 0..name_padding.saturating_sub(block_name.len()) {
                     acc.push(' ');
                 }
 
@@ -214,8 +241,7 @@ impl Blocks {
                     acc.push_str(", ");
 
                     // Pad the block properties with spaces.
-                    for _ in 0..Self::BLOCK_PROPERTY_PADDING.saturating_sub(block_properties.len())
-                    {
+                    for _ in 0..property_padding.saturating_sub(block_properties.len()) {
                         acc.push(' ');
                     }
 
@@ -1,25 +1,40 @@
-use std::path::PathBuf;
+use std::{collections::HashMap, path::PathBuf};
 
 use clap::Parser;
 use froglight_dependency::{
     container::{Dependency, DependencyContainer, SharedDependencies},
+    dependency::minecraft::DataGenerator,
     version::Version,
 };
+use froglight_extract::module::ExtractModule;
 use serde::Deserialize;
 
-use super::{Blocks, Entities, Items, Packets, Registry};
+use super::{Blocks, DiagnosticPolicy, Entities, Enums, Items, Packets, Registry};
+use crate::command::ToolCommand;
 
 #[derive(Debug, Clone, PartialEq, Eq, Parser, Dependency)]
+#[command(subcommand_negates_reqs = true)]
 #[dep(retrieve = Self::parse)]
 pub(crate) struct ToolArgs {
     /// Path to the configuration file
-    #[clap(short, long)]
-    pub(crate) config: PathBuf,
+    #[clap(short, long, required = true)]
+    pub(crate) config: Option<PathBuf>,
     /// The list of modules to run
     ///
     /// If empty, all modules will be run
+    ///
+    /// Modules always run after the modules they depend on
     #[clap(name = "module", short, long)]
     pub(crate) modules: Vec<String>,
+    /// What to do when part of the code could not be interpreted
+    #[clap(long, value_enum, default_value_t)]
+    pub(crate) on_error: DiagnosticPolicy,
+    /// Write a report of everything that could not be interpreted
+    #[clap(long)]
+    pub(crate) report: Option<PathBuf>,
+    /// Run a subcommand instead of the modules
+    #[clap(subcommand)]
+    pub(crate) command: Option<ToolCommand>,
 }
 
 impl ToolArgs {
@@ -28,6 +43,7 @@ impl ToolArgs {
         Blocks::MODULE_NAME,
         Items::MODULE_NAME,
         Entities::MODULE_NAME,
+        Enums::MODULE_NAME,
         Packets::MODULE_NAME,
         Registry::MODULE_NAME,
     ];
@@ -45,14 +61,34 @@ impl ToolArgs {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Dependency, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Dependency)]
 #[dep(retrieve = Self::parse)]
 pub(crate) struct ToolConfig {
     pub(crate) versions: Vec<Version>,
-    #[serde(skip)]
     pub(crate) modules: Vec<String>,
 }
 
+/// The contents of a configuration file.
+#[derive(Deserialize)]
+struct ConfigFile {
+    versions: Vec<Version>,
+    /// Settings for running the data generator.
+    #[serde(default)]
+    generator: GeneratorConfig,
+    /// Module configurations, keyed by module name.
+    #[serde(default)]
+    modules: HashMap<String, serde_json::Value>,
+}
+
+/// The `[generator]` section of a configuration file.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct GeneratorConfig {
+    /// Arguments passed to the JVM, such as `-Xmx4G`.
+    #[serde(default)]
+    jvm_args: Vec<String>,
+}
+
 impl ToolConfig {
     /// Retrieve the configuration from the dependency container
     #[inline]
@@ -61,7 +97,8 @@ impl ToolConfig {
     }
 
     async fn parse(deps: &mut DependencyContainer) -> anyhow::Result<Self> {
-        let ToolArgs { config, modules } = deps.get_or_retrieve::<ToolArgs>().await?.clone();
+        let ToolArgs { config, modules, .. } = deps.get_or_retrieve::<ToolArgs>().await?.clone();
+        let config = config.ok_or_else(|| anyhow::anyhow!("No configuration file provided"))?;
 
         let path = if tokio::fs::try_exists(&config).await? {
             config
@@ -73,6 +110,70 @@ impl ToolConfig {
         tracing::debug!("Loading configuration from: \"{}\"", path.display());
         let file = tokio::fs::read_to_string(path).await?;
 
-        Ok(Self { versions: toml_edit::de::from_str::<Self>(&file)?.versions, modules })
+        let ConfigFile { versions, generator, modules: mut sections } =
+            toml_edit::de::from_str(&file)?;
+
+        deps.get_or_retrieve_mut::<DataGenerator>().await?.set_jvm_args(generator.jvm_args);
+
+        // Configure every module, so modules that were not selected can still be used
+        // by others.
+        for module in ExtractModule::iter() {
+            module.configure(sections.remove(module.name()), deps)?;
+        }
+        if let Some(name) = sections.keys().next() {
+            anyhow::bail!("Configuration for unknown module \"{name}\"");
+        }
+
+        Ok(Self { versions, modules })
     }
 }
+
+#[test]
+#[cfg(test)]
+fn module_config() {
+    use super::{block::BlocksConfig, enums::EnumsConfig};
+
+    let file: ConfigFile = toml_edit::de::from_str(
+        r#"
+versions = ["1.21.5"]
+
+[generator]
+jvm_args = ["-Xmx4G"]
+
+[modules.blocks]
+name_padding = 40
+
+[modules.items]
+name_pading = 40
+
+[[modules.enums.generate]]
+class = "net/minecraft/util/Rarity"
+path = "crates/froglight-item"
+name = "ItemRarity"
+"#,
+    )
+    .unwrap();
+    assert_eq!(file.generator.jvm_args, ["-Xmx4G"]);
+
+    let modules = ExtractModule::map();
+    let mut deps = DependencyContainer::new(std::env::temp_dir());
+
+    modules[Blocks::MODULE_NAME].configure(file.modules.get("blocks").cloned(), &mut deps).unwrap();
+    let config = deps.get::<BlocksConfig>().unwrap();
+    assert_eq!(config.name_padding, 40);
+    assert_eq!(config.property_padding, BlocksConfig::default().property_padding);
+
+    let error = modules[Items::MODULE_NAME]
+        .configure(file.modules.get("items").cloned(), &mut deps)
+        .unwrap_err();
+    assert!(error.to_string().contains("unknown field `name_pading`"), "{error}");
+
+    let error =
+        modules[Enums::MODULE_NAME].configure(file.modules.get("items").cloned(), &mut deps);
+    assert!(error.is_err());
+
+    modules[Enums::MODULE_NAME].configure(file.modules.get("enums").cloned(), &mut deps).unwrap();
+    let config = deps.get::<EnumsConfig>().unwrap();
+    assert_eq!(config.generate.len(), 1);
+    assert_eq!(config.generate[0].name, "ItemRarity");
+}
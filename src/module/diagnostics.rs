@@ -0,0 +1,147 @@
+use std::{fmt::Write, path::Path};
+
+use clap::ValueEnum;
+use froglight_dependency::{
+    container::{Dependency, DependencyContainer},
+    version::Version,
+};
+
+use super::config::ToolArgs;
+use crate::class_helper::Diagnostic;
+
+/// What to do when a module reports a [`Diagnostic`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub(crate) enum DiagnosticPolicy {
+    /// Stop the run with an error.
+    #[default]
+    Fail,
+    /// Skip anything that could not be interpreted and continue.
+    Continue,
+}
+
+/// All [`Diagnostic`]s reported by modules during a run.
+#[derive(Debug, Clone, PartialEq, Eq, Dependency)]
+#[dep(retrieve = Self::retrieve)]
+pub(crate) struct Diagnostics {
+    policy: DiagnosticPolicy,
+    reported: Vec<(String, Version, Diagnostic)>,
+}
+
+impl Diagnostics {
+    async fn retrieve(deps: &mut DependencyContainer) -> anyhow::Result<Self> {
+        let policy = deps.get_or_retrieve::<ToolArgs>().await?.on_error;
+        Ok(Self { policy, reported: Vec::new() })
+    }
+
+    /// Report any [`Diagnostic`]s from a module.
+    ///
+    /// Diagnostics that were already reported are ignored,
+    /// so extractors that run more than once do not repeat them.
+    ///
+    /// # Errors
+    /// Returns an error if there are any new diagnostics
+    /// and the policy is [`DiagnosticPolicy::Fail`].
+    pub(crate) async fn report(
+        module: &str,
+        version: &Version,
+        diagnostics: Vec<Diagnostic>,
+        deps: &mut DependencyContainer,
+    ) -> anyhow::Result<()> {
+        if diagnostics.is_empty() {
+            return Ok(());
+        }
+
+        let this = deps.get_or_retrieve_mut::<Self>().await?;
+
+        let mut new = Vec::new();
+        for diagnostic in diagnostics {
+            let entry = (module.to_string(), version.clone(), diagnostic);
+            if !this.reported.contains(&entry) && !new.contains(&entry.2) {
+                tracing::warn!("{module} ({version}): {}", entry.2);
+                new.push(entry.2.clone());
+                this.reported.push(entry);
+            }
+        }
+
+        if this.policy == DiagnosticPolicy::Fail && !new.is_empty() {
+            anyhow::bail!(
+                "{module}: Could not interpret {} item(s) for {version}, use `--on-error continue` to skip them",
+                new.len()
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Returns `true` if no diagnostics have been reported.
+    #[must_use]
+    pub(crate) fn is_empty(&self) -> bool { self.reported.is_empty() }
+
+    /// Render a report listing all diagnostics, grouped by module and version.
+    #[must_use]
+    pub(crate) fn render(&self) -> String {
+        let mut report = String::from("# Extraction Report\n");
+
+        let mut current = None;
+        for (module, version, diagnostic) in &self.reported {
+            if current != Some((module, version)) {
+                current = Some((module, version));
+                write!(report, "\n## {module} ({version})\n\n").unwrap();
+            }
+
+            let diagnostic = diagnostic.to_string().replace('\n', "\n  ");
+            writeln!(report, "- {diagnostic}").unwrap();
+        }
+
+        report
+    }
+
+    /// Write the report to a file.
+    ///
+    /// # Errors
+    /// Returns an error if the file could not be written.
+    pub(crate) async fn write_report(&self, path: &Path) -> anyhow::Result<()> {
+        tracing::info!("Writing diagnostics report to \"{}\"", path.display());
+        tokio::fs::write(path, self.render()).await.map_err(Into::into)
+    }
+}
+
+#[test]
+#[cfg(test)]
+fn report() {
+    let mut diagnostics = Diagnostics { policy: DiagnosticPolicy::Continue, reported: Vec::new() };
+    assert!(diagnostics.is_empty());
+
+    let version = Version::new_release(1, 21, 4);
+    let diagnostic = Diagnostic {
+        message: String::from("EntityType: Unknown builder method \"glow\""),
+        class: Some(String::from("net/minecraft/entity/EntityType")),
+        method: Some(String::from("<clinit>()V")),
+        offset: Some(12),
+        context: vec![(9, String::from("Aload(0)")), (12, String::from("Invokevirtual"))],
+    };
+    diagnostics.reported.push((String::from("Entities"), version.clone(), diagnostic));
+    diagnostics.reported.push((
+        String::from("Packets"),
+        version,
+        Diagnostic::new("PacketCodec: No codec found").class("net/minecraft/Packet"),
+    ));
+
+    assert_eq!(
+        diagnostics.render(),
+        r#"# Extraction Report
+
+## Entities (1.21.4)
+
+- EntityType: Unknown builder method "glow"
+      at net/minecraft/entity/EntityType.<clinit>()V @ 12
+            9: Aload(0)
+      >    12: Invokevirtual
+
+## Packets (1.21.4)
+
+- PacketCodec: No codec found
+      in net/minecraft/Packet
+"#
+    );
+}
@@ -12,7 +12,7 @@ use cafebabe::{
 use convert_case::{Case, Casing};
 use froglight_dependency::{
     container::DependencyContainer,
-    dependency::minecraft::{MinecraftCode, minecraft_code::CodeBundle},
+    dependency::minecraft::{MinecraftCode, XrefLocation, minecraft_code::Classes},
     version::Version,
 };
 use indexmap::{IndexMap, map::Entry};
@@ -22,18 +22,41 @@ use tracing::{debug, error, trace};
 use super::Entities;
 use crate::{
     ToolConfig,
-    class_helper::{ClassHelper, OwnedConstant},
+    class_helper::{
+        ClassHelper, Diagnostic, OwnedConstant,
+        inline::InlineOptions,
+        interpreter::{Effect, Interpreter, InvokeKind, Value},
+    },
+    module::{Diagnostics, Symbols},
 };
 
-const BUILDER_TYPE: &str = "net/minecraft/entity/attribute/DefaultAttributeContainer$Builder";
-const BUILDER_DESCRIPTOR: &str =
-    "()Lnet/minecraft/entity/attribute/DefaultAttributeContainer$Builder;";
-
-const ENTITY_ATTRIBUTES: &str = "net/minecraft/entity/attribute/EntityAttributes";
-const REGISTRY_TYPE_DESCRIPTOR: &str = "Lnet/minecraft/registry/entry/RegistryEntry;";
+/// The classes used to find entity attribute values, resolved for a version.
+struct AttributeSymbols<'a> {
+    /// `DefaultAttributeContainer$Builder`
+    builder: &'a str,
+    /// The descriptor of a method returning a `DefaultAttributeContainer$Builder`.
+    builder_method: String,
+    /// `EntityAttributes`
+    entity_attributes: &'a str,
+    registry_entry_descriptor: String,
+    /// `EntityType`
+    entity_type: &'a str,
+    entity_type_descriptor: String,
+}
 
-const ENTITY_TYPE: &str = "net/minecraft/entity/EntityType";
-const ENTITY_TYPE_DESCRIPTOR: &str = "Lnet/minecraft/entity/EntityType;";
+impl<'a> AttributeSymbols<'a> {
+    fn new(symbols: &'a Symbols, version: &Version) -> anyhow::Result<Self> {
+        let builder = symbols.require("default_attribute_container_builder", version)?;
+        Ok(Self {
+            builder,
+            builder_method: format!("()L{builder};"),
+            entity_attributes: symbols.require("entity_attributes", version)?,
+            registry_entry_descriptor: symbols.require_descriptor("registry_entry", version)?,
+            entity_type: symbols.require("entity_type", version)?,
+            entity_type_descriptor: symbols.require_descriptor("entity_type", version)?,
+        })
+    }
+}
 
 impl Entities {
     /// Extract the entity attribute values for each entity type.
@@ -46,48 +69,83 @@ impl Entities {
         deps: &mut DependencyContainer,
     ) -> anyhow::Result<IndexMap<String, IndexMap<String, String>>> {
         let mut attributes = IndexMap::<String, IndexMap<String, String>>::new();
+        let mut diagnostics = Vec::new();
 
         deps.get_or_retrieve::<MinecraftCode>().await?;
         deps.scoped_fut::<MinecraftCode, anyhow::Result<_>>(async |jars, deps| {
-            let jar = jars.get_version(version, deps).await?;
-            let class = jar.get(ENTITY_TYPE).ok_or_else(|| {
-                anyhow::anyhow!("Packets: Could not find \"{ENTITY_TYPE}\" class!")
-            })?;
+            let bundle = jars.get_side(version, Self::CODE_SIDE, deps).await?;
+            let symbols = deps.get_or_retrieve::<Symbols>().await?;
+            let names = AttributeSymbols::new(symbols, version)?;
+            bundle.with_classes(|jar| {
+                let class = jar.get(names.entity_type).ok_or_else(|| {
+                    anyhow::anyhow!("Entities: Could not find \"{}\" class!", names.entity_type)
+                })?;
 
-            let mut classes = Vec::new();
+                let xrefs = jar.xrefs();
 
-            let initial = class.class_code().bytecode.as_ref().unwrap();
-            let initial = initial.opcodes.iter().map(|(_, opcode)| opcode).collect::<Vec<_>>();
-            class.iter_code_recursive(&initial, jar, |opcode| match opcode {
-                // Initializing any entity classes that are not AI, animation-related, boss-related.
-                Opcode::Invokespecial(MemberRef { class_name, name_and_type })
-                    if class_name.starts_with("net/minecraft/entity/")
-                        && !class_name.starts_with("net/minecraft/entity/ai")
-                        && !class_name.starts_with("net/minecraft/entity/boss")
-                        && !class_name.contains("$")
-                        && class_name != "net/minecraft/entity/AnimationState"
-                        && name_and_type.name == "<init>" =>
-                {
-                    classes.push(class_name.to_string())
-                }
-                // Note: This is where the class init is called, so we finally get the class name!
-                Opcode::Invokedynamic(dynamic) => {
-                    for method in class.class_bootstrap_methods(dynamic.attr_index) {
-                        if method.kind == ReferenceKind::NewInvokeSpecial {
-                            classes.push(method.class_name.to_string());
+                // Every static field written by `EntityType.<clinit>`, in bytecode order.
+                let mut writes = class
+                    .fields
+                    .iter()
+                    .flat_map(|field| {
+                        xrefs
+                            .field_writers(names.entity_type, &field.name)
+                            .iter()
+                            .filter(|location| Self::is_entity_type_init(location, &names))
+                            .map(move |location| (location.offset, field))
+                    })
+                    .collect::<Vec<_>>();
+                writes.sort_unstable_by_key(|(offset, _)| *offset);
+
+                // Every entity class constructed by `EntityType.<clinit>`,
+                // either directly, through a constructor reference or inside a lambda.
+                let mut constructed = Vec::new();
+                for class_name in jar.class_names().filter(|name| {
+                    // Ignore any classes that are AI, animation-related or boss-related.
+                    name.starts_with("net/minecraft/entity/")
+                        && !name.starts_with("net/minecraft/entity/ai")
+                        && !name.starts_with("net/minecraft/entity/boss")
+                        && !name.contains('$')
+                        && *name != "net/minecraft/entity/AnimationState"
+                }) {
+                    for location in xrefs.callers(class_name, "<init>", None) {
+                        if Self::is_entity_type_init(location, &names) {
+                            constructed.push((location.offset, class_name));
+                        } else if location.class == names.entity_type {
+                            for lambda in xrefs
+                                .callers(
+                                    names.entity_type,
+                                    &location.method,
+                                    Some(&location.descriptor),
+                                )
+                                .into_iter()
+                                .filter(|lambda| Self::is_entity_type_init(lambda, &names))
+                            {
+                                constructed.push((lambda.offset, class_name));
+                            }
                         }
                     }
                 }
-                // Actually creating the `EntityType` here,
-                // so stop and process all collected classes so far.
-                Opcode::Putstatic(MemberRef { class_name, name_and_type })
-                    if class_name == ENTITY_TYPE
-                        && name_and_type.descriptor == ENTITY_TYPE_DESCRIPTOR =>
-                {
+                constructed.sort_unstable();
+
+                // Assign each class to the next field written,
+                // skipping any that are not an `EntityType`.
+                let mut constructed = constructed.into_iter().peekable();
+                for (offset, field) in writes {
+                    let mut classes = Vec::new();
+                    while let Some((_, class)) = constructed.next_if(|(at, _)| *at < offset) {
+                        classes.push(class);
+                    }
+                    if field.descriptor.to_string() != names.entity_type_descriptor {
+                        continue;
+                    }
+
                     let mut collected = IndexMap::new();
-                    for class in core::mem::take(&mut classes) {
-                        debug!("[{}]: Parsing class \"{class}\"", name_and_type.name);
-                        for (attr, value) in Self::parse_class_attributes(&class, jar) {
+                    for class in classes {
+                        debug!("[{}]: Parsing class \"{class}\"", field.name);
+                        for (attr, value) in
+                            Self::parse_class_attributes(class, jar, &names, &mut diagnostics)
+                        {
                             match collected.entry(attr.to_case(Case::Pascal)) {
                                 Entry::Occupied(mut entry) => {
                                     if &value != "\"default\"" {
@@ -100,31 +158,46 @@ impl Entities {
                             }
                         }
                     }
-                    attributes.insert(name_and_type.name.to_lowercase(), collected);
+                    attributes.insert(field.name.to_lowercase(), collected);
                 }
-                Opcode::Putstatic(..) => classes.clear(),
-                _ => {}
-            });
 
-            Ok(())
+                Ok(())
+            })
         })
         .await?;
 
+        Diagnostics::report(Self::MODULE_NAME, version, diagnostics, deps).await?;
         Ok(attributes)
     }
 
+    /// Returns `true` if the location is inside of `EntityType.<clinit>`.
+    fn is_entity_type_init(location: &XrefLocation, names: &AttributeSymbols) -> bool {
+        location.class == names.entity_type && location.method == "<clinit>"
+    }
+
     /// Iterate over all of the methods in the class and
     /// look for an attribute builder method.
     ///
     /// Use this to recursively parse attributes through the class hierarchy.
-    fn parse_class_attributes(class_name: &str, jar: &CodeBundle) -> IndexMap<String, String> {
+    fn parse_class_attributes(
+        class_name: &str,
+        jar: &Classes<'_>,
+        names: &AttributeSymbols,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> IndexMap<String, String> {
         if class_name.starts_with("net/minecraft") {
             if let Some(class) = jar.get(class_name) {
                 if let Some(method) =
-                    class.methods.iter().find(|m| m.descriptor.to_string() == BUILDER_DESCRIPTOR)
+                    class.methods.iter().find(|m| m.descriptor.to_string() == names.builder_method)
                 {
                     debug!("    [{class_name}]: Using \"{}\" as the entrypoint", method.name);
-                    return Self::parse_class_method_attributes(&class, method, jar);
+                    return Self::parse_class_method_attributes(
+                        class,
+                        method,
+                        jar,
+                        names,
+                        diagnostics,
+                    );
                 }
             }
         }
@@ -136,115 +209,149 @@ impl Entities {
     fn parse_class_method_attributes(
         class: &ClassFile,
         method: &MethodInfo,
-        jar: &CodeBundle,
+        jar: &Classes<'_>,
+        names: &AttributeSymbols,
+        diagnostics: &mut Vec<Diagnostic>,
     ) -> IndexMap<String, String> {
         let mut attributes = IndexMap::new();
 
         if let Some(AttributeInfo { data: AttributeData::Code(code), .. }) =
             method.attributes.iter().find(|attr| matches!(&attr.data, AttributeData::Code(..)))
         {
-            let mut constants = Vec::<OwnedConstant>::new();
-
             let initial = code.bytecode.as_ref().unwrap();
             let initial = initial.opcodes.iter().map(|(_, opcode)| opcode).collect::<Vec<_>>();
-            class.iter_code_recursive(&initial, jar, |opcode| {
-                match opcode {
-                    // Collect constants used in the method for later use
-                    Opcode::Dconst0 => constants.push(OwnedConstant::Double(0.0)),
-                    Opcode::Dconst1 => constants.push(OwnedConstant::Double(1.0)),
-                    Opcode::Ldc(Loadable::LiteralConstant(constant))
-                    | Opcode::LdcW(Loadable::LiteralConstant(constant))
-                    | Opcode::Ldc2W(Loadable::LiteralConstant(constant)) => {
-                        constants.push(OwnedConstant::from(constant));
-                    }
-                    // Collect attribute names and store them as constants for later use
-                    Opcode::Getstatic(MemberRef { class_name, name_and_type })
-                        if class_name == ENTITY_ATTRIBUTES
-                            && name_and_type.descriptor == REGISTRY_TYPE_DESCRIPTOR =>
-                    {
-                        constants.push(OwnedConstant::String(name_and_type.name.to_string()));
-                    }
-                    // Recurse into other builder methods and collect their attributes
-                    Opcode::Invokestatic(MemberRef { class_name, name_and_type })
-                    | Opcode::Invokevirtual(MemberRef { class_name, name_and_type })
-                    | Opcode::Invokeinterface(MemberRef { class_name, name_and_type }, ..)
-                        if name_and_type.descriptor == BUILDER_DESCRIPTOR =>
-                    {
-                        for (attr, value) in Self::find_and_parse_class_method(class_name, &name_and_type.name, jar) {
-                            match attributes.entry(attr.to_case(Case::Pascal)) {
-                                Entry::Occupied(mut entry) => {
-                                    if &value != "\"default\"" {
-                                        *entry.get_mut() = value;
+            let mut interpreter = Interpreter::for_method(class, method);
+            class.interpret_code_recursive(
+                &initial,
+                jar,
+                InlineOptions::NONE,
+                &mut interpreter,
+                |opcode, effect| {
+                    let Some(Effect::Invoke(invocation)) = effect else { return };
+                    match invocation.kind {
+                        InvokeKind::Special => {}
+                        // Recurse into other builder methods and collect their attributes
+                        _ if invocation.descriptor == names.builder_method => {
+                            for (attr, value) in Self::find_and_parse_class_method(
+                                &invocation.class,
+                                &invocation.name,
+                                jar,
+                                names,
+                                diagnostics,
+                            ) {
+                                match attributes.entry(attr.to_case(Case::Pascal)) {
+                                    Entry::Occupied(mut entry) => {
+                                        if &value != "\"default\"" {
+                                            *entry.get_mut() = value;
+                                        }
+                                    }
+                                    Entry::Vacant(entry) => {
+                                        entry.insert(value);
                                     }
-                                }
-                                Entry::Vacant(entry) => {
-                                    entry.insert(value);
                                 }
                             }
                         }
-                    }
-                    // Add attributes to the map when added to the builder
-                    Opcode::Invokestatic(MemberRef { class_name, name_and_type })
-                    | Opcode::Invokevirtual(MemberRef { class_name, name_and_type })
-                    | Opcode::Invokeinterface(MemberRef { class_name, name_and_type }, ..)
-                        if class_name == BUILDER_TYPE => {
-                            match name_and_type.name.as_ref() {
-                                "add" if &name_and_type.descriptor == "(Lnet/minecraft/registry/entry/RegistryEntry;)Lnet/minecraft/entity/attribute/DefaultAttributeContainer$Builder;" => {
-                                    match constants.pop() {
-                                        #[allow(unused_variables)]
-                                        Some(OwnedConstant::String(s)) => {
-                                            trace!("    [{}]: Adding attribute \"{s}\" -> Default", class.this_class);
-                                            if let Entry::Vacant(entry) = attributes.entry(s.to_string()) {
-                                                entry.insert(String::from("\"default\""));
-                                            }
-                                        }
-                                        a => {
-                                            panic!("Expected a string for \"add\", got: {a:?}");
+                        // Add attributes to the map when added to the builder
+                        _ if invocation.class == names.builder => {
+                            match (invocation.name.as_str(), invocation.args.as_slice()) {
+                                ("add", [attr]) => match Self::attribute_name(attr, names) {
+                                    Some(s) => {
+                                        trace!(
+                                            "    [{}]: Adding attribute \"{s}\" -> Default",
+                                            class.this_class
+                                        );
+                                        if let Entry::Vacant(entry) = attributes.entry(s) {
+                                            entry.insert(String::from("\"default\""));
                                         }
                                     }
+                                    None => diagnostics.push(
+                                        Diagnostic::new(format!(
+                                            "Expected an attribute for \"add\", got: {attr:?}"
+                                        ))
+                                        .at(class, opcode),
+                                    ),
                                 },
-                                "add" if &name_and_type.descriptor == "(Lnet/minecraft/registry/entry/RegistryEntry;D)Lnet/minecraft/entity/attribute/DefaultAttributeContainer$Builder;" => {
-                                    match (constants.pop(), constants.pop()) {
-                                        (Some(OwnedConstant::Double(d)), Some(OwnedConstant::String(s))) => {
-                                            fn round(d: f64) -> f64 { (d * 10000.0).round() / 10000.0 }
+                                ("add", [attr, value]) => {
+                                    match (Self::attribute_name(attr, names), value.as_f64()) {
+                                        (Some(s), Some(d)) => {
+                                            fn round(d: f64) -> f64 {
+                                                (d * 10000.0).round() / 10000.0
+                                            }
 
-                                            debug!("    [{}]: Adding attribute \"{s}\" -> {d}", class.this_class);
-                                            attributes.insert(s.to_string(), format!("{}f64", round(d)));
-                                        }
-                                        (a, b) => {
-                                            panic!("Expected a string and double for \"add\", got: ({a:?}, {b:?})");
+                                            debug!(
+                                                "    [{}]: Adding attribute \"{s}\" -> {d}",
+                                                class.this_class
+                                            );
+                                            attributes.insert(s, format!("{}f64", round(d)));
                                         }
+                                        _ => diagnostics.push(
+                                            Diagnostic::new(format!(
+                                                "Expected an attribute and double for \"add\", \
+                                                 got: ({attr:?}, {value:?})"
+                                            ))
+                                            .at(class, opcode),
+                                        ),
                                     }
-                                },
-                                unk => panic!("Unknown attribute builder method: {unk} ({})", name_and_type.descriptor),
+                                }
+                                (unk, _) => diagnostics.push(
+                                    Diagnostic::new(format!(
+                                        "Unknown attribute builder method: {unk} ({})",
+                                        invocation.descriptor
+                                    ))
+                                    .at(class, opcode),
+                                ),
                             }
-
                         }
-                    _ => {}
-                }
-            });
+                        _ => {}
+                    }
+                },
+            );
         }
 
         attributes
     }
 
+    /// Get the name of an `EntityAttributes` field passed to the builder.
+    fn attribute_name(value: &Value, names: &AttributeSymbols) -> Option<String> {
+        match value {
+            Value::Static(field)
+                if field.class == names.entity_attributes
+                    && field.descriptor == names.registry_entry_descriptor =>
+            {
+                Some(field.name.clone())
+            }
+            _ => None,
+        }
+    }
+
     /// Find a class method,
     /// either in the class itself or in any of its superclasses.
     fn find_and_parse_class_method(
         class_name: &str,
         class_method: &str,
-        jar: &CodeBundle,
+        jar: &Classes<'_>,
+        names: &AttributeSymbols,
+        diagnostics: &mut Vec<Diagnostic>,
     ) -> IndexMap<String, String> {
         if !class_name.starts_with("net/minecraft") {
             return IndexMap::new();
         } else if let Some(class) = jar.get(class_name) {
             if let Some(method) = class.methods.iter().find(|m| m.name == class_method) {
-                return Self::parse_class_method_attributes(&class, method, jar);
+                return Self::parse_class_method_attributes(class, method, jar, names, diagnostics);
             } else if let Some(super_class) = class.super_class.as_ref() {
-                return Self::find_and_parse_class_method(super_class, class_method, jar);
+                return Self::find_and_parse_class_method(
+                    super_class,
+                    class_method,
+                    jar,
+                    names,
+                    diagnostics,
+                );
             }
         }
 
-        panic!("Could not find class method \"{class_method}\" in class \"{class_name}\"");
+        diagnostics.push(Diagnostic::new(format!(
+            "Could not find class method \"{class_method}\" in class \"{class_name}\""
+        )));
+        IndexMap::new()
     }
 }
@@ -1,9 +1,5 @@
 use std::{ops::RangeInclusive, path::Path};
 
-use cafebabe::{
-    bytecode::Opcode,
-    constant_pool::{LiteralConstant, Loadable, MemberRef},
-};
 use convert_case::{Case, Casing};
 use froglight_dependency::{
     container::DependencyContainer, dependency::minecraft::MinecraftCode, version::Version,
@@ -12,7 +8,8 @@ use tokio::sync::OnceCell;
 
 use super::Entities;
 use crate::{
-    class_helper::{ClassHelper, OwnedConstant},
+    class_helper::{interpreter::Value, ClassHelper, Diagnostic, OwnedConstant},
+    module::{Diagnostics, Symbols},
     ToolConfig,
 };
 
@@ -125,87 +122,62 @@ froglight_macros::entity_attribute_properties! {{
         version: &Version,
         deps: &mut DependencyContainer,
     ) -> anyhow::Result<Vec<EntityAttribute>> {
-        const ENTITY_ATTRIBUTES: &str = "net/minecraft/entity/attribute/EntityAttributes";
-        const REGISTRY_ENTRY_DESCRIPTOR: &str = "Lnet/minecraft/registry/entry/RegistryEntry;";
-
         let mut attributes = Vec::new();
+        let mut diagnostics = Vec::new();
 
         deps.get_or_retrieve::<MinecraftCode>().await?;
         deps.scoped_fut::<MinecraftCode, anyhow::Result<_>>(async |jars, deps| {
-            let jar = jars.get_version(version, deps).await?;
-            let class = jar.get(ENTITY_ATTRIBUTES).ok_or_else(|| {
-                anyhow::anyhow!("Packets: Could not find \"{ENTITY_ATTRIBUTES}\" class!")
-            })?;
-
-            let mut attribute = EntityAttributeBuilder::default();
-            let mut constants = Vec::<OwnedConstant>::new();
-
-            let initial = class.class_code().bytecode.as_ref().unwrap();
-            let initial = initial.opcodes.iter().map(|(_, opcode)| opcode).collect::<Vec<_>>();
-            class.iter_code_recursive(&initial, jar, |opcode| match opcode {
-                Opcode::Iconst0 => constants.push(OwnedConstant::Integer(0)),
-                Opcode::Iconst1 => constants.push(OwnedConstant::Integer(1)),
-                Opcode::Iconst2 => constants.push(OwnedConstant::Integer(2)),
-                Opcode::Iconst3 => constants.push(OwnedConstant::Integer(3)),
-                Opcode::Iconst4 => constants.push(OwnedConstant::Integer(4)),
-                Opcode::Iconst5 => constants.push(OwnedConstant::Integer(5)),
-                Opcode::Fconst0 => constants.push(OwnedConstant::Float(0.0)),
-                Opcode::Fconst1 => constants.push(OwnedConstant::Float(1.0)),
-                Opcode::Fconst2 => constants.push(OwnedConstant::Float(2.0)),
-                Opcode::Dconst0 => constants.push(OwnedConstant::Double(0.0)),
-                Opcode::Dconst1 => constants.push(OwnedConstant::Double(1.0)),
-                Opcode::Ldc(Loadable::LiteralConstant(constant))
-                | Opcode::LdcW(Loadable::LiteralConstant(constant))
-                | Opcode::Ldc2W(Loadable::LiteralConstant(constant)) => {
-                    match constant {
-                        // Look for the first string constant and set it as the identifier
-                        LiteralConstant::String(string) if attribute.identifier.is_none() => {
-                            attribute.identifier = Some(string.to_string());
-                        }
-                        // Look for the second string constant and set it as the translation key
-                        LiteralConstant::String(string) if attribute.translation.is_none() => {
-                            attribute.translation = Some(string.to_string());
-                        }
-                        other => {
-                            constants.push(OwnedConstant::from(other));
-                        }
-                    }
-                }
-                // Finish the entity type and push it to the list
-                Opcode::Putstatic(MemberRef { class_name, name_and_type })
-                    if class_name == ENTITY_ATTRIBUTES
-                        && name_and_type.descriptor == REGISTRY_ENTRY_DESCRIPTOR =>
+            let jar = jars.get_side(version, Self::CODE_SIDE, deps).await?;
+            let symbols = deps.get_or_retrieve::<Symbols>().await?;
+            let entity_attributes = symbols.require("entity_attributes", version)?;
+            let registry_entry_descriptor =
+                symbols.require_descriptor("registry_entry", version)?;
+
+            jar.with_classes(|classes| {
+                let class = classes.get(entity_attributes).ok_or_else(|| {
+                    anyhow::anyhow!("Entities: Could not find \"{entity_attributes}\" class!")
+                })?;
+                for registration in
+                    class.class_registrations(classes, Some(registry_entry_descriptor.as_str()))
                 {
-                    for constant in constants.drain(..) {
-                        match constant {
-                            OwnedConstant::Double(d) if attribute.default.is_none() => {
-                                attribute.default = Some(d);
-                            }
-                            OwnedConstant::Double(d) if attribute.range.is_none() => {
-                                attribute.range = Some(d..=f64::MAX);
-                            }
-                            OwnedConstant::Double(d)
-                                if attribute
-                                    .range
-                                    .as_ref()
-                                    .is_some_and(|r| r.end() == &f64::MAX) =>
-                            {
-                                attribute.range =
-                                    Some(*attribute.range.as_ref().unwrap().start()..=d);
-                            }
-                            _ => {}
+                    let mut attribute = EntityAttributeBuilder {
+                        identifier: registration.identifier.clone(),
+                        ..EntityAttributeBuilder::default()
+                    };
+
+                    // Look for the constructor taking the translation key, default and range
+                    for invocation in registration.calls.iter().filter(|c| c.name == "<init>") {
+                        if let Some((Value::Constant(OwnedConstant::String(key)), args)) =
+                            invocation.args.split_first()
+                        {
+                            attribute.translation = Some(key.clone());
+
+                            let mut values = args.iter().filter_map(Value::as_f64);
+                            attribute.default = values.next();
+                            attribute.range = values.next().map(|min| {
+                                let max = values.next().unwrap_or(f64::MAX);
+                                min..=max
+                            });
+                            break;
                         }
                     }
 
-                    attributes.push(core::mem::take(&mut attribute).into());
+                    // Finish the entity attribute and push it to the list
+                    match attribute.try_into() {
+                        Ok(attribute) => attributes.push(attribute),
+                        Err(err) => diagnostics.push(
+                            Diagnostic::new(format!("{}: {err}", registration.field.name))
+                                .at_registration(class, &registration),
+                        ),
+                    }
                 }
-                _ => {}
-            });
 
-            Ok(())
+                Ok(())
+            })
         })
         .await?;
 
+        Diagnostics::report(Self::MODULE_NAME, version, diagnostics, deps).await?;
         Ok(attributes)
     }
 }
@@ -226,13 +198,17 @@ struct EntityAttributeBuilder {
     default: Option<f64>,
     range: Option<RangeInclusive<f64>>,
 }
-impl From<EntityAttributeBuilder> for EntityAttribute {
-    fn from(builder: EntityAttributeBuilder) -> Self {
-        Self {
-            identifier: builder.identifier.expect("EntityAttributeBuilder: Identifier is None!"),
-            translation: builder.translation.expect("EntityAttributeBuilder: Translation is None!"),
-            default: builder.default.expect("EntityAttributeBuilder: Default is None!"),
-            range: builder.range.expect("EntityAttributeBuilder: Range is None!"),
-        }
+impl TryFrom<EntityAttributeBuilder> for EntityAttribute {
+    type Error = &'static str;
+
+    fn try_from(builder: EntityAttributeBuilder) -> Result<Self, Self::Error> {
+        Ok(Self {
+            identifier: builder.identifier.ok_or("EntityAttributeBuilder: Identifier is None!")?,
+            translation: builder
+                .translation
+                .ok_or("EntityAttributeBuilder: Translation is None!")?,
+            default: builder.default.ok_or("EntityAttributeBuilder: Default is None!")?,
+            range: builder.range.ok_or("EntityAttributeBuilder: Range is None!")?,
+        })
     }
 }
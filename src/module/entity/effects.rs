@@ -1,9 +1,5 @@
 use std::path::Path;
 
-use cafebabe::{
-    bytecode::Opcode,
-    constant_pool::{LiteralConstant, Loadable, MemberRef},
-};
 use convert_case::{Case, Casing};
 use froglight_dependency::{
     container::DependencyContainer, dependency::minecraft::MinecraftCode, version::Version,
@@ -11,7 +7,11 @@ use froglight_dependency::{
 use tokio::sync::OnceCell;
 
 use super::Entities;
-use crate::{class_helper::ClassHelper, ToolConfig};
+use crate::{
+    class_helper::{interpreter::Value, ClassHelper, Diagnostic},
+    module::{Diagnostics, Symbols},
+    ToolConfig,
+};
 
 impl Entities {
     /// Generate status effect unit structs.
@@ -118,60 +118,67 @@ froglight_macros::status_effect_properties! {{
         version: &Version,
         deps: &mut DependencyContainer,
     ) -> anyhow::Result<Vec<StatusEffect>> {
-        const STATUS_EFFECTS: &str = "net/minecraft/entity/effect/StatusEffects";
-        const REGISTRY_ENTRY_DESCRIPTOR: &str = "Lnet/minecraft/registry/entry/RegistryEntry;";
-        const STATUS_EFFECT_CATEGORY: &str = "net/minecraft/entity/effect/StatusEffectCategory";
-
         let mut effects = Vec::new();
+        let mut diagnostics = Vec::new();
 
         deps.get_or_retrieve::<MinecraftCode>().await?;
         deps.scoped_fut::<MinecraftCode, anyhow::Result<_>>(async |jars, deps| {
-            let jar = jars.get_version(version, deps).await?;
-            let class = jar.get(STATUS_EFFECTS).ok_or_else(|| {
-                anyhow::anyhow!("Packets: Could not find \"{STATUS_EFFECTS}\" class!")
-            })?;
-
-            let mut effect = StatusEffectBuilder::default();
-
-            let initial = class.class_code().bytecode.as_ref().unwrap();
-            let initial = initial.opcodes.iter().map(|(_, opcode)| opcode).collect::<Vec<_>>();
-            class.iter_code_recursive(&initial, jar, |opcode| match opcode {
-                Opcode::Ldc(Loadable::LiteralConstant(constant))
-                | Opcode::LdcW(Loadable::LiteralConstant(constant))
-                | Opcode::Ldc2W(Loadable::LiteralConstant(constant)) => {
-                    match constant {
-                        // Look for the first string constant and set it as the identifier
-                        LiteralConstant::String(string) if effect.identifier.is_none() => {
-                            effect.identifier = Some(string.to_string());
-                        }
-                        // Look for the first integer constant and set it as the color
-                        LiteralConstant::Integer(integer) if effect.color.is_none() => {
-                            // Look for the first integer constant and set it as the color
-                            effect.color = Some(*integer as u32);
+            let jar = jars.get_side(version, Self::CODE_SIDE, deps).await?;
+            let symbols = deps.get_or_retrieve::<Symbols>().await?;
+            let status_effects = symbols.require("status_effects", version)?;
+            let registry_entry_descriptor =
+                symbols.require_descriptor("registry_entry", version)?;
+            let status_effect_category = symbols.require("status_effect_category", version)?;
+
+            jar.with_classes(|classes| {
+                let class = classes.get(status_effects).ok_or_else(|| {
+                    anyhow::anyhow!("Entities: Could not find \"{status_effects}\" class!")
+                })?;
+                for registration in
+                    class.class_registrations(classes, Some(registry_entry_descriptor.as_str()))
+                {
+                    let mut effect = StatusEffectBuilder {
+                        identifier: registration.identifier.clone(),
+                        ..StatusEffectBuilder::default()
+                    };
+
+                    // Look for the constructor taking the effect category and color
+                    for invocation in registration.calls.iter().filter(|c| c.name == "<init>") {
+                        let category = invocation.args.iter().find_map(|arg| match arg {
+                            Value::Static(field) if field.class == status_effect_category => {
+                                Some(field.name.to_lowercase())
+                            }
+                            _ => None,
+                        });
+                        if category.is_some() {
+                            effect.category = category;
+                            #[expect(clippy::cast_sign_loss)]
+                            let color = invocation
+                                .args
+                                .iter()
+                                .find_map(Value::as_int)
+                                .map(|color| color as u32);
+                            effect.color = color;
+                            break;
                         }
-                        _ => {}
+                    }
+
+                    // Finish the status effect and push it to the list
+                    match effect.try_into() {
+                        Ok(effect) => effects.push(effect),
+                        Err(err) => diagnostics.push(
+                            Diagnostic::new(format!("{}: {err}", registration.field.name))
+                                .at_registration(class, &registration),
+                        ),
                     }
                 }
-                // Look for references to the effect category and set it
-                Opcode::Getstatic(MemberRef { class_name, name_and_type })
-                    if class_name == STATUS_EFFECT_CATEGORY =>
-                {
-                    effect.category = Some(name_and_type.name.to_lowercase());
-                }
-                // Finish the entity type and push it to the list
-                Opcode::Putstatic(MemberRef { class_name, name_and_type })
-                    if class_name == STATUS_EFFECTS
-                        && name_and_type.descriptor == REGISTRY_ENTRY_DESCRIPTOR =>
-                {
-                    effects.push(core::mem::take(&mut effect).into());
-                }
-                _ => {}
-            });
 
-            Ok(())
+                Ok(())
+            })
         })
         .await?;
 
+        Diagnostics::report(Self::MODULE_NAME, version, diagnostics, deps).await?;
         Ok(effects)
     }
 }
@@ -190,12 +197,14 @@ struct StatusEffectBuilder {
     category: Option<String>,
     color: Option<u32>,
 }
-impl From<StatusEffectBuilder> for StatusEffect {
-    fn from(builder: StatusEffectBuilder) -> Self {
-        Self {
-            identifier: builder.identifier.expect("StatusEffectBuilder: Identifier is None!"),
-            category: builder.category.expect("StatusEffectBuilder: Category is None!"),
-            color: builder.color.expect("StatusEffectBuilder: Color is None!"),
-        }
+impl TryFrom<StatusEffectBuilder> for StatusEffect {
+    type Error = &'static str;
+
+    fn try_from(builder: StatusEffectBuilder) -> Result<Self, Self::Error> {
+        Ok(Self {
+            identifier: builder.identifier.ok_or("StatusEffectBuilder: Identifier is None!")?,
+            category: builder.category.ok_or("StatusEffectBuilder: Category is None!")?,
+            color: builder.color.ok_or("StatusEffectBuilder: Color is None!")?,
+        })
     }
 }
@@ -1,9 +1,5 @@
 use std::path::Path;
 
-use cafebabe::{
-    bytecode::Opcode,
-    constant_pool::{LiteralConstant, Loadable, MemberRef},
-};
 use convert_case::{Case, Casing};
 use froglight_dependency::{
     container::DependencyContainer, dependency::minecraft::MinecraftCode, version::Version,
@@ -14,7 +10,8 @@ use tracing::warn;
 use super::Entities;
 use crate::{
     ToolConfig,
-    class_helper::{ClassHelper, OwnedConstant},
+    class_helper::{ClassHelper, Diagnostic, OwnedConstant, interpreter::Value},
+    module::{Diagnostics, Symbols},
 };
 
 impl Entities {
@@ -145,111 +142,111 @@ froglight_macros::entity_type_properties! {{
         version: &Version,
         deps: &mut DependencyContainer,
     ) -> anyhow::Result<Vec<EntityType>> {
-        const ENTITY_TYPE: &str = "net/minecraft/entity/EntityType";
-        const ENTITY_TYPE_DESCRIPTOR: &str = "Lnet/minecraft/entity/EntityType;";
-        const ENTITY_BUILDER: &str = "net/minecraft/entity/EntityType$Builder";
-        const SPAWN_GROUP: &str = "net/minecraft/entity/SpawnGroup";
-
         let mut entities = Vec::new();
+        let mut diagnostics = Vec::new();
 
         deps.get_or_retrieve::<MinecraftCode>().await?;
         deps.scoped_fut::<MinecraftCode, anyhow::Result<_>>(async |jars, deps| {
-            let jar = jars.get_version(version, deps).await?;
-            let class = jar.get(ENTITY_TYPE).ok_or_else(|| {
-                anyhow::anyhow!("Packets: Could not find \"{ENTITY_TYPE}\" class!")
-            })?;
-
-            let mut entity = EntityTypeBuilder::default();
-            let mut constants = Vec::<OwnedConstant>::new();
-
-            let initial = class.class_code().bytecode.as_ref().unwrap();
-            let initial = initial.opcodes.iter().map(|(_, opcode)| opcode).collect::<Vec<_>>();
-            class.iter_code_recursive(&initial, jar, |opcode| match opcode {
-                Opcode::Iconst0 => constants.push(OwnedConstant::Integer(0)),
-                Opcode::Iconst1 => constants.push(OwnedConstant::Integer(1)),
-                Opcode::Iconst2 => constants.push(OwnedConstant::Integer(2)),
-                Opcode::Iconst3 => constants.push(OwnedConstant::Integer(3)),
-                Opcode::Iconst4 => constants.push(OwnedConstant::Integer(4)),
-                Opcode::Iconst5 => constants.push(OwnedConstant::Integer(5)),
-                Opcode::Fconst0 => constants.push(OwnedConstant::Float(0.0)),
-                Opcode::Fconst1 => constants.push(OwnedConstant::Float(1.0)),
-                Opcode::Fconst2 => constants.push(OwnedConstant::Float(2.0)),
-                // Look for the first string constant and set it as the identifier
-                Opcode::Ldc(Loadable::LiteralConstant(constant))
-                | Opcode::LdcW(Loadable::LiteralConstant(constant))
-                | Opcode::Ldc2W(Loadable::LiteralConstant(constant)) => {
-                    if let LiteralConstant::String(string) = constant
-                        && constants.is_empty()
-                        && entity.identifier.is_none()
-                    {
-                        entity.identifier = Some(string.clone().into_owned());
-                    } else {
-                        constants.push(constant.into());
-                    }
-                }
-                // Look for references to the spawn group and set it
-                Opcode::Getstatic(MemberRef { class_name, name_and_type })
-                    if class_name == SPAWN_GROUP =>
+            let jar = jars.get_side(version, Self::CODE_SIDE, deps).await?;
+            let symbols = deps.get_or_retrieve::<Symbols>().await?;
+            let entity_type = symbols.require("entity_type", version)?;
+            let entity_type_descriptor = symbols.require_descriptor("entity_type", version)?;
+            let entity_builder = symbols.require("entity_type_builder", version)?;
+            let spawn_group = symbols.require("spawn_group", version)?;
+
+            jar.with_classes(|classes| {
+                let class = classes.get(entity_type).ok_or_else(|| {
+                    anyhow::anyhow!("Entities: Could not find \"{entity_type}\" class!")
+                })?;
+                for registration in
+                    class.class_registrations(classes, Some(entity_type_descriptor.as_str()))
                 {
-                    entity.spawn_group = Some(name_and_type.name.to_lowercase());
-                }
-                // Match any entity builder methods called
-                Opcode::Invokevirtual(MemberRef { class_name, name_and_type })
-                    if class_name == ENTITY_BUILDER =>
-                {
-                    match name_and_type.name.as_ref() {
-                        "dimensions" => match (constants.pop(), constants.pop()) {
+                    let mut entity = EntityTypeBuilder {
+                        identifier: registration.identifier.clone(),
+                        ..EntityTypeBuilder::default()
+                    };
+
+                    // Match any entity builder methods called
+                    for invocation in
+                        registration.calls.iter().filter(|c| c.class == entity_builder)
+                    {
+                        // Look for the spawn group passed to the builder and set it
+                        for arg in &invocation.args {
+                            if let Value::Static(field) = arg
+                                && field.class == spawn_group
+                            {
+                                entity.spawn_group = Some(field.name.to_lowercase());
+                            }
+                        }
+
+                        match (invocation.name.as_str(), invocation.args.as_slice()) {
                             (
-                                Some(OwnedConstant::Float(height)),
-                                Some(OwnedConstant::Float(width)),
+                                "dimensions",
+                                [
+                                    Value::Constant(OwnedConstant::Float(width)),
+                                    Value::Constant(OwnedConstant::Float(height)),
+                                ],
                             ) => {
-                                entity.dimensions = Some((width, height));
-                            }
-                            (Some(..), Some(..)) => {
-                                panic!("EntityType: Dimensions are incorrect type!")
+                                entity.dimensions = Some((*width, *height));
                             }
-                            _ => panic!("EntityType: Dimensions expected but not found!"),
-                        },
-                        "eyeHeight" => match constants.pop() {
-                            Some(OwnedConstant::Float(height)) => {
-                                entity.eye_height = Some(height);
+                            ("dimensions", _) => diagnostics.push(
+                                Diagnostic::new(format!(
+                                    "EntityType: Dimensions of \"{}\" are not constant: {:?}",
+                                    registration.field.name, invocation.args
+                                ))
+                                .at_registration(class, &registration),
+                            ),
+                            ("eyeHeight", [Value::Constant(OwnedConstant::Float(height))]) => {
+                                entity.eye_height = Some(*height);
                             }
-                            Some(..) => panic!("EntityType: EyeHeight is incorrect type!"),
-                            _ => panic!("EntityType: EyeHeight expected but not found!"),
-                        },
-                        "makeFireImmune" => entity.fire_immune = Some(true),
-                        // Ignore known methods that are not used
-                        "allowSpawningInside"
-                        | "attachment"
-                        | "disableSaving"
-                        | "disableSummon"
-                        | "dropsNothing"
-                        | "maxTrackingRange"
-                        | "nameTagAttachment"
-                        | "passengerAttachments"
-                        | "spawnableFarFromPlayer"
-                        | "spawnBoxScale"
-                        | "trackingTickInterval"
-                        | "vehicleAttachment" => {}
-                        // Warn about unknown methods
-                        unk => warn!("EntityType: Unknown builder function \"{unk}\""),
+                            ("eyeHeight", _) => diagnostics.push(
+                                Diagnostic::new(format!(
+                                    "EntityType: EyeHeight of \"{}\" is not constant: {:?}",
+                                    registration.field.name, invocation.args
+                                ))
+                                .at_registration(class, &registration),
+                            ),
+                            ("makeFireImmune", _) => entity.fire_immune = Some(true),
+                            // Ignore known methods that are not used
+                            (
+                                "allowSpawningInside"
+                                | "attachment"
+                                | "build"
+                                | "create"
+                                | "createNothing"
+                                | "disableSaving"
+                                | "disableSummon"
+                                | "dropsNothing"
+                                | "maxTrackingRange"
+                                | "nameTagAttachment"
+                                | "passengerAttachments"
+                                | "spawnableFarFromPlayer"
+                                | "spawnBoxScale"
+                                | "trackingTickInterval"
+                                | "vehicleAttachment",
+                                _,
+                            ) => {}
+                            // Warn about unknown methods
+                            (unk, _) => warn!("EntityType: Unknown builder function \"{unk}\""),
+                        }
+                    }
+
+                    // Finish the entity type and push it to the list
+                    match entity.try_into() {
+                        Ok(entity) => entities.push(entity),
+                        Err(err) => diagnostics.push(
+                            Diagnostic::new(format!("{}: {err}", registration.field.name))
+                                .at_registration(class, &registration),
+                        ),
                     }
                 }
-                // Finish the entity type and push it to the list
-                Opcode::Putstatic(MemberRef { class_name, name_and_type })
-                    if class_name == ENTITY_TYPE
-                        && name_and_type.descriptor == ENTITY_TYPE_DESCRIPTOR =>
-                {
-                    constants.clear();
-                    entities.push(core::mem::take(&mut entity).into());
-                }
-                _ => {}
-            });
 
-            Ok(())
+                Ok(())
+            })
         })
         .await?;
 
+        Diagnostics::report(Self::MODULE_NAME, version, diagnostics, deps).await?;
         Ok(entities)
     }
 }
@@ -275,14 +272,17 @@ struct EntityTypeBuilder {
     pub dimensions: Option<(f32, f32)>,
     pub eye_height: Option<f32>,
 }
-impl From<EntityTypeBuilder> for EntityType {
-    fn from(builder: EntityTypeBuilder) -> Self {
-        Self {
-            identifier: builder.identifier.expect("EntityTypeBuilder: Identifier is None!"),
-            spawn_group: builder.spawn_group.expect("EntityTypeBuilder: SpawnGroup is None!"),
+impl TryFrom<EntityTypeBuilder> for EntityType {
+    type Error = &'static str;
+
+    fn try_from(builder: EntityTypeBuilder) -> Result<Self, Self::Error> {
+        let dimensions = builder.dimensions.ok_or("EntityTypeBuilder: Dimensions is None!")?;
+        Ok(Self {
+            identifier: builder.identifier.ok_or("EntityTypeBuilder: Identifier is None!")?,
+            spawn_group: builder.spawn_group.ok_or("EntityTypeBuilder: SpawnGroup is None!")?,
             fire_immune: builder.fire_immune.unwrap_or(false),
-            dimensions: builder.dimensions.expect("EntityTypeBuilder: Dimensions is None!"),
-            eye_height: builder.eye_height.unwrap_or_else(|| builder.dimensions.unwrap().1 * 0.85),
-        }
+            dimensions,
+            eye_height: builder.eye_height.unwrap_or(dimensions.1 * 0.85),
+        })
     }
 }
@@ -0,0 +1,141 @@
+use std::collections::HashMap;
+
+use cafebabe::{ClassAccessFlags, ClassFile, FieldAccessFlags};
+use froglight_dependency::{
+    container::{Dependency, DependencyContainer},
+    dependency::minecraft::MinecraftCode,
+    version::Version,
+};
+
+use super::Enums;
+use crate::class_helper::{
+    ClassHelper,
+    interpreter::{Effect, Interpreter, Value},
+};
+
+/// Java enums extracted from a
+/// [`CodeBundle`](froglight_dependency::dependency::minecraft::minecraft_code::CodeBundle).
+#[derive(Default, Dependency)]
+pub(crate) struct JavaEnums(HashMap<Version, HashMap<String, JavaEnum>>);
+
+impl JavaEnums {
+    /// Get the [`JavaEnum`] for the given version and class.
+    ///
+    /// Returns `None` if the enum has not been extracted yet.
+    #[must_use]
+    pub(crate) fn class(&self, version: &Version, class: &str) -> Option<&JavaEnum> {
+        self.0.get(version).and_then(|enums| enums.get(class))
+    }
+
+    /// Extract the [`JavaEnum`] for the given version and class.
+    ///
+    /// # Errors
+    /// Returns an error if the class does not exist or is not an enum.
+    pub(crate) async fn get_class(
+        &mut self,
+        version: &Version,
+        class: &str,
+        deps: &mut DependencyContainer,
+    ) -> anyhow::Result<&JavaEnum> {
+        if self.class(version, class).is_none() {
+            deps.get_or_retrieve::<MinecraftCode>().await?;
+            let parsed = deps
+                .scoped_fut::<MinecraftCode, anyhow::Result<JavaEnum>>(
+                    async |code: &mut MinecraftCode, deps| {
+                        let bundle = code.get_side(version, Enums::CODE_SIDE, deps).await?;
+                        bundle.with_classes(|classes| {
+                            let class = classes.get(class).ok_or_else(|| {
+                                anyhow::anyhow!("Could not find class \"{class}\"!")
+                            })?;
+                            JavaEnum::parse(class)
+                        })
+                    },
+                )
+                .await
+                .map_err(|err| anyhow::anyhow!("JavaEnums: {err}"))?;
+
+            self.0.entry(version.clone()).or_default().insert(class.to_string(), parsed);
+        }
+
+        Ok(self.class(version, class).unwrap())
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// A Java enum and its constants.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct JavaEnum {
+    /// The enum class, such as `net/minecraft/entity/SpawnGroup`.
+    pub(crate) class: String,
+    /// The enum constants, in ordinal order.
+    pub(crate) constants: Vec<EnumConstant>,
+}
+
+/// A constant of a [`JavaEnum`].
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct EnumConstant {
+    /// The name of the constant, such as `MONSTER`.
+    pub(crate) name: String,
+    /// The constructor arguments,
+    /// without the name and ordinal added by the compiler.
+    pub(crate) args: Vec<Value>,
+}
+
+impl JavaEnum {
+    /// Parse the constants of an enum class from its static initializer.
+    ///
+    /// # Errors
+    /// Returns an error if the class is not an enum.
+    pub(crate) fn parse(class: &ClassFile<'_>) -> anyhow::Result<Self> {
+        if !class.access_flags.contains(ClassAccessFlags::ENUM) {
+            anyhow::bail!("Class \"{}\" is not an enum!", class.this_class);
+        }
+
+        // Constants are declared in ordinal order.
+        let mut constants: Vec<(Option<i32>, EnumConstant)> = class
+            .fields
+            .iter()
+            .filter(|field| field.access_flags.contains(FieldAccessFlags::ENUM))
+            .map(|field| (None, EnumConstant { name: field.name.to_string(), args: Vec::new() }))
+            .collect();
+
+        let opcodes = class
+            .class_optional_method_code("<clinit>")
+            .and_then(|code| code.bytecode.as_ref())
+            .map_or(&[][..], |bytecode| bytecode.opcodes.as_slice());
+
+        let mut interpreter = Interpreter::new();
+        for (_, opcode) in opcodes {
+            let Some(Effect::PutStatic(field, Value::Object(object))) = interpreter.execute(opcode)
+            else {
+                continue;
+            };
+            if field.class != *class.this_class {
+                continue;
+            }
+
+            if let Some((ordinal, constant)) =
+                constants.iter_mut().find(|(_, constant)| constant.name == field.name)
+            {
+                let mut args = object.constructor.map(|(_, args)| args).unwrap_or_default();
+                *ordinal = args.get(1).and_then(Value::as_int);
+                constant.args = args.split_off(2.min(args.len()));
+            }
+        }
+
+        for (ordinal, constant) in &constants {
+            if ordinal.is_none() {
+                tracing::warn!("JavaEnums: Could not find ordinal of \"{}\"", constant.name);
+            }
+        }
+
+        // Fall back to declaration order if the ordinal is unknown.
+        constants.sort_by_key(|(ordinal, _)| ordinal.unwrap_or(i32::MAX));
+
+        Ok(Self {
+            class: class.this_class.to_string(),
+            constants: constants.into_iter().map(|(_, constant)| constant).collect(),
+        })
+    }
+}
@@ -0,0 +1,242 @@
+use std::fmt::Write;
+
+use convert_case::{Case, Casing};
+use froglight_dependency::version::Version;
+
+use super::extract::JavaEnum;
+use crate::class_helper::{OwnedConstant, interpreter::Value};
+
+/// Generate a Rust enum matching a [`JavaEnum`] across versions.
+///
+/// Variants are ordered by their first appearance,
+/// and each version gets a table converting variants to and from ordinals.
+///
+/// `common` is the path to the `froglight-common` crate,
+/// which is `crate` when generating inside of it.
+pub(super) fn generate_enum(name: &str, common: &str, versions: &[(Version, &JavaEnum)]) -> String {
+    let mut variants = Vec::<(String, &str, String)>::new();
+    for (_, java) in versions {
+        for constant in &java.constants {
+            if variants.iter().all(|(_, existing, _)| *existing != constant.name) {
+                let args = constant.args.iter().map(format_value).collect::<Vec<_>>().join(", ");
+                variants.push((variant_name(&constant.name), &constant.name, args));
+            }
+        }
+    }
+
+    let class = versions.first().map_or("", |(_, java)| java.class.as_str()).replace('/', ".");
+
+    let mut output = format!(
+        r"//! This file is generated, do not modify it manually.
+//!
+//! TODO: Documentation
+#![allow(missing_docs)]
+
+/// `{class}`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum {name} {{
+"
+    );
+    for (variant, constant, args) in &variants {
+        if args.is_empty() {
+            writeln!(output, "    /// `{constant}`").unwrap();
+        } else {
+            writeln!(output, "    /// `{constant}({args})`").unwrap();
+        }
+        writeln!(output, "    {variant},").unwrap();
+    }
+    write!(
+        output,
+        r"}}
+
+/// The ordinal of a [`{name}`] in a version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct {name}Ordinal<V>(u32, core::marker::PhantomData<V>);
+
+impl<V> {name}Ordinal<V> {{
+    /// Create a [`{name}Ordinal`] from a raw ordinal.
+    #[must_use]
+    pub const fn new(ordinal: u32) -> Self {{ Self(ordinal, core::marker::PhantomData) }}
+
+    /// Get the raw ordinal.
+    #[must_use]
+    pub const fn get(self) -> u32 {{ self.0 }}
+}}
+"
+    )
+    .unwrap();
+
+    for (version, java) in versions {
+        write_conversions(&mut output, name, common, version, java, variants.len());
+    }
+
+    output
+}
+
+/// Write the ordinal conversions for a single version of a [`JavaEnum`].
+fn write_conversions(
+    output: &mut String,
+    name: &str,
+    common: &str,
+    version: &Version,
+    java: &JavaEnum,
+    variant_count: usize,
+) {
+    let version_ident =
+        format!("{common}::version::V{}", version.to_long_string().replace('.', "_"));
+    let ordinal = format!("{name}Ordinal<{version_ident}>");
+
+    // Use `From` if every variant exists in this version.
+    let total = java.constants.len() == variant_count;
+
+    let mut to_ordinal = String::new();
+    let mut from_ordinal = String::new();
+    for (index, constant) in java.constants.iter().enumerate() {
+        let variant = variant_name(&constant.name);
+        if total {
+            writeln!(to_ordinal, "            {name}::{variant} => Self::new({index}),")
+        } else {
+            writeln!(to_ordinal, "            {name}::{variant} => Ok(Self::new({index})),")
+        }
+        .unwrap();
+        writeln!(from_ordinal, "            {index} => Ok(Self::{variant}),").unwrap();
+    }
+
+    if total {
+        write!(
+            output,
+            r"
+impl From<{name}> for {ordinal} {{
+    fn from(value: {name}) -> Self {{
+        match value {{
+{to_ordinal}        }}
+    }}
+}}
+"
+        )
+        .unwrap();
+    } else {
+        write!(
+            output,
+            r"
+impl TryFrom<{name}> for {ordinal} {{
+    type Error = {name};
+
+    fn try_from(value: {name}) -> Result<Self, Self::Error> {{
+        match value {{
+{to_ordinal}            other => Err(other),
+        }}
+    }}
+}}
+"
+        )
+        .unwrap();
+    }
+
+    write!(
+        output,
+        r"
+impl TryFrom<{ordinal}> for {name} {{
+    type Error = u32;
+
+    fn try_from(ordinal: {ordinal}) -> Result<Self, Self::Error> {{
+        match ordinal.get() {{
+{from_ordinal}            other => Err(other),
+        }}
+    }}
+}}
+"
+    )
+    .unwrap();
+}
+
+/// Get the name of the Rust variant for a Java enum constant.
+pub(crate) fn variant_name(constant: &str) -> String {
+    constant.to_lowercase().to_case(Case::Pascal)
+}
+
+/// Format a [`Value`] the way it would appear in Java source.
+fn format_value(value: &Value) -> String {
+    match value {
+        Value::Constant(OwnedConstant::Integer(value)) => value.to_string(),
+        Value::Constant(OwnedConstant::Long(value)) => format!("{value}L"),
+        Value::Constant(OwnedConstant::Float(value)) => format!("{value:?}F"),
+        Value::Constant(OwnedConstant::Double(value)) => format!("{value:?}"),
+        Value::Constant(OwnedConstant::String(value)) => format!("{value:?}"),
+        Value::Null => String::from("null"),
+        Value::Class(class) => format!("{}.class", short_name(class)),
+        Value::Static(field) => format!("{}.{}", short_name(&field.class), field.name),
+        _ => String::from(".."),
+    }
+}
+
+fn short_name(class: &str) -> &str { class.rsplit(['/', '$']).next().unwrap_or(class) }
+
+#[test]
+#[cfg(test)]
+fn generate() {
+    use super::extract::EnumConstant;
+    use crate::class_helper::interpreter::FieldRef;
+
+    let constant = |name: &str, args: Vec<Value>| EnumConstant { name: name.to_string(), args };
+    let old = JavaEnum {
+        class: String::from("net/minecraft/util/Rarity"),
+        constants: vec![
+            constant("COMMON", vec![Value::Constant(OwnedConstant::String("common".into()))]),
+            constant(
+                "VERY_RARE",
+                vec![Value::Static(FieldRef {
+                    class: String::from("net/minecraft/util/Formatting"),
+                    name: String::from("AQUA"),
+                    descriptor: String::from("Lnet/minecraft/util/Formatting;"),
+                })],
+            ),
+        ],
+    };
+    let new = JavaEnum {
+        class: old.class.clone(),
+        constants: vec![constant("EPIC", Vec::new()), old.constants[0].clone()],
+    };
+
+    let output = generate_enum(
+        "Rarity",
+        "froglight_common",
+        &[(Version::new_release(1, 21, 4), &old), (Version::new_release(1, 21, 5), &new)],
+    );
+
+    assert!(output.contains("/// `net.minecraft.util.Rarity`\n"));
+    assert!(output.contains("    /// `COMMON(\"common\")`\n    Common,\n"));
+    assert!(output.contains("    /// `VERY_RARE(Formatting.AQUA)`\n    VeryRare,\n"));
+    assert!(output.contains("    /// `EPIC`\n    Epic,\n}\n"));
+    assert!(output.contains(
+        "impl TryFrom<Rarity> for RarityOrdinal<froglight_common::version::V1_21_4> {\n    \
+         type Error = Rarity;\n\n    \
+         fn try_from(value: Rarity) -> Result<Self, Self::Error> {\n        \
+         match value {\n            \
+         Rarity::Common => Ok(Self::new(0)),\n            \
+         Rarity::VeryRare => Ok(Self::new(1)),\n            \
+         other => Err(other),\n"
+    ));
+    assert!(output.contains(
+        "impl TryFrom<RarityOrdinal<froglight_common::version::V1_21_5>> for Rarity {\n    \
+         type Error = u32;\n\n    \
+         fn try_from(ordinal: RarityOrdinal<froglight_common::version::V1_21_5>) \
+         -> Result<Self, Self::Error> {\n        \
+         match ordinal.get() {\n            \
+         0 => Ok(Self::Epic),\n            \
+         1 => Ok(Self::Common),\n            \
+         other => Err(other),\n"
+    ));
+
+    // Versions with every variant can always be converted to an ordinal,
+    // and enums inside of `froglight-common` use the crate's own versions.
+    let output = generate_enum("Rarity", "crate", &[(Version::new_release(1, 21, 4), &old)]);
+    assert!(output.contains(
+        "impl From<Rarity> for RarityOrdinal<crate::version::V1_21_4> {\n    \
+         fn from(value: Rarity) -> Self {\n        \
+         match value {\n            \
+         Rarity::Common => Self::new(0),\n            \
+         Rarity::VeryRare => Self::new(1),\n        \
+         }\n"
+    ));
+}
@@ -0,0 +1,155 @@
+use std::path::{Path, PathBuf};
+
+use convert_case::{Case, Casing};
+use froglight_dependency::{
+    container::{Dependency, DependencyContainer},
+    version::Version,
+};
+use froglight_extract::module::ExtractModule;
+use serde::Deserialize;
+use tokio::sync::OnceCell;
+
+mod extract;
+pub(crate) use extract::{JavaEnum, JavaEnums};
+
+mod generate;
+pub(crate) use generate::variant_name;
+
+use crate::ToolConfig;
+
+#[derive(ExtractModule)]
+#[module(function = Enums::generate, config = EnumsConfig)]
+pub(crate) struct Enums;
+
+/// The `[modules.enums]` section of the configuration file.
+#[derive(Debug, Clone, PartialEq, Eq, Dependency, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct EnumsConfig {
+    /// The Java enums to generate.
+    pub(crate) generate: Vec<EnumConfig>,
+}
+
+/// A Java enum to generate a Rust enum for.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct EnumConfig {
+    /// The enum class, such as `net/minecraft/util/Rarity`.
+    pub(crate) class: String,
+    /// The path to the crate the enum is generated in.
+    pub(crate) path: PathBuf,
+    /// The name of the Rust enum.
+    pub(crate) name: String,
+}
+
+impl Default for EnumsConfig {
+    fn default() -> Self {
+        let generate = [
+            ("net/minecraft/entity/SpawnGroup", "crates/froglight-entity", "SpawnGroup"),
+            ("net/minecraft/util/Rarity", "crates/froglight-item", "ItemRarity"),
+            ("net/minecraft/util/Formatting", "crates/froglight-common", "Formatting"),
+            ("net/minecraft/util/math/Direction", "crates/froglight-common", "Direction"),
+        ];
+
+        Self {
+            generate: generate
+                .into_iter()
+                .map(|(class, path, name)| EnumConfig {
+                    class: class.to_string(),
+                    path: PathBuf::from(path),
+                    name: name.to_string(),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Enums {
+    async fn generate(_: &Version, deps: &mut DependencyContainer) -> anyhow::Result<()> {
+        static ONCE: OnceCell<anyhow::Result<()>> = OnceCell::const_new();
+        ONCE.get_or_init(async || {
+            let config = deps.get_or_retrieve::<EnumsConfig>().await?.clone();
+            for EnumConfig { class, path, name } in config.generate {
+                let directory = std::env::current_dir()?.join(&path);
+                if tokio::fs::try_exists(&directory).await? {
+                    Self::generate_enum(&class, &name, deps, &directory).await?;
+                } else {
+                    tracing::warn!(
+                        "Could not find the crate for \"{name}\" at \"{}\"",
+                        directory.display()
+                    );
+                }
+            }
+            Ok(())
+        })
+        .await
+        .as_ref()
+        .map_or_else(|e| Err(anyhow::anyhow!(e)), |()| Ok(()))
+    }
+
+    /// Generate a Rust enum for a Java enum across all versions.
+    async fn generate_enum(
+        class: &str,
+        name: &str,
+        deps: &mut DependencyContainer,
+        path: &Path,
+    ) -> anyhow::Result<()> {
+        deps.get_or_retrieve::<JavaEnums>().await?;
+
+        let mut versions = Vec::new();
+        for version in deps.get_or_retrieve::<ToolConfig>().await?.versions.clone() {
+            let java = deps
+                .scoped_fut::<JavaEnums, anyhow::Result<JavaEnum>>(
+                    async |enums: &mut JavaEnums, deps| {
+                        enums.get_class(&version, class, deps).await.cloned()
+                    },
+                )
+                .await?;
+            versions.push((version, java));
+        }
+
+        // Inside of `froglight-common` its versions are referenced through `crate`.
+        let common = if path.file_name().is_some_and(|name| name == "froglight-common") {
+            "crate"
+        } else {
+            "froglight_common"
+        };
+
+        let versions = versions.iter().map(|(v, java)| (v.clone(), java)).collect::<Vec<_>>();
+        let module = name.to_case(Case::Snake);
+        let generated = path.join("src/generated");
+        tokio::fs::create_dir_all(&generated).await?;
+        tokio::fs::write(
+            generated.join(format!("{module}.rs")),
+            generate::generate_enum(name, common, &versions),
+        )
+        .await?;
+
+        Self::register_module(&generated, &module).await
+    }
+
+    /// Declare a module in the `mod.rs` of a `generated` directory,
+    /// if it is not already declared.
+    async fn register_module(generated: &Path, module: &str) -> anyhow::Result<()> {
+        let path = generated.join("mod.rs");
+        let mut contents = if tokio::fs::try_exists(&path).await? {
+            tokio::fs::read_to_string(&path).await?
+        } else {
+            String::new()
+        };
+
+        let declaration = format!("mod {module};");
+        if contents.lines().any(|line| line.trim().trim_start_matches("pub ") == declaration) {
+            return Ok(());
+        }
+
+        if !contents.is_empty() && !contents.ends_with('\n') {
+            contents.push('\n');
+        }
+        contents.push_str("pub ");
+        contents.push_str(&declaration);
+        contents.push('\n');
+        tokio::fs::write(path, contents).await?;
+
+        Ok(())
+    }
+}
@@ -1,10 +1,15 @@
 #![expect(unused_imports)]
 
-use std::{collections::HashSet, fmt::Write, path::Path, sync::Once};
+use std::{
+    collections::HashSet,
+    fmt::Write,
+    path::{Path, PathBuf},
+    sync::Once,
+};
 
 use convert_case::{Case, Casing};
 use froglight_dependency::{
-    container::DependencyContainer,
+    container::{Dependency, DependencyContainer},
     dependency::{
         minecraft::{DataGenerator, PumpkinExtractor, Translations, TranslationsFile},
         vineflower::DecompiledJar,
@@ -12,6 +17,7 @@ use froglight_dependency::{
     version::Version,
 };
 use froglight_extract::module::ExtractModule;
+use serde::Deserialize;
 use tokio::sync::OnceCell;
 use tracing::Level;
 
@@ -19,16 +25,38 @@ mod report;
 pub(crate) use report::{ItemReport, ItemReports};
 
 use super::ToolConfig;
-use crate::module::block::BlockReports;
+use crate::module::{
+    block::{BlockReports, Blocks},
+    enums::{Enums, variant_name},
+};
 
 #[derive(ExtractModule)]
-#[module(function = Items::generate)]
+#[module(
+    function = Items::generate,
+    depends_on = [Blocks, Enums],
+    requires = [BlockReports],
+    config = ItemsConfig
+)]
 pub(crate) struct Items;
 
+/// The `[modules.items]` section of the configuration file.
+#[derive(Debug, Clone, PartialEq, Eq, Dependency, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct ItemsConfig {
+    /// The path to the `froglight-item` crate.
+    pub(crate) path: PathBuf,
+    /// The width item names are padded to.
+    pub(crate) name_padding: usize,
+}
+
+impl Default for ItemsConfig {
+    fn default() -> Self { Self { path: PathBuf::from("crates/froglight-item"), name_padding: 36 } }
+}
+
 impl Items {
     async fn generate(version: &Version, deps: &mut DependencyContainer) -> anyhow::Result<()> {
-        let mut directory = std::env::current_dir()?;
-        directory.push("crates/froglight-item");
+        let config = deps.get_or_retrieve::<ItemsConfig>().await?;
+        let directory = std::env::current_dir()?.join(&config.path);
 
         if !tokio::fs::try_exists(&directory).await? {
             anyhow::bail!("Could not find \"froglight-item\" at \"{}\"", directory.display());
@@ -127,8 +155,6 @@ froglight_macros::items! {{
 }
 
 impl Items {
-    const ITEM_NAME_PADDING: usize = 36;
-
     /// Generate item trait implementations.
     async fn generate_item_traits(
         version: &Version,
@@ -138,6 +164,8 @@ impl Items {
         let version_ident =
             format!("froglight_common::version::V{}", version.to_long_string().replace('.', "_"));
 
+        let name_padding = deps.get_or_retrieve::<ItemsConfig>().await?.name_padding;
+
         let report = deps.get::<ItemReports>().unwrap();
         let report = report.version(version).unwrap();
 
@@ -152,14 +180,14 @@ impl Items {
                 acc.push_str(&item_name);
 
                 // Pad the item name with spaces.
-                for _ in 0..Self::ITEM_NAME_PADDING.saturating_sub(item_name.len()) {
+                for _ in 0..name_padding.saturating_sub(item_name.len()) {
                     acc.push(' ');
                 }
 
                 // Add the item properties.
                 let item_properties = format!(
-                    " => {{ properties: {{ ident: \"{item}\", rarity: ItemRarity::{:?} }}",
-                    entry.rarity
+                    " => {{ properties: {{ ident: \"{item}\", rarity: ItemRarity::{} }}",
+                    variant_name(&entry.rarity)
                 );
                 acc.push_str(&item_properties);
 
@@ -185,7 +213,7 @@ impl Items {
 #![allow(missing_docs)]
 
 #[allow(clippy::wildcard_imports)]
-use crate::{{generated::item::*, item::ItemRarity}};
+use crate::generated::{{item::*, item_rarity::ItemRarity}};
 
 froglight_macros::item_properties! {{
     path = crate,
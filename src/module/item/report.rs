@@ -1,18 +1,49 @@
 use std::{collections::HashMap, ops::RangeInclusive, path::Path};
 
-use cafebabe::{
-    bytecode::Opcode,
-    constant_pool::{LiteralConstant, Loadable, MemberRef, NameAndType},
-    ClassFile,
-};
 use froglight_dependency::{
     container::{Dependency, DependencyContainer},
-    dependency::minecraft::{minecraft_code::CodeBundle, DataGenerator, MinecraftCode},
+    dependency::minecraft::{minecraft_code::Classes, DataGenerator, MinecraftCode},
     version::Version,
 };
 use indexmap::IndexMap;
 
-use crate::class_helper::ClassHelper;
+use super::Items;
+use crate::{
+    class_helper::{ClassHelper, Diagnostic},
+    module::{
+        enums::{JavaEnum, JavaEnums},
+        Diagnostics, Symbols,
+    },
+};
+
+/// The rarity of items that do not set one.
+const DEFAULT_RARITY: &str = "COMMON";
+
+/// The classes used to build an [`ItemReport`], resolved for a version.
+struct ItemSymbols {
+    /// `Items`
+    items: String,
+    /// The type of fields in `Items`.
+    item_descriptor: String,
+    /// `Blocks`
+    blocks: String,
+    /// The type of fields in `Blocks`.
+    block_descriptor: String,
+    /// The Java enum of item rarities.
+    rarity: String,
+}
+
+impl ItemSymbols {
+    fn new(symbols: &Symbols, version: &Version) -> anyhow::Result<Self> {
+        Ok(Self {
+            items: symbols.require("items", version)?.to_string(),
+            item_descriptor: symbols.require_descriptor("item", version)?,
+            blocks: symbols.require("blocks", version)?.to_string(),
+            block_descriptor: symbols.require_descriptor("block", version)?,
+            rarity: symbols.require("rarity", version)?.to_string(),
+        })
+    }
+}
 
 /// A collection of [`ItemReport`]s.
 #[derive(Default, Dependency)]
@@ -36,109 +67,118 @@ impl ItemReports {
         deps: &mut DependencyContainer,
     ) -> anyhow::Result<&ItemReport> {
         if !self.0.contains_key(version) {
+            let mut diagnostics = Vec::new();
+            let names = ItemSymbols::new(deps.get_or_retrieve::<Symbols>().await?, version)?;
+
+            deps.get_or_retrieve::<JavaEnums>().await?;
+            let rarities = deps
+                .scoped_fut::<JavaEnums, anyhow::Result<JavaEnum>>(
+                    async |enums: &mut JavaEnums, deps| {
+                        enums.get_class(version, &names.rarity, deps).await.cloned()
+                    },
+                )
+                .await?;
+
             deps.get_or_retrieve::<MinecraftCode>().await?;
             deps.scoped_fut::<MinecraftCode, anyhow::Result<()>>(
                 async |code: &mut MinecraftCode, deps| {
-                    let bundle = code.get_version(version, deps).await?;
-                    self.0.insert(version.clone(), Self::parse_class(bundle).await?);
+                    let bundle = code.get_side(version, Items::CODE_SIDE, deps).await?;
+                    let report = bundle.with_classes(|classes| {
+                        Self::parse_class(classes, &names, &rarities, &mut diagnostics)
+                    })?;
+                    self.0.insert(version.clone(), report);
                     Ok(())
                 },
             )
             .await?;
+
+            Diagnostics::report(Items::MODULE_NAME, version, diagnostics, deps).await?;
         }
 
         Ok(self.version(version).unwrap())
     }
 
-    #[expect(clippy::unused_async)]
-    async fn parse_class(classes: &CodeBundle) -> anyhow::Result<ItemReport> {
+    fn parse_class(
+        classes: &Classes<'_>,
+        names: &ItemSymbols,
+        rarities: &JavaEnum,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> anyhow::Result<ItemReport> {
         let items = classes
-            .get("net/minecraft/item/Items")
-            .ok_or_else(|| anyhow::anyhow!("Could not find class \"net/minecraft/item/Items\"!"))?;
-        let blocks = classes.get("net/minecraft/block/Blocks").ok_or_else(|| {
-            anyhow::anyhow!("Could not find class \"net/minecraft/block/Blocks\"!")
-        })?;
+            .get(&names.items)
+            .ok_or_else(|| anyhow::anyhow!("Could not find class \"{}\"!", names.items))?;
+        let blocks = classes
+            .get(&names.blocks)
+            .ok_or_else(|| anyhow::anyhow!("Could not find class \"{}\"!", names.blocks))?;
+
+        // Collect the identifier of every block once, by field name.
+        let block_names: HashMap<String, String> = blocks
+            .class_registrations(classes, Some(&names.block_descriptor))
+            .into_iter()
+            .filter_map(|registration| Some((registration.field.name, registration.identifier?)))
+            .collect();
 
         let mut report = ItemReport::default();
-
-        let mut name = Option::<String>::None;
-        let mut rarity = ItemRarity::default();
-
-        let initial = items.class_code().bytecode.as_ref().unwrap();
-        let initial = initial.opcodes.iter().map(|(_, opcode)| opcode).collect::<Vec<_>>();
-        items.iter_code_recursive(&initial, classes, |op| match op {
-            Opcode::Getstatic(MemberRef { class_name, name_and_type }) => {
-                if name.is_none() && name_and_type.descriptor == "Lnet/minecraft/block/Block;" {
-                    name = Some(Self::block_name(&blocks, name_and_type));
-                }
-
-                if class_name == "net/minecraft/util/Rarity" {
-                    match name_and_type.name.as_ref() {
-                        "COMMON" => rarity = ItemRarity::Common,
-                        "UNCOMMON" => rarity = ItemRarity::Uncommon,
-                        "RARE" => rarity = ItemRarity::Rare,
-                        "EPIC" => rarity = ItemRarity::Epic,
-                        unk => panic!("Unknown rarity: \"{unk}\""),
-                    }
-                }
-            }
-            Opcode::LdcW(Loadable::LiteralConstant(LiteralConstant::String(string))) => {
+        for registration in items.class_registrations(classes, Some(&names.item_descriptor)) {
+            let block =
+                registration.statics.iter().find(|f| f.descriptor == names.block_descriptor);
+            let name = if let Some(identifier) = registration.identifier.clone() {
+                Some(identifier)
+            } else if let Some(block) = block {
+                let name = block_names.get(&block.name).cloned();
                 if name.is_none() {
-                    name = Some(string.to_string());
+                    diagnostics.push(
+                        Diagnostic::new(format!(
+                            "Could not find name of block \"{}\" for item \"{}\"",
+                            block.name, registration.field.name
+                        ))
+                        .at_registration(items, &registration),
+                    );
                 }
-            }
-            Opcode::Putstatic(MemberRef { class_name, name_and_type }) => {
-                if name_and_type.descriptor == "Lnet/minecraft/item/Item;"
-                    && **class_name == *items.this_class
-                {
-                    let name = name.take().expect("Could not find name!");
-                    let rarity = std::mem::take(&mut rarity);
-
-                    report.0.insert(format!("minecraft:{name}"), ItemReportEntry { rarity });
+                name
+            } else {
+                None
+            };
+
+            let rarity = match registration.statics.iter().find(|f| f.class == names.rarity) {
+                Some(rarity) if rarities.constants.iter().any(|c| c.name == rarity.name) => {
+                    rarity.name.clone()
                 }
-            }
-            _ => {}
-        });
-
-        Ok(report)
-    }
-
-    fn block_name(blocks: &ClassFile, block: &NameAndType) -> String {
-        let mut name = Option::<String>::None;
-
-        let code = blocks.class_code().bytecode.as_ref().unwrap();
-        for (_, op) in &code.opcodes {
-            match op {
-                Opcode::LdcW(Loadable::LiteralConstant(LiteralConstant::String(string))) => {
-                    name = Some(string.to_string());
-                }
-                Opcode::Putstatic(MemberRef { name_and_type, .. }) => {
-                    if name_and_type.name == block.name {
-                        return name.expect("Created block but did not find name!");
-                    }
+                Some(rarity) => {
+                    diagnostics.push(
+                        Diagnostic::new(format!(
+                            "Unknown rarity \"{}\" for item \"{}\"",
+                            rarity.name, registration.field.name
+                        ))
+                        .at_registration(items, &registration),
+                    );
+                    DEFAULT_RARITY.to_string()
                 }
-
-                _ => {}
+                None => DEFAULT_RARITY.to_string(),
+            };
+
+            if let Some(name) = name {
+                report.0.insert(format!("minecraft:{name}"), ItemReportEntry { rarity });
+            } else {
+                diagnostics.push(
+                    Diagnostic::new(format!(
+                        "Could not find name of item \"{}\"",
+                        registration.field.name
+                    ))
+                    .at_registration(items, &registration),
+                );
             }
         }
 
-        panic!("Could not find block name!")
+        Ok(report)
     }
 }
 
 #[derive(Debug, Default, Clone, PartialEq, Eq)]
 pub(crate) struct ItemReport(pub IndexMap<String, ItemReportEntry>);
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) struct ItemReportEntry {
-    pub(crate) rarity: ItemRarity,
-}
-
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
-pub(crate) enum ItemRarity {
-    #[default]
-    Common,
-    Uncommon,
-    Rare,
-    Epic,
+    /// The `Rarity` constant of the item, such as `EPIC`.
+    pub(crate) rarity: String,
 }
@@ -1,5 +1,17 @@
 mod config;
-pub(crate) use config::ToolConfig;
+pub(crate) use config::{ToolArgs, ToolConfig};
+
+mod diagnostics;
+pub(crate) use diagnostics::{DiagnosticPolicy, Diagnostics};
+
+mod renames;
+pub(crate) use renames::Renames;
+
+mod source_diff;
+pub(crate) use source_diff::{DiffFormat, SourceDiff};
+
+mod symbols;
+pub(crate) use symbols::Symbols;
 
 mod block;
 pub(crate) use block::Blocks;
@@ -7,6 +19,9 @@ pub(crate) use block::Blocks;
 mod entity;
 pub(crate) use entity::Entities;
 
+mod enums;
+pub(crate) use enums::Enums;
+
 mod item;
 pub(crate) use item::Items;
 
@@ -5,13 +5,16 @@ use cafebabe::{
 };
 use froglight_dependency::{
     container::DependencyContainer,
-    dependency::minecraft::{minecraft_code::CodeBundle, MinecraftCode},
+    dependency::minecraft::{minecraft_code::Classes, MinecraftCode},
     version::Version,
 };
 use indexmap::IndexMap;
 
 use super::Packets;
-use crate::class_helper::ClassHelper;
+use crate::{
+    class_helper::{inline::InlineOptions, ClassHelper, Diagnostic},
+    module::{Diagnostics, Symbols},
+};
 
 impl Packets {
     pub(super) async fn extract_packet_classes(
@@ -21,51 +24,70 @@ impl Packets {
         const STATE_PATH: &str = "net/minecraft/network/state/";
 
         let mut states = IndexMap::new();
+        let mut diagnostics = Vec::new();
 
         deps.get_or_retrieve::<MinecraftCode>().await?;
         deps.scoped_fut::<MinecraftCode, anyhow::Result<()>>(
             async |code: &mut MinecraftCode, deps| {
-                let bundle = code.get_version(version, deps).await?;
-
-                for class in
-                    bundle.get_filter(|(c, _)| c.starts_with(STATE_PATH) && !c.contains('$'))
-                {
-                    if class.methods.iter().any(|m| m.name == "<clinit>") {
-                        states.extend_one(Self::extract_state_packets(class, bundle).await?);
+                let bundle = code.get_side(version, Self::CODE_SIDE, deps).await?;
+                let symbols = deps.get_or_retrieve::<Symbols>().await?;
+
+                bundle.with_classes(|classes| {
+                    for class in
+                        classes.get_filter(|c| c.starts_with(STATE_PATH) && !c.contains('$'))
+                    {
+                        if class.methods.iter().any(|m| m.name == "<clinit>")
+                            && let Some(state) = Self::extract_state_packets(
+                                class,
+                                classes,
+                                version,
+                                symbols,
+                                &mut diagnostics,
+                            )?
+                        {
+                            states.extend_one(state);
+                        }
                     }
-                }
 
-                Ok(())
+                    Ok(())
+                })
             },
         )
         .await?;
 
+        Diagnostics::report(Self::MODULE_NAME, version, diagnostics, deps).await?;
         Ok(states)
     }
 
-    async fn extract_state_packets(
-        class: ClassFile<'_>,
-        classes: &CodeBundle,
-    ) -> anyhow::Result<(String, NetworkState)> {
-        const NETWORK_PHASE: &str = "net/minecraft/network/NetworkPhase";
-
-        // -= 1.21.4
-        const NETWORK_STATE_BUILDER_OLD: &str = "net/minecraft/network/NetworkStateBuilder";
-        // += 1.21.5
-        const NETWORK_STATE_BUILDER: &str = "net/minecraft/network/state/NetworkStateBuilder";
-
-        // -= 1.21.4
-        const NETWORK_STATE_FACTORY_DESCRIPTOR_OLD: &str =
-            "Lnet/minecraft/network/NetworkState$Factory;";
-        // += 1.21.5
-        const NETWORK_STATE_FACTORY_DESCRIPTOR: &str =
-            "Lnet/minecraft/network/state/NetworkStateFactory;";
-        // += 1.21.5
-        const AWARE_NETWORK_STATE_FACTORY_DESCRIPTOR: &str =
-            "Lnet/minecraft/network/state/ContextAwareNetworkStateFactory;";
-
-        const PACKET_CODEC_DESCRIPTOR: &str = "Lnet/minecraft/network/codec/PacketCodec;";
-        const PACKET_TYPE_DESCRIPTOR: &str = "Lnet/minecraft/network/packet/PacketType;";
+    #[expect(clippy::too_many_lines)]
+    fn extract_state_packets(
+        class: &ClassFile<'_>,
+        classes: &Classes<'_>,
+        version: &Version,
+        symbols: &Symbols,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> anyhow::Result<Option<(String, NetworkState)>> {
+        let required = |symbol: &str| {
+            symbols.get(symbol, version).ok_or_else(|| {
+                anyhow::anyhow!("PacketStateBuilder: Could not resolve \"{symbol}\" for {version}")
+            })
+        };
+
+        let network_phase = required("network_phase")?;
+        let network_state_builder = required("network_state_builder")?;
+        let bundle_delimiter = required("bundle_delimiter_packet")?;
+        let packet_codec_descriptor = format!("L{};", required("packet_codec")?);
+        let packet_type_descriptor = format!("L{};", required("packet_type")?);
+
+        let s2c = symbols.get("network_state_builder_s2c", version);
+        let c2s = ["network_state_builder_c2s", "network_state_builder_context_aware_c2s"]
+            .into_iter()
+            .filter_map(|symbol| symbols.get(symbol, version))
+            .collect::<Vec<_>>();
+        let factory_descriptors = ["network_state_factory", "context_aware_network_state_factory"]
+            .into_iter()
+            .filter_map(|symbol| symbols.descriptor(symbol, version))
+            .collect::<Vec<_>>();
 
         let mut state_name = Option::None;
         let mut state = NetworkState::default();
@@ -76,44 +98,52 @@ impl Packets {
 
         let initial = class.class_code().bytecode.as_ref().unwrap();
         let initial = initial.opcodes.iter().map(|(_, opcode)| opcode).collect::<Vec<_>>();
-        class.iter_code_recursive(&initial, classes, |op| match op {
+        class.iter_code_recursive(&initial, classes, InlineOptions::NONE, |op| match op {
             Opcode::Invokestatic(MemberRef { class_name, name_and_type })
-                if (class_name == NETWORK_STATE_BUILDER_OLD
-                    || class_name == NETWORK_STATE_BUILDER) =>
+                if class_name == network_state_builder =>
             {
                 match name_and_type.name.as_ref() {
-                    "s2c" => dir_name = Some("s2c"),
-                    "c2s" | "contextAwareC2S" => dir_name = Some("c2s"),
-                    unk => panic!("PacketStateBuilder: Unknown method `{unk}`!"),
+                    name if s2c == Some(name) => dir_name = Some("s2c"),
+                    name if c2s.contains(&name) => dir_name = Some("c2s"),
+                    unk => diagnostics.push(
+                        Diagnostic::new(format!("PacketStateBuilder: Unknown method `{unk}`!"))
+                            .at(class, op),
+                    ),
                 }
             }
             Opcode::Getstatic(MemberRef { class_name, name_and_type })
-                if class_name == NETWORK_PHASE =>
+                if class_name == network_phase =>
             {
                 state_name = Some(Self::phase_name(&name_and_type.name.to_lowercase()));
             }
             Opcode::Getstatic(MemberRef { class_name, name_and_type })
-                if name_and_type.descriptor == PACKET_TYPE_DESCRIPTOR =>
+                if name_and_type.descriptor == packet_type_descriptor =>
             {
-                packet_name = Some(Self::packet_name(class_name, &name_and_type.name, classes));
+                packet_name =
+                    Self::packet_name(class_name, &name_and_type.name, classes, diagnostics);
             }
             Opcode::Getstatic(MemberRef { class_name, name_and_type })
-                if name_and_type.descriptor == PACKET_CODEC_DESCRIPTOR =>
+                if name_and_type.descriptor == packet_codec_descriptor =>
             {
-                match core::mem::take(&mut packet_name) {
-                    Some(name) => packets.insert(
+                if let Some(name) = core::mem::take(&mut packet_name) {
+                    packets.insert(
                         format!("minecraft:{name}"),
                         PacketClass {
                             class: class_name.to_string(),
                             codec: Some(name_and_type.name.to_string()),
                         },
-                    ),
-                    None => panic!("PacketStateBuilder: Could not find packet name!"),
-                };
+                    );
+                } else {
+                    diagnostics.push(
+                        Diagnostic::new(format!(
+                            "PacketStateBuilder: Could not find packet name for `{class_name}`!"
+                        ))
+                        .at(class, op),
+                    );
+                }
             }
             Opcode::Invokespecial(MemberRef { class_name, .. })
-                if class_name
-                    == "net/minecraft/network/packet/s2c/play/BundleDelimiterS2CPacket" =>
+                if class_name == bundle_delimiter =>
             {
                 packets.insert(
                     String::from("minecraft:bundle"),
@@ -121,21 +151,29 @@ impl Packets {
                 );
             }
             Opcode::Putstatic(MemberRef { name_and_type, .. })
-                if (name_and_type.descriptor == NETWORK_STATE_FACTORY_DESCRIPTOR_OLD
-                    || name_and_type.descriptor == NETWORK_STATE_FACTORY_DESCRIPTOR
-                    || name_and_type.descriptor == AWARE_NETWORK_STATE_FACTORY_DESCRIPTOR) =>
+                if factory_descriptors.iter().any(|d| name_and_type.descriptor == d.as_str()) =>
             {
                 match dir_name {
                     Some("c2s") => state.c2s = core::mem::take(&mut packets),
                     Some("s2c") => state.s2c = core::mem::take(&mut packets),
                     Some(..) => unreachable!(),
-                    None => panic!("PacketStateBuilder: Directory name is None!"),
+                    None => diagnostics.push(
+                        Diagnostic::new("PacketStateBuilder: Directory name is None!")
+                            .at(class, op),
+                    ),
                 }
             }
             _ => {}
         });
 
-        Ok((state_name.expect("PacketStateBuilder: Name is None!"), state))
+        if let Some(state_name) = state_name {
+            Ok(Some((state_name, state)))
+        } else {
+            diagnostics.push(
+                Diagnostic::new("PacketStateBuilder: Name is None!").class(&class.this_class),
+            );
+            Ok(None)
+        }
     }
 
     fn phase_name(state_name: &str) -> String {
@@ -146,9 +184,17 @@ impl Packets {
         }
     }
 
-    fn packet_name(class_name: &str, field_name: &str, classes: &CodeBundle) -> String {
+    fn packet_name(
+        class_name: &str,
+        field_name: &str,
+        classes: &Classes<'_>,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> Option<String> {
         let Some(class) = classes.get(class_name) else {
-            panic!("PacketStateBuilder: Could not find class `{class_name}`!");
+            diagnostics.push(Diagnostic::new(format!(
+                "PacketStateBuilder: Could not find class `{class_name}`!"
+            )));
+            return None;
         };
 
         let mut temp_constant = Option::<String>::None;
@@ -156,7 +202,7 @@ impl Packets {
 
         let initial = class.class_code().bytecode.as_ref().unwrap();
         let initial = initial.opcodes.iter().map(|(_, opcode)| opcode).collect::<Vec<_>>();
-        class.iter_code_recursive(&initial, classes, |op| match op {
+        class.iter_code_recursive(&initial, classes, InlineOptions::NONE, |op| match op {
             Opcode::Ldc(Loadable::LiteralConstant(LiteralConstant::String(constant)))
             | Opcode::LdcW(Loadable::LiteralConstant(LiteralConstant::String(constant)))
             | Opcode::Ldc2W(Loadable::LiteralConstant(LiteralConstant::String(constant)))
@@ -173,7 +219,15 @@ impl Packets {
             _ => {}
         });
 
-        constant.expect("PacketStateBuilder: Could not find Packet identifier!")
+        if constant.is_none() {
+            diagnostics.push(
+                Diagnostic::new(format!(
+                    "PacketStateBuilder: Could not find Packet identifier for `{field_name}`!"
+                ))
+                .class(class_name),
+            );
+        }
+        constant
     }
 }
 
@@ -8,13 +8,17 @@ use cafebabe::{
 use derive_more::Deref;
 use froglight_dependency::{
     container::{Dependency, DependencyContainer},
-    dependency::minecraft::{MinecraftCode, minecraft_code::CodeBundle},
+    dependency::minecraft::{MinecraftCode, minecraft_code::Classes},
     version::Version,
 };
 use indexmap::IndexMap;
 
 use super::Packets;
-use crate::{ToolConfig, class_helper::ClassHelper, module::packet::classes::NetworkState};
+use crate::{
+    ToolConfig,
+    class_helper::{ClassHelper, Diagnostic, inline::InlineOptions},
+    module::{Diagnostics, packet::classes::NetworkState},
+};
 
 #[derive(Clone, PartialEq, Dependency)]
 #[dep(retrieve = VersionCodecs::generate)]
@@ -54,17 +58,23 @@ impl Packets {
     ) -> anyhow::Result<IndexMap<String, NetworkPackets>> {
         let classes = Self::extract_packet_classes(version, deps).await?;
         let mut codecs = IndexMap::with_capacity(classes.len());
+        let mut diagnostics = Vec::new();
 
         for (name, state) in classes {
-            codecs.insert(name, Self::parse_state_classes(state, version, deps).await?);
+            codecs.insert(
+                name,
+                Self::parse_state_classes(state, version, &mut diagnostics, deps).await?,
+            );
         }
 
+        Diagnostics::report(Self::MODULE_NAME, version, diagnostics, deps).await?;
         Ok(codecs)
     }
 
     async fn parse_state_classes(
         state: NetworkState,
         version: &Version,
+        diagnostics: &mut Vec<Diagnostic>,
         deps: &mut DependencyContainer,
     ) -> anyhow::Result<NetworkPackets> {
         let mut packets = NetworkPackets::default();
@@ -72,29 +82,39 @@ impl Packets {
         deps.get_or_retrieve::<MinecraftCode>().await?;
         deps.scoped_fut::<MinecraftCode, anyhow::Result<()>>(
             async |code: &mut MinecraftCode, deps| {
-                let bundle = code.get_version(version, deps).await?;
-
-                for (ident, packet) in state.c2s {
-                    if let Some(class) = bundle.get(&packet.class) {
-                        packets.c2s.insert(
-                            ident,
-                            Self::parse_class(class, packet.codec.as_deref(), bundle),
-                        );
-                    } else {
-                        panic!("PacketCodecBuilder: Missing class \"{}\"", packet.class);
+                let bundle = code.get_side(version, Self::CODE_SIDE, deps).await?;
+
+                bundle.with_classes(|classes| {
+                    for (ident, packet) in state.c2s {
+                        if let Some(class) = classes.get(&packet.class) {
+                            let codec = packet.codec.as_deref();
+                            packets.c2s.insert(
+                                ident,
+                                Self::parse_class(class, codec, classes, diagnostics),
+                            );
+                        } else {
+                            diagnostics.push(Diagnostic::new(format!(
+                                "PacketCodecBuilder: Missing class \"{}\"",
+                                packet.class
+                            )));
+                        }
                     }
-                }
 
-                for (ident, packet) in state.s2c {
-                    if let Some(class) = bundle.get(&packet.class) {
-                        packets.s2c.insert(
-                            ident,
-                            Self::parse_class(class, packet.codec.as_deref(), bundle),
-                        );
-                    } else {
-                        panic!("PacketCodecBuilder: Missing class \"{}\"", packet.class);
+                    for (ident, packet) in state.s2c {
+                        if let Some(class) = classes.get(&packet.class) {
+                            let codec = packet.codec.as_deref();
+                            packets.s2c.insert(
+                                ident,
+                                Self::parse_class(class, codec, classes, diagnostics),
+                            );
+                        } else {
+                            diagnostics.push(Diagnostic::new(format!(
+                                "PacketCodecBuilder: Missing class \"{}\"",
+                                packet.class
+                            )));
+                        }
                     }
-                }
+                });
 
                 Ok(())
             },
@@ -104,8 +124,14 @@ impl Packets {
         Ok(packets)
     }
 
-    fn parse_class(class: ClassFile<'_>, codec: Option<&str>, classes: &CodeBundle) -> PacketInfo {
+    fn parse_class(
+        class: &ClassFile<'_>,
+        codec: Option<&str>,
+        classes: &Classes<'_>,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> PacketInfo {
         let mut fields = IndexMap::new();
+        let mut followed = true;
 
         if let Some(codec) = codec {
             let mut codec_type = CodecType::None;
@@ -116,9 +142,9 @@ impl Packets {
             if let Some(initial) = class.class_optional_method_code("<clinit>") {
                 let initial = initial.bytecode.as_ref().unwrap();
                 let initial = initial.opcodes.iter().map(|(_, opcode)| opcode).collect::<Vec<_>>();
-                class.iter_code_recursive(&initial, classes, |op| {
+                class.iter_code_recursive(&initial, classes, InlineOptions::NONE, |op| {
                     Self::handle_opcode(
-                        &class,
+                        class,
                         op,
                         codec,
                         &mut codec_type,
@@ -126,31 +152,65 @@ impl Packets {
                         &mut fields,
                         &mut last_fields,
                         classes,
-                    )
+                        diagnostics,
+                    );
                 });
 
                 if codec_type != CodecType::Done {
-                    panic!(
-                        "PacketCodecBuilder: No Codec found for class \"{}\": {codec_type:?}, {direction:?}",
-                        class.this_class
+                    followed = false;
+                    diagnostics.push(
+                        Diagnostic::new(format!(
+                            "PacketCodecBuilder: No Codec found for class \"{}\": {codec_type:?}, {direction:?}",
+                            class.this_class
+                        ))
+                        .class(&class.this_class),
                     );
                 }
             }
         }
 
+        // Use placeholders for the fields of the class if the codec could not be
+        // followed
+        if !followed && fields.is_empty() {
+            let layout = class.class_layout();
+            if !layout.fields.is_empty() {
+                diagnostics.push(
+                    Diagnostic::new(format!(
+                        "PacketCodecBuilder: Using placeholders for the fields of class \"{}\": {}",
+                        class.this_class,
+                        layout
+                            .fields
+                            .iter()
+                            .map(|f| f.name.as_str())
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ))
+                    .class(&class.this_class),
+                );
+            }
+            for field in layout.fields {
+                fields.insert(field.name, PacketField::Other(field.ty.rust_type()));
+            }
+        }
+
         PacketInfo { class: class.this_class.to_string(), fields }
     }
 
-    fn parse_class_method(class_name: &str, method_name: &str, classes: &CodeBundle) -> PacketInfo {
+    fn parse_class_method(
+        class_name: &str,
+        method_name: &str,
+        classes: &Classes<'_>,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> PacketInfo {
         let mut fields = IndexMap::new();
 
         if let Some(class) = classes.get(class_name) {
             if let Some(code) = class.class_optional_method_code(method_name) {
                 let initial = code.bytecode.as_ref().unwrap();
                 let initial = initial.opcodes.iter().map(|(_, opcode)| opcode).collect::<Vec<_>>();
-                class.iter_code_recursive(&initial, classes, |op| {
+                class.iter_code_recursive(&initial, classes, InlineOptions::NONE, |op| {
                     Self::handle_opcode(
-                        &class,
+                        class,
                         op,
                         method_name,
                         &mut CodecType::None,
@@ -158,7 +218,8 @@ impl Packets {
                         &mut fields,
                         &mut IndexMap::new(),
                         classes,
-                    )
+                        diagnostics,
+                    );
                 });
             }
         }
@@ -166,6 +227,7 @@ impl Packets {
         PacketInfo { class: class_name.to_string(), fields }
     }
 
+    #[expect(clippy::too_many_arguments, clippy::too_many_lines)]
     fn handle_opcode(
         class: &ClassFile<'_>,
         op: &Opcode<'_>,
@@ -174,7 +236,8 @@ impl Packets {
         direction: &mut CodecDirection,
         fields: &mut IndexMap<String, PacketField>,
         last_fields: &mut IndexMap<String, (String, String)>,
-        classes: &CodeBundle,
+        classes: &Classes<'_>,
+        diagnostics: &mut Vec<Diagnostic>,
     ) {
         const PACKET_BYTE_BUF_TYPE: &str = "net/minecraft/network/PacketByteBuf";
         const REGISTRY_BYTE_BUF_TYPE: &str = "net/minecraft/network/RegistryByteBuf";
@@ -191,7 +254,6 @@ impl Packets {
         const VALUE_FIRST_ENCODER_DESCRIPTOR: &str =
             "()Lnet/minecraft/network/codec/ValueFirstEncoder;";
 
-        // println!("{}: {op:?}", class.this_class);
         match op {
             Opcode::Getfield(MemberRef { name_and_type, .. })
             | Opcode::Putfield(MemberRef { name_and_type, .. })
@@ -218,9 +280,12 @@ impl Packets {
                         match name_and_type.name.as_ref() {
                             "encode" => *direction = CodecDirection::Encode,
                             "decode" => *direction = CodecDirection::Decode,
-                            unk => {
-                                panic!("PacketCodec: Unknown ValueFirst method \"{unk}\"")
-                            }
+                            unk => diagnostics.push(
+                                Diagnostic::new(format!(
+                                    "PacketCodec: Unknown ValueFirst method \"{unk}\""
+                                ))
+                                .at(class, op),
+                            ),
                         }
                     }
                     ENCODER_DESCRIPTOR if name_and_type.name == "encode" => {
@@ -232,7 +297,10 @@ impl Packets {
                         *direction = CodecDirection::Decode;
                     }
                     PACKET_CODEC_RESULT_FUNCTION_DESCRIPTOR if name_and_type.name == "apply" => {}
-                    unk => panic!("PacketCodec: Unknown encoder type \"{unk}\""),
+                    unk => diagnostics.push(
+                        Diagnostic::new(format!("PacketCodec: Unknown encoder type \"{unk}\""))
+                            .at(class, op),
+                    ),
                 }
             }
             Opcode::Invokevirtual(MemberRef { class_name, name_and_type })
@@ -331,9 +399,12 @@ impl Packets {
                         if name_and_type.descriptor
                             == "(Ljava/util/function/ToIntFunction;Ljava/lang/Object;)Lnet/minecraft/network/PacketByteBuf;" =>
                         {}
-                    unk => {
-                        panic!("PacketCodec: Unknown PacketByteBuf encode method \"{unk}\"")
-                    }
+                    unk => diagnostics.push(
+                        Diagnostic::new(format!(
+                            "PacketCodec: Unknown PacketByteBuf encode method \"{unk}\""
+                        ))
+                        .at(class, op),
+                    ),
                 }
             }
             Opcode::Invokevirtual(MemberRef { class_name, name_and_type })
@@ -362,16 +433,20 @@ impl Packets {
                     "readOptional" | "readNullable" => {}
                     _ty @ ("readBlockPos" | "readBlockHitResult" | "readBitSet"
                     | "readChunkPos" | "readUuid") => {}
-                    unk => {
-                        panic!("PacketCodec: Unknown PacketByteBuf decode method \"{unk}\"")
-                    }
+                    unk => diagnostics.push(
+                        Diagnostic::new(format!(
+                            "PacketCodec: Unknown PacketByteBuf decode method \"{unk}\""
+                        ))
+                        .at(class, op),
+                    ),
                 }
             }
             Opcode::Invokevirtual(MemberRef { class_name, name_and_type })
                 if name_and_type.descriptor.contains(PACKET_BYTE_BUF_TYPE)
                     && *direction == CodecDirection::Encode =>
             {
-                let field = Self::parse_class_method(class_name, &name_and_type.name, classes);
+                let field =
+                    Self::parse_class_method(class_name, &name_and_type.name, classes, diagnostics);
 
                 if let Some((name, field_type)) = last_fields.shift_remove(&*class.this_class)
                     && name_and_type.descriptor.contains(&field_type)
@@ -387,11 +462,10 @@ impl Packets {
                     && name_and_type.name != codec
                     && *direction == CodecDirection::Encode =>
             {
-                // println!("{}: Parsing \"{}.{}\"", class.this_class, class_name,
-                // name_and_type.name);
                 if let Some(class) = classes.get(&*class_name) {
                     fields.extend(
-                        Self::parse_class(class, Some(&name_and_type.name), classes).fields,
+                        Self::parse_class(class, Some(&name_and_type.name), classes, diagnostics)
+                            .fields,
                     );
                 }
             }
@@ -540,3 +614,41 @@ impl PacketField {
         }
     }
 }
+
+#[test]
+#[cfg(test)]
+fn unfollowed_codec() {
+    use cafebabe::{FieldAccessFlags, MethodAccessFlags};
+    use froglight_dependency::dependency::minecraft::{
+        class_builder::ClassBuilder, minecraft_code::CodeBundle,
+    };
+
+    // A packet whose `CODEC` is never assigned by the static initializer
+    let class = ClassBuilder::new("SetYawPacket")
+        .field(FieldAccessFlags::STATIC, "CODEC", "Lnet/minecraft/network/codec/PacketCodec;")
+        .field(FieldAccessFlags::FINAL, "entityId", "I")
+        .field(FieldAccessFlags::FINAL, "yaw", "F")
+        .method(MethodAccessFlags::STATIC, "<clinit>", "()V", |code| {
+            code.return_();
+        })
+        .build();
+    let bundle = CodeBundle::from_classes([(String::from("SetYawPacket"), class)]);
+
+    let mut diagnostics = Vec::new();
+    let info = bundle.with_classes(|classes| {
+        let class = classes.get("SetYawPacket").unwrap();
+        Packets::parse_class(class, Some("CODEC"), classes, &mut diagnostics)
+    });
+
+    // Fields of the class are kept as placeholders, with a diagnostic listing them.
+    assert_eq!(diagnostics.len(), 2);
+    assert!(diagnostics[1].message.ends_with("\"SetYawPacket\": entityId, yaw"));
+    assert_eq!(
+        info.fields().map(|(name, field)| (name.as_str(), field.clone())).collect::<Vec<_>>(),
+        [
+            ("entityId", PacketField::Other(String::from("i32"))),
+            ("yaw", PacketField::Other(String::from("f32")))
+        ]
+    );
+    assert!(info.fields().all(|(_, field)| field.print_field().2 == "()"));
+}
@@ -1,7 +1,9 @@
 use std::collections::HashMap;
 
 use froglight_dependency::{
-    container::DependencyContainer, dependency::minecraft::DataGenerator, version::Version,
+    container::DependencyContainer,
+    dependency::minecraft::{DataGenerator, GeneratorOutput},
+    version::Version,
 };
 use serde::Deserialize;
 use tokio::fs::File;
@@ -17,7 +19,7 @@ impl Registry {
         deps.get_or_retrieve::<DataGenerator>().await?;
         deps.scoped_fut::<DataGenerator, anyhow::Result<_>>(
             async |data: &mut DataGenerator, deps| {
-                let path = data.get_version(version, deps).await?;
+                let path = data.get_output(version, GeneratorOutput::Reports, deps).await?;
 
                 let file = File::open(path.join("reports/registries.json")).await?.into_std().await;
                 let raw: RawRegistryReport = serde_json::from_reader(&file)?;
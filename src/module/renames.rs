@@ -0,0 +1,257 @@
+use std::{collections::HashMap, fmt::Write};
+
+use froglight_dependency::{
+    container::DependencyContainer,
+    dependency::yarn::{TinyMappings, YarnMappings},
+    version::Version,
+};
+
+/// Renamed, added and removed classes and members between two versions.
+///
+/// Classes and members are matched using their intermediary names,
+/// which stay the same across versions.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct Renames {
+    pub(crate) classes: Vec<Change>,
+    pub(crate) fields: Vec<Change>,
+    pub(crate) methods: Vec<Change>,
+    /// Named classes in the first version to their intermediary names.
+    from_intermediary: HashMap<String, String>,
+    /// Intermediary classes to their named names in the second version.
+    to_named: HashMap<String, String>,
+}
+
+/// A change to a class or member between two versions.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum Change {
+    Renamed { from: String, to: String },
+    Added(String),
+    Removed(String),
+}
+
+/// Named classes and members of a version, keyed by intermediary name.
+#[derive(Default)]
+struct Named {
+    classes: HashMap<String, String>,
+    fields: HashMap<(String, String), String>,
+    methods: HashMap<(String, String, String), String>,
+    /// Intermediary method descriptors to named method descriptors.
+    descriptors: HashMap<String, String>,
+}
+
+impl Renames {
+    /// Compare the Yarn mappings of two versions.
+    ///
+    /// # Errors
+    /// Returns an error if the mappings could not be retrieved.
+    pub(crate) async fn between(
+        from: &Version,
+        to: &Version,
+        deps: &mut DependencyContainer,
+    ) -> anyhow::Result<Self> {
+        deps.get_or_retrieve::<YarnMappings>().await?;
+        deps.scoped_fut::<YarnMappings, anyhow::Result<Self>>(
+            async |mappings: &mut YarnMappings, deps| {
+                let from = mappings.get_version(from, deps).await?.read().await?;
+                let to = mappings.get_version(to, deps).await?.read().await?;
+                Self::new(&from, &to)
+            },
+        )
+        .await
+    }
+
+    /// Compare two sets of mappings.
+    ///
+    /// # Errors
+    /// Returns an error if either mappings are missing
+    /// the `intermediary` or `named` namespaces.
+    pub(crate) fn new(from: &TinyMappings, to: &TinyMappings) -> anyhow::Result<Self> {
+        let (from, to) = (Named::new(from)?, Named::new(to)?);
+
+        // Display members using the names of their classes in the newest version they
+        // exist in, so renamed classes do not rename all of their members.
+        Ok(Self {
+            classes: diff(&from.classes, &to.classes, |_, name| name.clone()),
+            fields: diff(&from.fields, &to.fields, |(owner, _), name| {
+                format!("{}.{name}", newest(owner, &from.classes, &to.classes))
+            }),
+            methods: diff(&from.methods, &to.methods, |(owner, _, descriptor), name| {
+                format!(
+                    "{}.{name}{}",
+                    newest(owner, &from.classes, &to.classes),
+                    newest(descriptor, &from.descriptors, &to.descriptors)
+                )
+            }),
+            from_intermediary: from.classes.into_iter().map(|(k, v)| (v, k)).collect(),
+            to_named: to.classes,
+        })
+    }
+
+    /// Find the name of a class from the first version in the second version.
+    ///
+    /// Returns `None` if the class does not exist in either version.
+    #[must_use]
+    pub(crate) fn find_class(&self, class: &str) -> Option<&str> {
+        let intermediary = self.from_intermediary.get(class)?;
+        self.to_named.get(intermediary).map(String::as_str)
+    }
+
+    /// Returns `true` if nothing was renamed, added or removed.
+    #[must_use]
+    pub(crate) fn is_empty(&self) -> bool {
+        self.classes.is_empty() && self.fields.is_empty() && self.methods.is_empty()
+    }
+
+    /// Render a report listing all changes.
+    #[must_use]
+    pub(crate) fn render(&self, from: &Version, to: &Version) -> String {
+        let mut report = format!("# Renames from {from} to {to}\n");
+
+        for (title, changes) in
+            [("Classes", &self.classes), ("Fields", &self.fields), ("Methods", &self.methods)]
+        {
+            if changes.is_empty() {
+                continue;
+            }
+
+            write!(report, "\n## {title}\n\n").unwrap();
+            for change in changes {
+                match change {
+                    Change::Renamed { from, to } => {
+                        writeln!(report, "- renamed `{from}` -> `{to}`")
+                    }
+                    Change::Added(name) => writeln!(report, "- added `{name}`"),
+                    Change::Removed(name) => writeln!(report, "- removed `{name}`"),
+                }
+                .unwrap();
+            }
+        }
+
+        report
+    }
+}
+
+impl Named {
+    fn new(mappings: &TinyMappings) -> anyhow::Result<Self> {
+        let namespace = |name: &str| {
+            mappings.namespace(name).ok_or_else(|| {
+                anyhow::anyhow!("Renames: Mappings are missing the `{name}` namespace")
+            })
+        };
+        let (intermediary, named) = (namespace("intermediary")?, namespace("named")?);
+        let intermediary_classes = mappings.class_map(0, intermediary);
+        let named_classes = mappings.class_map(0, named);
+
+        let mut result = Self::default();
+        for class in &mappings.classes {
+            let class_name = class.name(intermediary).to_string();
+            for field in &class.fields {
+                result.fields.insert(
+                    (class_name.clone(), field.name(intermediary).to_string()),
+                    field.name(named).to_string(),
+                );
+            }
+            for method in &class.methods {
+                let descriptor = method.remap_descriptor(&intermediary_classes);
+                result
+                    .descriptors
+                    .entry(descriptor.clone())
+                    .or_insert_with(|| method.remap_descriptor(&named_classes));
+                result.methods.insert(
+                    (class_name.clone(), method.name(intermediary).to_string(), descriptor),
+                    method.name(named).to_string(),
+                );
+            }
+            result.classes.insert(class_name, class.name(named).to_string());
+        }
+
+        Ok(result)
+    }
+}
+
+/// Get the named name of an intermediary name,
+/// preferring the second version.
+fn newest<'a>(
+    key: &'a String,
+    from: &'a HashMap<String, String>,
+    to: &'a HashMap<String, String>,
+) -> &'a str {
+    to.get(key).or_else(|| from.get(key)).unwrap_or(key)
+}
+
+/// Compare two maps of intermediary keys to named names.
+fn diff<K: Eq + std::hash::Hash>(
+    from: &HashMap<K, String>,
+    to: &HashMap<K, String>,
+    display: impl Fn(&K, &String) -> String,
+) -> Vec<Change> {
+    let mut changes = Vec::new();
+
+    for (key, name) in from {
+        match to.get(key) {
+            Some(renamed) if renamed != name => changes
+                .push(Change::Renamed { from: display(key, name), to: display(key, renamed) }),
+            Some(_) => {}
+            None => changes.push(Change::Removed(display(key, name))),
+        }
+    }
+    for (key, name) in to {
+        if !from.contains_key(key) {
+            changes.push(Change::Added(display(key, name)));
+        }
+    }
+
+    changes.sort_unstable();
+    changes
+}
+
+#[test]
+#[cfg(test)]
+fn renames() {
+    let from = TinyMappings::parse(
+        "tiny\t2\t0\tofficial\tintermediary\tnamed
+c\ta\tnet/minecraft/class_1\tnet/minecraft/network/NetworkStateBuilder
+\tm\t()V\tb\tmethod_1\tc2s
+\tm\t()V\tc\tmethod_2\ts2c
+c\tb\tnet/minecraft/class_2\tnet/minecraft/network/NetworkState$Factory
+",
+    )
+    .unwrap();
+    let to = TinyMappings::parse(
+        "tiny\t2\t0\tofficial\tintermediary\tnamed
+c\tc\tnet/minecraft/class_1\tnet/minecraft/network/state/NetworkStateBuilder
+\tm\t()V\ta\tmethod_1\tc2s
+\tm\t()V\tb\tmethod_3\tcontextAwareC2S
+c\td\tnet/minecraft/class_3\tnet/minecraft/network/state/NetworkStateFactory
+",
+    )
+    .unwrap();
+
+    let renames = Renames::new(&from, &to).unwrap();
+    assert_eq!(
+        renames.classes,
+        [
+            Change::Renamed {
+                from: String::from("net/minecraft/network/NetworkStateBuilder"),
+                to: String::from("net/minecraft/network/state/NetworkStateBuilder"),
+            },
+            Change::Added(String::from("net/minecraft/network/state/NetworkStateFactory")),
+            Change::Removed(String::from("net/minecraft/network/NetworkState$Factory")),
+        ]
+    );
+    assert_eq!(
+        renames.methods,
+        [
+            Change::Added(String::from(
+                "net/minecraft/network/state/NetworkStateBuilder.contextAwareC2S()V"
+            )),
+            Change::Removed(String::from("net/minecraft/network/state/NetworkStateBuilder.s2c()V")),
+        ]
+    );
+
+    assert_eq!(
+        renames.find_class("net/minecraft/network/NetworkStateBuilder"),
+        Some("net/minecraft/network/state/NetworkStateBuilder")
+    );
+    assert_eq!(renames.find_class("net/minecraft/network/NetworkState$Factory"), None);
+}
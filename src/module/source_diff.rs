@@ -0,0 +1,325 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    path::{Path, PathBuf},
+};
+
+use clap::ValueEnum;
+use froglight_dependency::{
+    container::DependencyContainer,
+    dependency::{minecraft::CodeSide, vineflower::DecompiledJar},
+    version::Version,
+};
+use similar::TextDiff;
+
+/// How to write a [`SourceDiff`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub(crate) enum DiffFormat {
+    /// A unified diff that can be applied with `patch` or `git apply`.
+    #[default]
+    Patch,
+    /// A single HTML page.
+    Html,
+}
+
+/// A unified diff of the decompiled sources of two versions.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct SourceDiff {
+    pub(crate) files: Vec<FileDiff>,
+}
+
+/// The changes to a single source file.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct FileDiff {
+    /// The path of the file, such as `net/minecraft/block/Blocks.java`.
+    pub(crate) path: String,
+    pub(crate) added: bool,
+    pub(crate) removed: bool,
+    /// The unified diff of the file, including the `---` and `+++` lines.
+    pub(crate) patch: String,
+}
+
+impl SourceDiff {
+    /// The number of unchanged lines to show around changes.
+    const CONTEXT: usize = 3;
+
+    /// Compare the decompiled sources of two versions.
+    ///
+    /// For [`CodeSide::Merged`], sources present in both
+    /// jars are taken from the client.
+    ///
+    /// If any packages are given, such as `net/minecraft/network/packet`,
+    /// only sources in those packages are compared.
+    ///
+    /// # Errors
+    /// Returns an error if either version could not be decompiled,
+    /// or the sources could not be read.
+    pub(crate) async fn between(
+        from: &Version,
+        to: &Version,
+        side: CodeSide,
+        packages: &[String],
+        deps: &mut DependencyContainer,
+    ) -> anyhow::Result<Self> {
+        deps.get_or_retrieve::<DecompiledJar>().await?;
+        let (from, to) = deps
+            .scoped_fut::<DecompiledJar, anyhow::Result<(Vec<PathBuf>, Vec<PathBuf>)>>(
+                async |jar: &mut DecompiledJar, deps| {
+                    let from = Self::roots(jar, from, side, deps).await?;
+                    let to = Self::roots(jar, to, side, deps).await?;
+                    Ok((from, to))
+                },
+            )
+            .await?;
+
+        let mut sources = [BTreeMap::new(), BTreeMap::new()];
+        for (roots, sources) in [from, to].iter().zip(&mut sources) {
+            for root in roots {
+                for (path, file) in Self::sources(root, packages).await? {
+                    sources.entry(path).or_insert(file);
+                }
+            }
+        }
+        let [from, to] = sources;
+
+        let mut paths = from.keys().chain(to.keys()).collect::<Vec<_>>();
+        paths.sort_unstable();
+        paths.dedup();
+
+        let mut diff = Self::default();
+        for path in paths {
+            let old = match from.get(path) {
+                Some(file) => Some(tokio::fs::read_to_string(file).await?),
+                None => None,
+            };
+            let new = match to.get(path) {
+                Some(file) => Some(tokio::fs::read_to_string(file).await?),
+                None => None,
+            };
+
+            if let Some(file) = FileDiff::new(path, old.as_deref(), new.as_deref()) {
+                diff.files.push(file);
+            }
+        }
+
+        Ok(diff)
+    }
+
+    /// Get the decompiled source directories for a [`CodeSide`].
+    async fn roots(
+        jar: &mut DecompiledJar,
+        version: &Version,
+        side: CodeSide,
+        deps: &mut DependencyContainer,
+    ) -> anyhow::Result<Vec<PathBuf>> {
+        let mut roots = Vec::new();
+        if matches!(side, CodeSide::Client | CodeSide::Merged) {
+            roots.push(jar.get_client(version, deps).await?.to_path_buf());
+        }
+        if matches!(side, CodeSide::Server | CodeSide::Merged) {
+            roots.push(jar.get_server(version, deps).await?.to_path_buf());
+        }
+        Ok(roots)
+    }
+
+    /// Find all source files in the given packages,
+    /// keyed by their path relative to `root`.
+    async fn sources(
+        root: &Path,
+        packages: &[String],
+    ) -> anyhow::Result<BTreeMap<String, PathBuf>> {
+        let mut directories = if packages.is_empty() {
+            vec![root.to_path_buf()]
+        } else {
+            packages.iter().map(|package| root.join(package.replace('.', "/"))).collect()
+        };
+
+        let mut sources = BTreeMap::new();
+        while let Some(directory) = directories.pop() {
+            if !tokio::fs::try_exists(&directory).await? {
+                continue;
+            }
+
+            let mut entries = tokio::fs::read_dir(&directory).await?;
+            while let Some(entry) = entries.next_entry().await? {
+                let path = entry.path();
+                if entry.file_type().await?.is_dir() {
+                    directories.push(path);
+                } else if path.extension().is_some_and(|ext| ext == "java") {
+                    let relative = path.strip_prefix(root)?.to_string_lossy().replace('\\', "/");
+                    sources.insert(relative, path);
+                }
+            }
+        }
+
+        Ok(sources)
+    }
+
+    /// Write the diff in the given format.
+    #[must_use]
+    pub(crate) fn render(&self, format: DiffFormat, from: &Version, to: &Version) -> String {
+        match format {
+            DiffFormat::Patch => self.render_patch(),
+            DiffFormat::Html => self.render_html(from, to),
+        }
+    }
+
+    /// Write the diff as a unified diff.
+    #[must_use]
+    pub(crate) fn render_patch(&self) -> String {
+        self.files.iter().map(|file| file.patch.as_str()).collect()
+    }
+
+    /// Write the diff as a single HTML page,
+    /// with a list of changed files at the top.
+    #[must_use]
+    pub(crate) fn render_html(&self, from: &Version, to: &Version) -> String {
+        let mut html = format!(
+            r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>Changes from {from} to {to}</title>
+<style>
+body {{ font-family: sans-serif; }}
+pre {{ background: #f6f8fa; padding: 0.5em; overflow-x: auto; }}
+.hunk {{ color: #6f42c1; }}
+.add {{ background: #e6ffec; display: block; }}
+.del {{ background: #ffebe9; display: block; }}
+</style>
+</head>
+<body>
+<h1>Changes from {from} to {to}</h1>
+<ul>
+"#
+        );
+
+        for (index, file) in self.files.iter().enumerate() {
+            let status = match (file.added, file.removed) {
+                (true, _) => " (added)",
+                (_, true) => " (removed)",
+                _ => "",
+            };
+            writeln!(html, "<li><a href=\"#file-{index}\">{}</a>{status}</li>", escape(&file.path))
+                .unwrap();
+        }
+        html.push_str("</ul>\n");
+
+        for (index, file) in self.files.iter().enumerate() {
+            write!(html, "<h2 id=\"file-{index}\"><code>{}</code></h2>\n<pre>", escape(&file.path))
+                .unwrap();
+            // Skip the `---` and `+++` lines, the file is named by the heading.
+            for line in file.patch.lines().skip(2) {
+                match line.as_bytes().first() {
+                    Some(b'@') => {
+                        writeln!(html, "<span class=\"hunk\">{}</span>", escape(line))
+                    }
+                    Some(b'-') => write!(html, "<span class=\"del\">{}</span>", escape(line)),
+                    Some(b'+') => write!(html, "<span class=\"add\">{}</span>", escape(line)),
+                    _ => writeln!(html, "{}", escape(line)),
+                }
+                .unwrap();
+            }
+            html.push_str("</pre>\n");
+        }
+
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+}
+
+impl FileDiff {
+    /// Compare two versions of a file.
+    ///
+    /// Returns `None` if the file did not change.
+    #[must_use]
+    pub(crate) fn new(path: &str, old: Option<&str>, new: Option<&str>) -> Option<Self> {
+        if old == new {
+            return None;
+        }
+
+        let source = old.map_or_else(|| String::from("/dev/null"), |_| format!("a/{path}"));
+        let target = new.map_or_else(|| String::from("/dev/null"), |_| format!("b/{path}"));
+        let unified = TextDiff::from_lines(old.unwrap_or_default(), new.unwrap_or_default())
+            .unified_diff()
+            .context_radius(SourceDiff::CONTEXT)
+            .header(&source, &target)
+            .to_string();
+
+        Some(Self {
+            path: path.to_string(),
+            added: old.is_none(),
+            removed: new.is_none(),
+            patch: unified,
+        })
+    }
+}
+
+/// Escape text for use in HTML.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+#[test]
+#[cfg(test)]
+fn source_diff() {
+    let old = "package net.minecraft.block;
+
+public class Blocks {
+   public static final Block STONE = register(\"stone\", Block::new);
+   public static final Block GRANITE = register(\"granite\", Block::new);
+   public static final Block DIORITE = register(\"diorite\", Block::new);
+   public static final Block ANDESITE = register(\"andesite\", Block::new);
+}
+";
+    let new = "package net.minecraft.block;
+
+public class Blocks {
+   public static final Block STONE = register(\"stone\", Block::new);
+   public static final Block GRANITE = register(\"granite\", GraniteBlock::new);
+   public static final Block DIORITE = register(\"diorite\", Block::new);
+   public static final Block ANDESITE = register(\"andesite\", Block::new);
+   public static final Block CALCITE = register(\"calcite\", Block::new);
+}
+";
+
+    assert_eq!(FileDiff::new("Blocks.java", Some(old), Some(old)), None);
+
+    let file = FileDiff::new("net/minecraft/block/Blocks.java", Some(old), Some(new)).unwrap();
+    let diff = SourceDiff { files: vec![file] };
+    assert_eq!(
+        diff.render_patch(),
+        "--- a/net/minecraft/block/Blocks.java
++++ b/net/minecraft/block/Blocks.java
+@@ -2,7 +2,8 @@\n \n public class Blocks {
+    public static final Block STONE = register(\"stone\", Block::new);
+-   public static final Block GRANITE = register(\"granite\", Block::new);
++   public static final Block GRANITE = register(\"granite\", GraniteBlock::new);
+    public static final Block DIORITE = register(\"diorite\", Block::new);
+    public static final Block ANDESITE = register(\"andesite\", Block::new);
++   public static final Block CALCITE = register(\"calcite\", Block::new);
+ }
+"
+    );
+
+    let added = FileDiff::new("Calcite.java", None, Some("class Calcite {}\n")).unwrap();
+    assert!(added.added);
+    assert_eq!(
+        added.patch,
+        "--- /dev/null\n+++ b/Calcite.java\n@@ -0,0 +1 @@\n+class Calcite {}\n"
+    );
+
+    // Files without a trailing newline are marked as such.
+    let changed = FileDiff::new("Calcite.java", Some("class Calcite {}"), Some("class Tuff {}"));
+    assert_eq!(
+        changed.unwrap().patch,
+        "--- a/Calcite.java
++++ b/Calcite.java
+@@ -1 +1 @@
+-class Calcite {}
+\\ No newline at end of file
++class Tuff {}
+\\ No newline at end of file
+"
+    );
+}
@@ -0,0 +1,170 @@
+use std::{cmp::Ordering, collections::HashMap};
+
+use froglight_dependency::{
+    container::{Dependency, DependencyContainer},
+    dependency::mojang::VersionManifest,
+    version::Version,
+};
+
+/// A table of logical names for Minecraft classes, methods and fields,
+/// resolved to the names used in a specific version.
+///
+/// Symbols are loaded from `symbols.toml`,
+/// so supporting a renamed class only requires adding an entry.
+#[derive(Debug, Clone, PartialEq, Eq, Dependency)]
+#[dep(retrieve = Self::retrieve)]
+pub(crate) struct Symbols {
+    symbols: HashMap<String, Vec<(Version, String)>>,
+    /// Used to order snapshots relative to other versions.
+    manifest: Option<VersionManifest>,
+}
+
+impl Symbols {
+    const SYMBOLS: &str = include_str!("symbols.toml");
+
+    async fn retrieve(deps: &mut DependencyContainer) -> anyhow::Result<Self> {
+        let manifest = match deps.get_or_retrieve::<VersionManifest>().await {
+            Ok(manifest) => Some(manifest.clone()),
+            Err(err) => {
+                tracing::warn!("Symbols: Could not retrieve the version manifest: {err}");
+                None
+            }
+        };
+
+        Self::parse(Self::SYMBOLS, manifest)
+    }
+
+    /// Parse a symbol table.
+    ///
+    /// # Errors
+    /// Returns an error if the table could not be parsed.
+    fn parse(content: &str, manifest: Option<VersionManifest>) -> anyhow::Result<Self> {
+        let table: HashMap<String, HashMap<Version, String>> = toml_edit::de::from_str(content)
+            .map_err(|err| anyhow::anyhow!("Symbols: Failed to parse symbol table: {err}"))?;
+
+        let symbols = table.into_iter().map(|(k, v)| (k, v.into_iter().collect())).collect();
+        Ok(Self { symbols, manifest })
+    }
+
+    /// Get the name of a symbol in the given version.
+    ///
+    /// Returns `None` if the symbol is unknown,
+    /// does not exist in the version, or the version could not be ordered.
+    #[must_use]
+    pub(crate) fn get(&self, symbol: &str, version: &Version) -> Option<&str> {
+        let Some(entries) = self.symbols.get(symbol) else {
+            tracing::warn!("Symbols: Unknown symbol \"{symbol}\"");
+            return None;
+        };
+
+        let mut earliest: Option<&(Version, String)> = None;
+        let mut resolved: Option<&(Version, String)> = None;
+        for entry in entries {
+            if earliest.is_none_or(|e| self.compare(&entry.0, &e.0) == Some(Ordering::Less)) {
+                earliest = Some(entry);
+            }
+            if self.compare(&entry.0, version).is_some_and(Ordering::is_le)
+                && resolved.is_none_or(|r| self.compare(&r.0, &entry.0) == Some(Ordering::Less))
+            {
+                resolved = Some(entry);
+            }
+        }
+
+        // Versions older than every entry use the earliest name.
+        if resolved.is_none()
+            && let Some(entry) = earliest
+            && self.compare(version, &entry.0) == Some(Ordering::Less)
+        {
+            resolved = Some(entry);
+        }
+
+        match resolved {
+            Some((_, name)) if name.is_empty() => None,
+            Some((_, name)) => Some(name),
+            None => {
+                tracing::warn!("Symbols: Could not resolve \"{symbol}\" for {version}");
+                None
+            }
+        }
+    }
+
+    /// Get the name of a symbol in the given version.
+    ///
+    /// # Errors
+    /// Returns an error if the symbol could not be resolved.
+    pub(crate) fn require(&self, symbol: &str, version: &Version) -> anyhow::Result<&str> {
+        self.get(symbol, version).ok_or_else(|| {
+            anyhow::anyhow!("Symbols: Could not resolve \"{symbol}\" for {version}")
+        })
+    }
+
+    /// Get the field descriptor of a class symbol in the given version.
+    ///
+    /// # Errors
+    /// Returns an error if the symbol could not be resolved.
+    pub(crate) fn require_descriptor(
+        &self,
+        symbol: &str,
+        version: &Version,
+    ) -> anyhow::Result<String> {
+        self.require(symbol, version).map(|class| format!("L{class};"))
+    }
+
+    /// Get the field descriptor of a class symbol in the given version,
+    /// such as `Lnet/minecraft/network/packet/PacketType;`.
+    #[must_use]
+    pub(crate) fn descriptor(&self, symbol: &str, version: &Version) -> Option<String> {
+        self.get(symbol, version).map(|class| format!("L{class};"))
+    }
+
+    /// Compare two versions.
+    ///
+    /// Releases, release candidates and pre-releases are ordered by their
+    /// version numbers, anything else is ordered by the version manifest.
+    fn compare(&self, a: &Version, b: &Version) -> Option<Ordering> {
+        match (a, b) {
+            (
+                Version::Release(a) | Version::ReleaseCandidate(a) | Version::PreRelease(a),
+                Version::Release(b) | Version::ReleaseCandidate(b) | Version::PreRelease(b),
+            ) => a.partial_cmp(b),
+            _ => a.compare_relative(b).or_else(|| self.manifest.as_ref()?.compare(a, b)),
+        }
+    }
+}
+
+#[test]
+#[cfg(test)]
+fn symbols() {
+    let symbols = Symbols::parse(
+        r#"
+[builder]
+"1.21.5" = "net/minecraft/network/state/NetworkStateBuilder"
+"1.21.4" = "net/minecraft/network/NetworkStateBuilder"
+
+[factory]
+"1.21.4" = ""
+"1.21.5" = "net/minecraft/network/state/ContextAwareNetworkStateFactory"
+"#,
+        None,
+    )
+    .unwrap();
+    assert!(Symbols::parse(Symbols::SYMBOLS, None).is_ok());
+
+    let old = Some("net/minecraft/network/NetworkStateBuilder");
+    let new = Some("net/minecraft/network/state/NetworkStateBuilder");
+
+    assert_eq!(symbols.get("builder", &Version::new_release(1, 21, 1)), old);
+    assert_eq!(symbols.get("builder", &Version::new_release(1, 21, 4)), old);
+    assert_eq!(symbols.get("builder", &Version::new_pre(1, 21, 5, 1)), old);
+    assert_eq!(symbols.get("builder", &Version::new_release(1, 21, 5)), new);
+    assert_eq!(symbols.get("builder", &Version::new_release(1, 21, 6)), new);
+    assert_eq!(symbols.get("builder", &Version::new_snapshot(25, 2, 'a').unwrap()), None);
+    assert_eq!(symbols.get("missing", &Version::new_release(1, 21, 5)), None);
+    assert!(symbols.require("missing", &Version::new_release(1, 21, 5)).is_err());
+
+    assert_eq!(symbols.descriptor("factory", &Version::new_release(1, 21, 4)), None);
+    assert_eq!(
+        symbols.descriptor("factory", &Version::new_release(1, 21, 5)).as_deref(),
+        Some("Lnet/minecraft/network/state/ContextAwareNetworkStateFactory;")
+    );
+}